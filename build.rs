@@ -83,6 +83,23 @@ struct TaFunc {
   params: Vec<TaType>,
   /// function documentation
   doc: String,
+  /// number of output arrays (`&mut [..]`), always leading the array params
+  outputs: usize,
+}
+
+/// Whether the arrays need the generic dispatch, the dedicated ones only
+/// cover single float outputs plus the CROSS (bool, num, num) shape, with a
+/// bool condition as the last input of SUMIF or the only input.
+fn use_generic(func: &TaFunc, arrays: &[(&String, &TaType)]) -> bool {
+  let is_bool: Vec<bool> = arrays
+    .iter()
+    .map(|(_, ty)| matches!(ty, TaType::BoolArray(_)))
+    .collect();
+  let dedicated = matches!(
+    is_bool.as_slice(),
+    [false, _] | [true, false, false] | [false, false, _] | [false, false, false, false]
+  );
+  func.outputs > 1 || arrays.len() > 4 || !dedicated
 }
 
 fn parse_ta_file<P: AsRef<Path>>(file_name: P) -> Result<Vec<TaFunc>> {
@@ -102,7 +119,8 @@ fn parse_ta_file<P: AsRef<Path>>(file_name: P) -> Result<Vec<TaFunc>> {
       continue;
     }
 
-    if trimmed.is_empty() {
+    // empty lines and attributes between doc and function keep the doc
+    if trimmed.is_empty() || trimmed.starts_with("#[") {
       continue;
     }
 
@@ -151,12 +169,16 @@ fn parse_ta_file<P: AsRef<Path>>(file_name: P) -> Result<Vec<TaFunc>> {
 
       let args_str = &full_decl[args_start + 1..args_end];
       let mut params = Vec::new();
+      let mut outputs = 0;
       if !args_str.trim().is_empty() {
         for arg in args_str.split(',') {
           let arg = arg.trim();
           if arg.is_empty() {
             continue;
           }
+          if arg.contains("&mut [") {
+            outputs += 1;
+          }
           params.push(TaType::try_from(arg)?);
         }
       }
@@ -165,6 +187,7 @@ fn parse_ta_file<P: AsRef<Path>>(file_name: P) -> Result<Vec<TaFunc>> {
         name,
         params,
         doc: std::mem::take(&mut current_doc),
+        outputs,
       });
     } else {
       current_doc.clear();
@@ -173,6 +196,96 @@ fn parse_ta_file<P: AsRef<Path>>(file_name: P) -> Result<Vec<TaFunc>> {
   Ok(functions)
 }
 
+/// Generate dispatch body for functions without a dedicated shape, see `use_generic`.
+///
+/// Every `NumArray` is extracted as the same float type (f64 first, then f32),
+/// `BoolArray` is always extracted as bool.
+fn build_generic_binding(
+  code: &mut String,
+  func: &TaFunc,
+  arrays: &[(&String, &TaType)],
+) -> Result<()> {
+  let outputs = func.outputs.max(1);
+  for (branch, type_name) in ["f64", "f32"].iter().enumerate() {
+    let mut pats = Vec::new();
+    let mut exprs = Vec::new();
+    for (k, (name, ty)) in arrays.iter().enumerate() {
+      let elem = match ty {
+        TaType::BoolArray(_) => "bool",
+        _ => type_name,
+      };
+      if k < outputs {
+        pats.push(format!("Ok(mut {})", name));
+        exprs.push(format!(
+          "{}.extract::<PyReadwriteArray1<'py, {}>>()",
+          name, elem
+        ));
+      } else {
+        pats.push(format!("Ok({})", name));
+        exprs.push(format!(
+          "{}.extract::<PyReadonlyArray1<'py, {}>>()",
+          name, elem
+        ));
+      }
+    }
+    writeln!(
+      code,
+      "    {}if let ({}) = (",
+      if branch == 0 { "" } else { "} else " },
+      pats.join(", ")
+    )?;
+    for expr in &exprs {
+      writeln!(code, "      {},", expr)?;
+    }
+    writeln!(code, "    ) {{")?;
+    for (k, (name, _)) in arrays.iter().enumerate() {
+      if k < outputs {
+        writeln!(code, "      let mut {} = {}.as_array_mut();", name, name)?;
+        writeln!(
+          code,
+          "      let {} = {}.as_slice_mut().ok_or(PyValueError::new_err(\"failed to get mutable slice\"))?;",
+          name, name
+        )?;
+      } else {
+        writeln!(code, "      let {} = {}.as_array();", name, name)?;
+        writeln!(
+          code,
+          "      let {} = {}.as_slice().ok_or(PyValueError::new_err(\"failed to get slice\"))?;",
+          name, name
+        )?;
+      }
+    }
+    let mut args = Vec::new();
+    for param in &func.params {
+      match param {
+        TaType::Context(_) => args.push("&ctx".to_string()),
+        TaType::NumArray(n) | TaType::BoolArray(n) => args.push(n.clone()),
+        TaType::Num(n) => {
+          if *type_name == "f32" {
+            args.push(format!("{} as f32", n));
+          } else {
+            args.push(n.clone());
+          }
+        }
+        TaType::Int(n) => args.push(n.clone()),
+        _ => {}
+      }
+    }
+    writeln!(
+      code,
+      "      ta_{}({}).map_err(|e| e.into())",
+      func.name,
+      args.join(", ")
+    )?;
+  }
+  writeln!(
+    code,
+    "    }} else {{ Err(PyValueError::new_err(\"invalid input\")) }}"
+  )?;
+  writeln!(code, "  }}")?;
+  Ok(())
+}
+
 fn build_py_bindings(functions: &[TaFunc]) -> Result<()> {
   let out_dir = env::var("OUT_DIR")?;
   let mut file = fs::File::create(out_dir + "/algo_bindings.rs")?;
@@ -230,6 +343,10 @@ fn build_py_bindings(functions: &[TaFunc]) -> Result<()> {
       func.doc.lines().next().unwrap_or("").trim()
     )?;
     writeln!(code, "  #[pyfunction]")?;
+    // `py` takes the place of the context
+    if func.params.len() > 7 {
+      writeln!(code, "  #[allow(clippy::too_many_arguments)]")?;
+    }
     if py_func_name == "ref" {
       writeln!(code, "  fn r#{}<'py>(", py_func_name)?;
     } else {
@@ -267,6 +384,12 @@ fn build_py_bindings(functions: &[TaFunc]) -> Result<()> {
       continue;
     }
 
+    // Multiple outputs, more than 4 arrays or bool outputs: generic dispatch
+    if use_generic(func, &arrays) {
+      build_generic_binding(&mut code, func, &arrays)?;
+      continue;
+    }
+
     if arrays.len() == 4 {
       let r_name = arrays[0].0;
       let a_name = arrays[1].0;
@@ -1449,6 +1572,90 @@ fn py_convert_list(ty: &TaType, name: &str) -> String {
   }
 }
 
+/// Generate python wrapper for functions without a dedicated shape, see `use_generic`.
+///
+/// Outputs are allocated like the first input array and returned as a tuple
+/// when there is more than one. List inputs are not supported here.
+fn build_generic_algo_py(
+  file: &mut fs::File,
+  func: &TaFunc,
+  arrays: &[(&String, &TaType)],
+  doc: &str,
+) -> Result<()> {
+  let outputs = func.outputs.max(1);
+  let (outs, ins) = arrays.split_at(outputs);
+
+  let mut py_params = Vec::new();
+  let mut call_params = Vec::new();
+  for param in &func.params {
+    match param {
+      TaType::NumArray(n) | TaType::BoolArray(n) => {
+        if ins.iter().any(|(name, _)| *name == n) {
+          py_params.push(format!("{}: np.ndarray", n));
+        }
+        call_params.push(n.as_str());
+      }
+      TaType::Num(n) => {
+        py_params.push(format!("{}: float", n));
+        call_params.push(n);
+      }
+      TaType::Int(n) => {
+        py_params.push(format!("{}: int", n));
+        call_params.push(n);
+      }
+      _ => {}
+    }
+  }
+
+  writeln!(file, "def {}(", func.name.to_uppercase())?;
+  writeln!(file, "  {}", py_params.join(", "))?;
+  if outs.len() > 1 {
+    writeln!(
+      file,
+      ") -> tuple[{}]:",
+      vec!["np.ndarray"; outs.len()].join(", ")
+    )?;
+  } else {
+    writeln!(file, ") -> np.ndarray:")?;
+  }
+  if !doc.trim().is_empty() {
+    writeln!(file, "  \"\"\"")?;
+    writeln!(file, "{}", doc)?;
+    writeln!(file, "  \"\"\"")?;
+  }
+  for (name, ty) in ins {
+    writeln!(file, "  {} = {}", name, py_convert(ty, name))?;
+  }
+  let like = ins
+    .iter()
+    .find(|(_, ty)| matches!(ty, TaType::NumArray(_)))
+    .or(ins.first())
+    .map(|(name, _)| name.as_str())
+    .ok_or(anyhow!("{} has no input array", func.name))?;
+  for (name, ty) in outs {
+    match ty {
+      TaType::BoolArray(_) => writeln!(file, "  {} = np.empty_like({}, dtype=bool)", name, like)?,
+      _ => writeln!(
+        file,
+        "  {} = np.empty_like({}, dtype=np.float64)",
+        name, like
+      )?,
+    }
+  }
+  writeln!(file, "  _algo.{}({})", func.name, call_params.join(", "))?;
+  writeln!(
+    file,
+    "  return {}",
+    outs
+      .iter()
+      .map(|(name, _)| name.as_str())
+      .collect::<Vec<_>>()
+      .join(", ")
+  )?;
+  writeln!(file)?;
+  Ok(())
+}
+
 fn build_algo_py(functions: &[TaFunc]) -> Result<()> {
   let out_file = "python/alpha/algo/algo_gen.py";
   let mut file = fs::File::create(out_file)?;
@@ -1508,6 +1715,11 @@ fn build_algo_py(functions: &[TaFunc]) -> Result<()> {
       continue;
     }
 
+    if use_generic(func, &arrays) {
+      build_generic_algo_py(&mut file, func, &arrays, &doc)?;
+      continue;
+    }
+
     if arrays.len() == 4 {
       let r_name = arrays[0].0;
       let a_name = arrays[1].0;
@@ -1793,7 +2005,7 @@ fn build_algo_md(functions: &Vec<TaFunc>) -> Result<()> {
       func
         .params
         .iter()
-        .skip(2)
+        .skip(1 + func.outputs.max(1))
         .map(|p| p.to_py_type_str())
         .collect::<Vec<_>>()
         .join(", "),
@@ -1828,7 +2040,19 @@ fn main() -> Result<()> {
   build_algo_md(&functions)?;
 
   // skip ema, we will write it as template by hand
-  functions.retain(|f| f.name != "ema");
+  // cov_matrix, pca, the ic decays and layer_backtest return non-panel shapes, their bindings
+  // are also written by hand
+  functions.retain(|f| {
+    ![
+      "ema",
+      "cov_matrix",
+      "pca",
+      "ic_decay",
+      "rank_ic_decay",
+      "layer_backtest",
+    ]
+    .contains(&f.name.as_str())
+  });
   build_py_bindings(&functions)?;
   build_algo_py(&functions)?;
 
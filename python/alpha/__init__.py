@@ -10,3 +10,44 @@ from .context import ExecContext
 FLAG_SKIP_NAN: int = 1
 # strictly cycle over the data
 FLAG_STRICTLY_CYCLE: int = 2
+
+# bins by equal-width value ranges instead of equal counts
+BINS_EQUAL_WIDTH: int = 1
+# bins index starts from 1 instead of 0
+BINS_ONE_BASED: int = 2
+
+# rank methods for ties, combine with RANK_PCT / RANK_DESCENDING
+RANK_AVERAGE: int = 0
+RANK_MIN: int = 1
+RANK_MAX: int = 2
+RANK_DENSE: int = 3
+RANK_ORDINAL: int = 4
+# output rank percentage instead of rank
+RANK_PCT: int = 8
+# rank in descending order
+RANK_DESCENDING: int = 16
+
+# covariance estimators of COV_MATRIX
+COV_SAMPLE: int = 0
+# Ledoit-Wolf shrinkage towards scaled identity
+COV_LW_IDENTITY: int = 1
+# Ledoit-Wolf shrinkage towards constant correlation
+COV_LW_CONST_CORR: int = 2
+
+# EWM_* decay parameter: smoothing factor alpha
+EWM_ALPHA: int = 0
+# decay is the span, alpha = 2 / (span + 1)
+EWM_SPAN: int = 1
+# decay is the halflife, alpha = 1 - exp(-ln(2) / halflife)
+EWM_HALFLIFE: int = 2
+# decay is the center of mass, alpha = 1 / (1 + com)
+EWM_COM: int = 3
+# pandas adjust=True
+EWM_ADJUST: int = 4
+# pandas bias=True
+EWM_BIAS: int = 8
+
+# signal line smoothing of MACD / PPO / TRIX: exponential moving average
+SIGNAL_EMA: int = 0
+# signal line smoothing: simple moving average
+SIGNAL_SMA: int = 1
@@ -1,6 +1,6 @@
 # Copyright 2026 MSD-RS Project LiJia
 # SPDX-License-Identifier: BSD-2-Clause
 
-from .algo import EMA
+from .algo import EMA, COV_MATRIX, PCA, IC_DECAY, RANK_IC_DECAY, LAYER_BACKTEST
 from .algo_gen import *
 from ._algo import set_ctx
@@ -30,3 +30,122 @@ def EMA(
     r = np.empty_like(input)
     _algo.ema(r, input, period)
     return r
+
+
+def IC_DECAY(factor: np.ndarray, price: np.ndarray, horizons: int) -> np.ndarray:
+  """
+  IC decay
+
+  IC between factor and forward returns `price[t+h] / price[t] - 1` for
+  horizons `h = 1..=horizons`.
+
+  Args:
+    factor: factor panel
+    price: price panel
+    horizons: number of forward horizons
+
+  Returns:
+    (horizons, T) IC matrix, row h-1 is horizon h
+  """
+  if factor.dtype != np.float64:
+    factor = factor.astype(np.float64)
+  if price.dtype != np.float64:
+    price = price.astype(np.float64)
+  return _algo.ic_decay(factor, price, horizons, False)
+
+
+def RANK_IC_DECAY(factor: np.ndarray, price: np.ndarray, horizons: int) -> np.ndarray:
+  """
+  RankIC decay
+
+  Same as `IC_DECAY` but uses Spearman correlation.
+
+  Returns:
+    (horizons, T) RankIC matrix, row h-1 is horizon h
+  """
+  if factor.dtype != np.float64:
+    factor = factor.astype(np.float64)
+  if price.dtype != np.float64:
+    price = price.astype(np.float64)
+  return _algo.ic_decay(factor, price, horizons, True)
+
+
+def LAYER_BACKTEST(
+  factor: np.ndarray, returns: np.ndarray, weights: np.ndarray, layers: int
+) -> tuple[np.ndarray, np.ndarray, np.ndarray, np.ndarray]:
+  """
+  Quantile portfolio (layered) backtest
+
+  At each date the securities are split into `layers` buckets by factor,
+  layer 0 holds the lowest factor values, each layer is weighted equally or
+  by `weights` when it is not empty.
+
+  Args:
+    factor: factor panel
+    returns: one period forward returns panel
+    weights: weights panel (e.g. market value), empty for equal weights
+    layers: number of layers
+
+  Returns:
+    (layers, T) layer returns, (layers, T) NAV, (layers, T) turnover and
+    the T top minus bottom spread series
+  """
+  if factor.dtype != np.float64:
+    factor = factor.astype(np.float64)
+  if returns.dtype != np.float64:
+    returns = returns.astype(np.float64)
+  if weights.dtype != np.float64:
+    weights = weights.astype(np.float64)
+  return _algo.layer_backtest(factor, returns, weights, layers)
+
+
+def COV_MATRIX(
+  input: np.ndarray,
+  dates: list[int] | np.ndarray,
+  periods: int,
+  halflife: float = 0.0,
+  target: int = 0,
+) -> tuple[np.ndarray, np.ndarray]:
+  """
+  Covariance matrix of securities at chosen dates
+
+  For each date in `dates` (time index), computes the N x N covariance of the
+  securities over the last `periods` dates, N is the groups of context.
+
+  Args:
+    input: returns panel
+    dates: time indexes to compute the covariance at
+    periods: lookback window
+    halflife: exponential weighting halflife, 0 means equal weights
+    target: COV_SAMPLE, COV_LW_IDENTITY or COV_LW_CONST_CORR
+
+  Returns:
+    (dates, N, N) covariance matrices and the shrinkage intensity of each date
+  """
+  if input.dtype != np.float64:
+    input = input.astype(np.float64)
+  dates = [int(d) for d in dates]
+  return _algo.cov_matrix(input, dates, periods, halflife, target)
+
+
+def PCA(
+  input: np.ndarray, periods: int, k: int
+) -> tuple[np.ndarray, np.ndarray, np.ndarray, np.ndarray]:
+  """
+  Statistical factor model by rolling PCA
+
+  At each date, extracts the top `k` principal components of the covariance
+  of the securities over the last `periods` dates.
+
+  Args:
+    input: returns panel
+    periods: lookback window
+    k: number of components
+
+  Returns:
+    (k, T) eigen-factor returns, (k, T) explained variance ratios,
+    (k, N x T) loading panels and the residual panel
+  """
+  if input.dtype != np.float64:
+    input = input.astype(np.float64)
+  return _algo.pca(input, periods, k)
@@ -18,6 +18,68 @@ def _to_bool(a):
     return a
   return a.astype(bool)
 
+def AD(
+  high: np.ndarray, low: np.ndarray, close: np.ndarray, volume: np.ndarray
+) -> np.ndarray:
+  """
+  Accumulation / Distribution Line
+  
+  AD = AD[-1] + CLV * volume, CLV = ((close - low) - (high - close)) /
+  (high - low) and 0 when high == low. The running total restarts after a
+  missing bar unless FLAG_SKIP_NAN.
+  
+  Ref: https://www.investopedia.com/terms/a/accumulationdistribution.asp
+  """
+  high = _to_f64(high)
+  low = _to_f64(low)
+  close = _to_f64(close)
+  volume = _to_f64(volume)
+  r = np.empty_like(high, dtype=np.float64)
+  _algo.ad(r, high, low, close, volume)
+  return r
+
+def ADOSC(
+  high: np.ndarray, low: np.ndarray, close: np.ndarray, volume: np.ndarray, fast: int, slow: int
+) -> np.ndarray:
+  """
+  Chaikin A/D Oscillator
+  
+  ADOSC = EMA(AD, fast) - EMA(AD, slow), both EMAs are seeded with the first
+  AD value and the first max(fast, slow) - 1 bars are NaN as TA-Lib.
+  
+  Ref: https://www.investopedia.com/terms/c/chaikinoscillator.asp
+  """
+  high = _to_f64(high)
+  low = _to_f64(low)
+  close = _to_f64(close)
+  volume = _to_f64(volume)
+  r = np.empty_like(high, dtype=np.float64)
+  _algo.adosc(r, high, low, close, volume, fast, slow)
+  return r
+
+def ALMA(
+  input: np.ndarray | list[np.ndarray], periods: int, offset: float, sigma: float
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Arnaud Legoux Moving Average
+  
+  ALMA = sum(w_i * input_i) / sum(w_i) over the last `periods` bars,
+  w_i = exp(-(i - offset * (periods - 1))^2 / (2 * (periods / sigma)^2)) with
+  i = 0 for the oldest bar. Usually offset = 0.85 and sigma = 6.
+  
+  Ref: https://www.prorealcode.com/prorealtime-indicators/alma-arnaud-legoux-moving-average/
+  """
+  if isinstance(input, list):
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in input]
+    _algo.alma(r, input, periods, offset, sigma)
+    return r
+  else:
+    input = _to_f64(input)
+    r = np.empty_like(input)
+    _algo.alma(r, input, periods, offset, sigma)
+    return r
+
 def ALPHA(
   input: np.ndarray | list[np.ndarray], benchmark: np.ndarray | list[np.ndarray], periods: int
 ) -> np.ndarray | list[np.ndarray]:
@@ -42,6 +104,77 @@ def ALPHA(
     _algo.alpha(r, input, benchmark, periods)
     return r
 
+def APO(
+  input: np.ndarray | list[np.ndarray], fast: int, slow: int
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Absolute Price Oscillator
+  
+  APO = EMA(input, fast) - EMA(input, slow), the MACD line alone, the first
+  max(fast, slow) - 1 values are NaN. Same as TA-Lib APO with matype = 1
+  (EMA).
+  
+  Ref: https://www.fidelity.com/learning-center/trading-investing/technical-analysis/technical-indicator-guide/apo
+  """
+  if isinstance(input, list):
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in input]
+    _algo.apo(r, input, fast, slow)
+    return r
+  else:
+    input = _to_f64(input)
+    r = np.empty_like(input)
+    _algo.apo(r, input, fast, slow)
+    return r
+
+def AROON(
+  high: np.ndarray, low: np.ndarray, periods: int
+) -> tuple[np.ndarray, np.ndarray, np.ndarray]:
+  """
+  Aroon Indicator
+  
+  up = 100 * (periods - bars since the highest high) / periods over the last
+  `periods + 1` bars, down likewise with the lowest low, oscillator is up -
+  down, same as TA-Lib AROON and AROONOSC after the warm-up.
+  
+  Ref: https://www.investopedia.com/terms/a/aroon.asp
+  """
+  high = _to_f64(high)
+  low = _to_f64(low)
+  up = np.empty_like(high, dtype=np.float64)
+  down = np.empty_like(high, dtype=np.float64)
+  osc = np.empty_like(high, dtype=np.float64)
+  _algo.aroon(up, down, osc, high, low, periods)
+  return up, down, osc
+
+def ATR(
+  high: np.ndarray | list[np.ndarray], low: np.ndarray | list[np.ndarray], close: np.ndarray | list[np.ndarray], periods: int
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Average True Range
+  
+  Wilder's smoothing of TR, ATR = (ATR[-1] * (periods - 1) + TR) / periods,
+  seeded with the mean of the first `periods` true ranges as TA-Lib. Without
+  FLAG_STRICTLY_CYCLE the mean of the true ranges so far is output during the
+  warm-up.
+  
+  Ref: https://www.investopedia.com/terms/a/atr.asp
+  """
+  if isinstance(high, list) and isinstance(low, list) and isinstance(close, list):
+    high = [_to_f64(x) for x in high]
+    low = [_to_f64(x) for x in low]
+    close = [_to_f64(x) for x in close]
+    r = [np.empty_like(x) for x in high]
+    _algo.atr(r, high, low, close, periods)
+    return r
+  else:
+    high = _to_f64(high)
+    low = _to_f64(low)
+    close = _to_f64(close)
+    r = np.empty_like(high)
+    _algo.atr(r, high, low, close, periods)
+    return r
+
 def BACKFILL(
   input: np.ndarray | list[np.ndarray]
 ) -> np.ndarray | list[np.ndarray]:
@@ -81,6 +214,26 @@ def BARSLAST(
     _algo.barslast(r, input)
     return r
 
+def BARSLASTCOUNT(
+  cond: np.ndarray | list[np.ndarray]
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Number of consecutive bars up to the current one where `cond` is true, 0
+  when `cond` is false
+  
+  Ref: https://www.amibroker.com/guide/afl/barslastcount.html
+  """
+  if isinstance(cond, list):
+    r = [np.empty_like(x, dtype=float) for x in cond]
+    cond = [x.astype(bool) for x in cond]
+    _algo.barslastcount(r, cond)
+    return r
+  else:
+    r = np.empty_like(cond, dtype=float)
+    cond = cond.astype(bool)
+    _algo.barslastcount(r, cond)
+    return r
+
 def BARSSINCE(
   input: np.ndarray | list[np.ndarray]
 ) -> np.ndarray | list[np.ndarray]:
@@ -100,6 +253,27 @@ def BARSSINCE(
     _algo.barssince(r, input)
     return r
 
+def BBANDS(
+  input: np.ndarray, periods: int, k: float
+) -> tuple[np.ndarray, np.ndarray, np.ndarray, np.ndarray, np.ndarray]:
+  """
+  Bollinger Bands
+  
+  middle = MA(input, periods), upper / lower = middle +/- k * STD(input,
+  periods) with the population stddev as TA-Lib BBANDS,
+  %B = (input - lower) / (upper - lower), bandwidth = (upper - lower) / middle.
+  
+  Ref: https://www.investopedia.com/terms/b/bollingerbands.asp
+  """
+  input = _to_f64(input)
+  upper = np.empty_like(input, dtype=np.float64)
+  middle = np.empty_like(input, dtype=np.float64)
+  lower = np.empty_like(input, dtype=np.float64)
+  percent_b = np.empty_like(input, dtype=np.float64)
+  bandwidth = np.empty_like(input, dtype=np.float64)
+  _algo.bbands(upper, middle, lower, percent_b, bandwidth, input, periods, k)
+  return upper, middle, lower, percent_b, bandwidth
+
 def BETA(
   input: np.ndarray | list[np.ndarray], benchmark: np.ndarray | list[np.ndarray], periods: int
 ) -> np.ndarray | list[np.ndarray]:
@@ -124,6 +298,20 @@ def BETA(
     _algo.beta(r, input, benchmark, periods)
     return r
 
+def BETWEEN(
+  a: np.ndarray, b: np.ndarray, c: np.ndarray
+) -> np.ndarray:
+  """
+  通达信 BETWEEN(A, B, C), true when `a` is between `b` and `c` inclusive,
+  in either order, false on NaN
+  """
+  a = _to_f64(a)
+  b = _to_f64(b)
+  c = _to_f64(c)
+  r = np.empty_like(a, dtype=bool)
+  _algo.between(r, a, b, c)
+  return r
+
 def BINS(
   input: np.ndarray | list[np.ndarray], bins: int
 ) -> np.ndarray | list[np.ndarray]:
@@ -144,12 +332,55 @@ def BINS(
     _algo.bins(r, input, bins)
     return r
 
+def BINS_TURNOVER(
+  input: np.ndarray, bins: int, bin: int
+) -> tuple[np.ndarray, np.ndarray]:
+  """
+  Membership turnover of one bin produced by `ta_bins`
+  
+  At each date the cross-section is split into `bins` bins like `ta_bins`,
+  and the members of bin `bin` (0 is the bottom bin, bins - 1 the top bin)
+  are compared with those of the previous date. Outputs, written to every
+  security of the date:
+  entering: fraction of the current members that were not in the bin
+  leaving: fraction of the previous members that left the bin
+  """
+  input = _to_f64(input)
+  entering = np.empty_like(input, dtype=np.float64)
+  leaving = np.empty_like(input, dtype=np.float64)
+  _algo.bins_turnover(entering, leaving, input, bins, bin)
+  return entering, leaving
+
+def CALMAR(
+  input: np.ndarray | list[np.ndarray], periods: int, annual: float
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Rolling Calmar Ratio of returns.
+  
+  Calmar = annualised compound return / |max drawdown|, the drawdown is
+  `MAX_DRAWDOWN` of the equity curve compounded from the returns of the window
+  and starting from 1 before its first bar, so a loss on the first bar is a
+  drawdown. `annual` is the number of bars per year.
+  
+  Ref: https://en.wikipedia.org/wiki/Calmar_ratio
+  """
+  if isinstance(input, list):
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in input]
+    _algo.calmar(r, input, periods, annual)
+    return r
+  else:
+    input = _to_f64(input)
+    r = np.empty_like(input)
+    _algo.calmar(r, input, periods, annual)
+    return r
+
 def CC_RANK(
   input: np.ndarray | list[np.ndarray]
 ) -> np.ndarray | list[np.ndarray]:
   """
   Calculate rank percentage cross group dimension, the ctx.groups() is the number of groups
-  Same value are averaged
+  Same value are averaged, see `ta_cc_rank_ex` for other tie methods.
   """
   if isinstance(input, list):
     input = [_to_f64(x) for x in input]
@@ -162,6 +393,30 @@ def CC_RANK(
     _algo.cc_rank(r, input)
     return r
 
+def CC_RANK_EX(
+  input: np.ndarray | list[np.ndarray], method: int
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Calculate rank cross group dimension using a tie `method`, the ctx.groups() is the number of groups
+  
+  `method` is one of `RANK_AVERAGE` (0), `RANK_MIN` (1), `RANK_MAX` (2), `RANK_DENSE` (3),
+  `RANK_ORDINAL` (4), optionally combined with `RANK_PCT` (8) and `RANK_DESCENDING` (16).
+  Ties of `RANK_ORDINAL` are ranked by group order.
+  NaN values are excluded, NaN input produces NaN output.
+  
+  Ref: https://pandas.pydata.org/docs/reference/api/pandas.DataFrame.rank.html
+  """
+  if isinstance(input, list):
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in input]
+    _algo.cc_rank_ex(r, input, method)
+    return r
+  else:
+    input = _to_f64(input)
+    r = np.empty_like(input)
+    _algo.cc_rank_ex(r, input, method)
+    return r
+
 def CC_ZSCORE(
   input: np.ndarray | list[np.ndarray]
 ) -> np.ndarray | list[np.ndarray]:
@@ -182,6 +437,340 @@ def CC_ZSCORE(
     _algo.cc_zscore(r, input)
     return r
 
+def CCI(
+  high: np.ndarray | list[np.ndarray], low: np.ndarray | list[np.ndarray], close: np.ndarray | list[np.ndarray], periods: int
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Commodity Channel Index
+  
+  TP = (high + low + close) / 3, CCI = (TP - MA(TP, n)) / (0.015 * mean
+  deviation of TP), 0 for a flat window, same as TA-Lib CCI. Requires
+  `periods` valid bars.
+  
+  Ref: https://en.wikipedia.org/wiki/Commodity_channel_index
+  """
+  if isinstance(high, list) and isinstance(low, list) and isinstance(close, list):
+    high = [_to_f64(x) for x in high]
+    low = [_to_f64(x) for x in low]
+    close = [_to_f64(x) for x in close]
+    r = [np.empty_like(x) for x in high]
+    _algo.cci(r, high, low, close, periods)
+    return r
+  else:
+    high = _to_f64(high)
+    low = _to_f64(low)
+    close = _to_f64(close)
+    r = np.empty_like(high)
+    _algo.cci(r, high, low, close, periods)
+    return r
+
+def CDL3BLACKCROWS(
+  open: np.ndarray, high: np.ndarray, low: np.ndarray, close: np.ndarray
+) -> np.ndarray:
+  """
+  Three Black Crows
+  
+  A white candle followed by three black candles with very short lower
+  shadows, each opening within the previous body and closing lower, the
+  first closing under the high of the white candle, -100 on a match.
+  
+  Ref: https://ta-lib.org/functions/
+  """
+  open = _to_f64(open)
+  high = _to_f64(high)
+  low = _to_f64(low)
+  close = _to_f64(close)
+  r = np.empty_like(open, dtype=np.float64)
+  _algo.cdl3blackcrows(r, open, high, low, close)
+  return r
+
+def CDL3WHITESOLDIERS(
+  open: np.ndarray, high: np.ndarray, low: np.ndarray, close: np.ndarray
+) -> np.ndarray:
+  """
+  Three Advancing White Soldiers
+  
+  Three white candles with rising closes and very short upper shadows, each
+  opening within or near the previous body and not far shorter than it, the
+  last one not short, +100 on a match.
+  
+  Ref: https://ta-lib.org/functions/
+  """
+  open = _to_f64(open)
+  high = _to_f64(high)
+  low = _to_f64(low)
+  close = _to_f64(close)
+  r = np.empty_like(open, dtype=np.float64)
+  _algo.cdl3whitesoldiers(r, open, high, low, close)
+  return r
+
+def CDLDOJI(
+  open: np.ndarray, high: np.ndarray, low: np.ndarray, close: np.ndarray
+) -> np.ndarray:
+  """
+  Doji
+  
+  Body <= 10% of the average high - low range, +100 on a match.
+  
+  Ref: https://ta-lib.org/functions/
+  """
+  open = _to_f64(open)
+  high = _to_f64(high)
+  low = _to_f64(low)
+  close = _to_f64(close)
+  r = np.empty_like(open, dtype=np.float64)
+  _algo.cdldoji(r, open, high, low, close)
+  return r
+
+def CDLDRAGONFLYDOJI(
+  open: np.ndarray, high: np.ndarray, low: np.ndarray, close: np.ndarray
+) -> np.ndarray:
+  """
+  Dragonfly Doji
+  
+  Doji with a very short upper shadow and a lower shadow that is not very
+  short, +100 on a match.
+  
+  Ref: https://ta-lib.org/functions/
+  """
+  open = _to_f64(open)
+  high = _to_f64(high)
+  low = _to_f64(low)
+  close = _to_f64(close)
+  r = np.empty_like(open, dtype=np.float64)
+  _algo.cdldragonflydoji(r, open, high, low, close)
+  return r
+
+def CDLENGULFING(
+  open: np.ndarray, high: np.ndarray, low: np.ndarray, close: np.ndarray
+) -> np.ndarray:
+  """
+  Engulfing Pattern
+  
+  The body of the candle engulfs the opposite colored body of the previous
+  one, +100 when bullish and -100 when bearish. As TA-Lib >= 0.5 one end of
+  the bodies may be equal, which scores +80 / -80.
+  
+  Ref: https://ta-lib.org/functions/
+  """
+  open = _to_f64(open)
+  high = _to_f64(high)
+  low = _to_f64(low)
+  close = _to_f64(close)
+  r = np.empty_like(open, dtype=np.float64)
+  _algo.cdlengulfing(r, open, high, low, close)
+  return r
+
+def CDLEVENINGSTAR(
+  open: np.ndarray, high: np.ndarray, low: np.ndarray, close: np.ndarray, penetration: float
+) -> np.ndarray:
+  """
+  Evening Star
+  
+  A long white candle, a short body gapping up and a black candle closing
+  below `penetration` (usually 0.3) of the first body, -100 on a match.
+  
+  Ref: https://ta-lib.org/functions/
+  """
+  open = _to_f64(open)
+  high = _to_f64(high)
+  low = _to_f64(low)
+  close = _to_f64(close)
+  r = np.empty_like(open, dtype=np.float64)
+  _algo.cdleveningstar(r, open, high, low, close, penetration)
+  return r
+
+def CDLGRAVESTONEDOJI(
+  open: np.ndarray, high: np.ndarray, low: np.ndarray, close: np.ndarray
+) -> np.ndarray:
+  """
+  Gravestone Doji
+  
+  Doji with a very short lower shadow and an upper shadow that is not very
+  short, +100 on a match.
+  
+  Ref: https://ta-lib.org/functions/
+  """
+  open = _to_f64(open)
+  high = _to_f64(high)
+  low = _to_f64(low)
+  close = _to_f64(close)
+  r = np.empty_like(open, dtype=np.float64)
+  _algo.cdlgravestonedoji(r, open, high, low, close)
+  return r
+
+def CDLHAMMER(
+  open: np.ndarray, high: np.ndarray, low: np.ndarray, close: np.ndarray
+) -> np.ndarray:
+  """
+  Hammer
+  
+  Hammer shaped candle with the body below or near the low of the previous
+  candle, +100 on a match.
+  
+  Ref: https://ta-lib.org/functions/
+  """
+  open = _to_f64(open)
+  high = _to_f64(high)
+  low = _to_f64(low)
+  close = _to_f64(close)
+  r = np.empty_like(open, dtype=np.float64)
+  _algo.cdlhammer(r, open, high, low, close)
+  return r
+
+def CDLHANGINGMAN(
+  open: np.ndarray, high: np.ndarray, low: np.ndarray, close: np.ndarray
+) -> np.ndarray:
+  """
+  Hanging Man
+  
+  Hammer shaped candle with the body above or near the high of the previous
+  candle, -100 on a match.
+  
+  Ref: https://ta-lib.org/functions/
+  """
+  open = _to_f64(open)
+  high = _to_f64(high)
+  low = _to_f64(low)
+  close = _to_f64(close)
+  r = np.empty_like(open, dtype=np.float64)
+  _algo.cdlhangingman(r, open, high, low, close)
+  return r
+
+def CDLHARAMI(
+  open: np.ndarray, high: np.ndarray, low: np.ndarray, close: np.ndarray
+) -> np.ndarray:
+  """
+  Harami Pattern
+  
+  A short body inside the long body of the previous candle, +100 after a
+  black and -100 after a white candle. As TA-Lib >= 0.5 a body sharing its
+  top or bottom with the previous one scores +80 / -80.
+  
+  Ref: https://ta-lib.org/functions/
+  """
+  open = _to_f64(open)
+  high = _to_f64(high)
+  low = _to_f64(low)
+  close = _to_f64(close)
+  r = np.empty_like(open, dtype=np.float64)
+  _algo.cdlharami(r, open, high, low, close)
+  return r
+
+def CDLINVERTEDHAMMER(
+  open: np.ndarray, high: np.ndarray, low: np.ndarray, close: np.ndarray
+) -> np.ndarray:
+  """
+  Inverted Hammer
+  
+  Small body with a long upper shadow gapping down from the previous body,
+  +100 on a match.
+  
+  Ref: https://ta-lib.org/functions/
+  """
+  open = _to_f64(open)
+  high = _to_f64(high)
+  low = _to_f64(low)
+  close = _to_f64(close)
+  r = np.empty_like(open, dtype=np.float64)
+  _algo.cdlinvertedhammer(r, open, high, low, close)
+  return r
+
+def CDLMARUBOZU(
+  open: np.ndarray, high: np.ndarray, low: np.ndarray, close: np.ndarray
+) -> np.ndarray:
+  """
+  Marubozu
+  
+  Long body with very short shadows on both ends, +100 for a white and -100
+  for a black candle.
+  
+  Ref: https://ta-lib.org/functions/
+  """
+  open = _to_f64(open)
+  high = _to_f64(high)
+  low = _to_f64(low)
+  close = _to_f64(close)
+  r = np.empty_like(open, dtype=np.float64)
+  _algo.cdlmarubozu(r, open, high, low, close)
+  return r
+
+def CDLMORNINGSTAR(
+  open: np.ndarray, high: np.ndarray, low: np.ndarray, close: np.ndarray, penetration: float
+) -> np.ndarray:
+  """
+  Morning Star
+  
+  A long black candle, a short body gapping down and a white candle closing
+  above `penetration` (usually 0.3) of the first body, +100 on a match.
+  
+  Ref: https://ta-lib.org/functions/
+  """
+  open = _to_f64(open)
+  high = _to_f64(high)
+  low = _to_f64(low)
+  close = _to_f64(close)
+  r = np.empty_like(open, dtype=np.float64)
+  _algo.cdlmorningstar(r, open, high, low, close, penetration)
+  return r
+
+def CDLSHOOTINGSTAR(
+  open: np.ndarray, high: np.ndarray, low: np.ndarray, close: np.ndarray
+) -> np.ndarray:
+  """
+  Shooting Star
+  
+  Small body with a long upper shadow gapping up from the previous body,
+  -100 on a match.
+  
+  Ref: https://ta-lib.org/functions/
+  """
+  open = _to_f64(open)
+  high = _to_f64(high)
+  low = _to_f64(low)
+  close = _to_f64(close)
+  r = np.empty_like(open, dtype=np.float64)
+  _algo.cdlshootingstar(r, open, high, low, close)
+  return r
+
+def CMF(
+  high: np.ndarray, low: np.ndarray, close: np.ndarray, volume: np.ndarray, periods: int
+) -> np.ndarray:
+  """
+  Chaikin Money Flow
+  
+  CMF = sum(CLV * volume, periods) / sum(volume, periods), NaN when the window
+  has no volume.
+  
+  Ref: https://www.investopedia.com/ask/answers/071414/whats-difference-between-chaikin-money-flow-cmf-and-money-flow-index-mfi.asp
+  """
+  high = _to_f64(high)
+  low = _to_f64(low)
+  close = _to_f64(close)
+  volume = _to_f64(volume)
+  r = np.empty_like(high, dtype=np.float64)
+  _algo.cmf(r, high, low, close, volume, periods)
+  return r
+
+def CONSTANT(
+  input: np.ndarray | list[np.ndarray]
+) -> np.ndarray | list[np.ndarray]:
+  """
+  通达信 CONST, the last value of `input` in each group repeated over the
+  group, the last valid one with FLAG_SKIP_NAN. It looks ahead by design,
+  do not use it for signals.
+  """
+  if isinstance(input, list):
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in input]
+    _algo.constant(r, input)
+    return r
+  else:
+    input = _to_f64(input)
+    r = np.empty_like(input)
+    _algo.constant(r, input)
+    return r
+
 def CORR(
   input: np.ndarray | list[np.ndarray], periods: int
 ) -> np.ndarray | list[np.ndarray]:
@@ -301,6 +890,29 @@ def CROSS(
     _algo.cross(r, a, b)
     return r
 
+def DEMA(
+  input: np.ndarray | list[np.ndarray], periods: int
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Double Exponential Moving Average
+  
+  DEMA = 2 * EMA(input, periods) - EMA(EMA(input, periods), periods), each
+  EMA seeded with the mean of its first `periods` values, so the first
+  2 * (periods - 1) bars are NaN as TA-Lib.
+  
+  Ref: https://www.investopedia.com/terms/d/double-exponential-moving-average.asp
+  """
+  if isinstance(input, list):
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in input]
+    _algo.dema(r, input, periods)
+    return r
+  else:
+    input = _to_f64(input)
+    r = np.empty_like(input)
+    _algo.dema(r, input, periods)
+    return r
+
 def DMA(
   input: np.ndarray | list[np.ndarray], weight: float
 ) -> np.ndarray | list[np.ndarray]:
@@ -321,92 +933,719 @@ def DMA(
     _algo.dma(r, input, weight)
     return r
 
-def ENTROPY(
-  input: np.ndarray | list[np.ndarray], periods: int, bins: int
+def DMA_EX(
+  input: np.ndarray | list[np.ndarray], weight: float, flags: int
 ) -> np.ndarray | list[np.ndarray]:
   """
-  Calculate rolling Shannon entropy over a moving window
+  Exponential Moving Average, pandas compatible, given weight
   
-  Discretizes values into `bins` equal-width buckets within the window's
-  [min, max] range, then computes -sum(p * ln(p)) where p is the frequency
-  of each occupied bin. Uses natural log (base e).
-  Requires at least 2 valid values. Single-value windows return 0.
+  Same as `ewm(alpha=weight, adjust).mean()` of pandas, same options as `EMA_EX`.
   """
   if isinstance(input, list):
     input = [_to_f64(x) for x in input]
     r = [np.empty_like(x) for x in input]
-    _algo.entropy(r, input, periods, bins)
+    _algo.dma_ex(r, input, weight, flags)
     return r
   else:
     input = _to_f64(input)
     r = np.empty_like(input)
-    _algo.entropy(r, input, periods, bins)
+    _algo.dma_ex(r, input, weight, flags)
     return r
 
-def FRET(
-  open: np.ndarray | list[np.ndarray], close: np.ndarray | list[np.ndarray], is_calc: np.ndarray | list[np.ndarray], delay: int, periods: int
-) -> np.ndarray | list[np.ndarray]:
+def DMI(
+  high: np.ndarray, low: np.ndarray, close: np.ndarray, periods: int
+) -> tuple[np.ndarray, np.ndarray, np.ndarray, np.ndarray]:
   """
-  Future Return
+  Directional Movement Index
   
-  Calculates the return from the open price of the delayed day (t+delay) to the close price of the future day (t+delay+periods-1).
-  Return = (Close[t+delay+periods-1] - Open[t+delay]) / Open[t+delay]
+  +DI and -DI are Wilder smoothed directional movements over true range,
+  ADX is the Wilder average of DX = 100 * |+DI - -DI| / (+DI + -DI), ADXR is
+  the mean of ADX and ADX `periods - 1` bars ago, same as TA-Lib PLUS_DI,
+  MINUS_DI, ADX and ADXR.
   
-  If n=1, delay=1, it calculates (Close[t+1] - Open[t+1]) / Open[t+1].
-  If `is_calc[t+delay]` is 0, returns NaN.
+  With FLAG_SKIP_NAN missing bars are skipped, otherwise a missing bar
+  restarts the warm-up.
+  
+  Ref: https://en.wikipedia.org/wiki/Average_directional_movement_index
+  """
+  high = _to_f64(high)
+  low = _to_f64(low)
+  close = _to_f64(close)
+  plus_di = np.empty_like(high, dtype=np.float64)
+  minus_di = np.empty_like(high, dtype=np.float64)
+  adx = np.empty_like(high, dtype=np.float64)
+  adxr = np.empty_like(high, dtype=np.float64)
+  _algo.dmi(plus_di, minus_di, adx, adxr, high, low, close, periods)
+  return plus_di, minus_di, adx, adxr
+
+def DONCHIAN(
+  high: np.ndarray, low: np.ndarray, periods: int
+) -> tuple[np.ndarray, np.ndarray, np.ndarray]:
   """
-  if isinstance(open, list) and isinstance(close, list) and isinstance(is_calc, list):
-    open = [_to_f64(x) for x in open]
-    close = [_to_f64(x) for x in close]
-    is_calc = [_to_f64(x) for x in is_calc]
-    r = [np.empty_like(x) for x in open]
-    _algo.fret(r, open, close, is_calc, delay, periods)
-    return r
-  else:
-    open = _to_f64(open)
-    close = _to_f64(close)
-    is_calc = _to_f64(is_calc)
-    r = np.empty_like(open)
-    _algo.fret(r, open, close, is_calc, delay, periods)
-    return r
+  Donchian Channels
+  
+  upper = HHV(high, periods), lower = LLV(low, periods),
+  middle = (upper + lower) / 2
+  
+  Ref: https://www.investopedia.com/terms/d/donchianchannels.asp
+  """
+  high = _to_f64(high)
+  low = _to_f64(low)
+  upper = np.empty_like(high, dtype=np.float64)
+  middle = np.empty_like(high, dtype=np.float64)
+  lower = np.empty_like(high, dtype=np.float64)
+  _algo.donchian(upper, middle, lower, high, low, periods)
+  return upper, middle, lower
 
-def GROUP_RANK(
-  category: np.ndarray | list[np.ndarray], input: np.ndarray | list[np.ndarray]
+def EMA_EX(
+  input: np.ndarray | list[np.ndarray], periods: int, flags: int
 ) -> np.ndarray | list[np.ndarray]:
   """
-  Calculate rank percentage within each category group at each time step
+  Exponential Moving Average, pandas compatible, weight = 2 / (n + 1)
   
-  For each time position, groups items by `category` value, then computes
-  rank percentage within each group. Same value gets averaged rank.
-  NaN in category or input produces NaN output.
+  Same as `ewm(span=n, adjust).mean()` of pandas, `flags` takes EWM_ADJUST,
+  FLAG_SKIP_NAN of context means ignore_na=True. Seeded from the first valid
+  value, NaN input carries the previous value forward. With
+  FLAG_STRICTLY_CYCLE the first n - 1 observations are NaN.
   """
-  if isinstance(category, list) and isinstance(input, list):
-    category = [_to_f64(x) for x in category]
+  if isinstance(input, list):
     input = [_to_f64(x) for x in input]
-    r = [np.empty_like(x) for x in category]
-    _algo.group_rank(r, category, input)
+    r = [np.empty_like(x) for x in input]
+    _algo.ema_ex(r, input, periods, flags)
     return r
   else:
-    category = _to_f64(category)
     input = _to_f64(input)
-    r = np.empty_like(category)
-    _algo.group_rank(r, category, input)
+    r = np.empty_like(input)
+    _algo.ema_ex(r, input, periods, flags)
     return r
 
-def GROUP_ZSCORE(
-  category: np.ndarray | list[np.ndarray], input: np.ndarray | list[np.ndarray]
+def EMV(
+  high: np.ndarray | list[np.ndarray], low: np.ndarray | list[np.ndarray], volume: np.ndarray | list[np.ndarray], periods: int, scale: float
 ) -> np.ndarray | list[np.ndarray]:
   """
-  Calculate Z-Score within each category group at each time step
+  Ease of Movement
   
-  For each time position, groups items by `category` value, then computes
-  (x - group_mean) / group_std within each group.
-  NaN in category or input produces NaN output.
-  Groups with fewer than 2 valid values produce NaN.
+  EMV = MA(distance / box ratio, periods) with distance = (high + low) / 2 -
+  ref((high + low) / 2, 1) and box ratio = volume / scale / (high - low). A
+  bar without range counts as 0, a bar without volume is left out of the
+  average. `scale` is usually 1e8 for share volume.
+  
+  Ref: https://www.investopedia.com/terms/e/easeofmovement.asp
   """
-  if isinstance(category, list) and isinstance(input, list):
-    category = [_to_f64(x) for x in category]
-    input = [_to_f64(x) for x in input]
+  if isinstance(high, list) and isinstance(low, list) and isinstance(volume, list):
+    high = [_to_f64(x) for x in high]
+    low = [_to_f64(x) for x in low]
+    volume = [_to_f64(x) for x in volume]
+    r = [np.empty_like(x) for x in high]
+    _algo.emv(r, high, low, volume, periods, scale)
+    return r
+  else:
+    high = _to_f64(high)
+    low = _to_f64(low)
+    volume = _to_f64(volume)
+    r = np.empty_like(high)
+    _algo.emv(r, high, low, volume, periods, scale)
+    return r
+
+def ENTROPY(
+  input: np.ndarray | list[np.ndarray], periods: int, bins: int
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Calculate rolling Shannon entropy over a moving window
+  
+  Discretizes values into `bins` equal-width buckets within the window's
+  [min, max] range, then computes -sum(p * ln(p)) where p is the frequency
+  of each occupied bin. Uses natural log (base e).
+  Requires at least 2 valid values. Single-value windows return 0.
+  """
+  if isinstance(input, list):
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in input]
+    _algo.entropy(r, input, periods, bins)
+    return r
+  else:
+    input = _to_f64(input)
+    r = np.empty_like(input)
+    _algo.entropy(r, input, periods, bins)
+    return r
+
+def EWM_BETA(
+  input: np.ndarray | list[np.ndarray], benchmark: np.ndarray | list[np.ndarray], decay: float, flags: int
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Exponentially Weighted Beta
+  
+  cov(input, benchmark) / var(benchmark) over pairs with both values valid,
+  the bias flag is ignored.
+  """
+  if isinstance(input, list) and isinstance(benchmark, list):
+    input = [_to_f64(x) for x in input]
+    benchmark = [_to_f64(x) for x in benchmark]
+    r = [np.empty_like(x) for x in input]
+    _algo.ewm_beta(r, input, benchmark, decay, flags)
+    return r
+  else:
+    input = _to_f64(input)
+    benchmark = _to_f64(benchmark)
+    r = np.empty_like(input)
+    _algo.ewm_beta(r, input, benchmark, decay, flags)
+    return r
+
+def EWM_CORR(
+  x: np.ndarray | list[np.ndarray], y: np.ndarray | list[np.ndarray], decay: float, flags: int
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Exponentially Weighted Correlation
+  
+  cov(x, y) / sqrt(var(x) * var(y)), the bias flag is ignored.
+  """
+  if isinstance(x, list) and isinstance(y, list):
+    x = [_to_f64(x) for x in x]
+    y = [_to_f64(x) for x in y]
+    r = [np.empty_like(x) for x in x]
+    _algo.ewm_corr(r, x, y, decay, flags)
+    return r
+  else:
+    x = _to_f64(x)
+    y = _to_f64(y)
+    r = np.empty_like(x)
+    _algo.ewm_corr(r, x, y, decay, flags)
+    return r
+
+def EWM_COV(
+  x: np.ndarray | list[np.ndarray], y: np.ndarray | list[np.ndarray], decay: float, flags: int
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Exponentially Weighted Covariance
+  
+  Only pairs with both values valid are observations, same parameters as `EWM_VAR`.
+  """
+  if isinstance(x, list) and isinstance(y, list):
+    x = [_to_f64(x) for x in x]
+    y = [_to_f64(x) for x in y]
+    r = [np.empty_like(x) for x in x]
+    _algo.ewm_cov(r, x, y, decay, flags)
+    return r
+  else:
+    x = _to_f64(x)
+    y = _to_f64(y)
+    r = np.empty_like(x)
+    _algo.ewm_cov(r, x, y, decay, flags)
+    return r
+
+def EWM_STD(
+  input: np.ndarray | list[np.ndarray], decay: float, flags: int
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Exponentially Weighted Standard Deviation
+  
+  Square root of `EWM_VAR`, same parameters.
+  """
+  if isinstance(input, list):
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in input]
+    _algo.ewm_std(r, input, decay, flags)
+    return r
+  else:
+    input = _to_f64(input)
+    r = np.empty_like(input)
+    _algo.ewm_std(r, input, decay, flags)
+    return r
+
+def EWM_VAR(
+  input: np.ndarray | list[np.ndarray], decay: float, flags: int
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Exponentially Weighted Variance
+  
+  `decay` is interpreted by `flags`: EWM_ALPHA (0), EWM_SPAN (1),
+  EWM_HALFLIFE (2) or EWM_COM (3), combined with EWM_ADJUST (4) and
+  EWM_BIAS (8), matches pandas `ewm(..., adjust, ignore_na).var(bias)`,
+  FLAG_SKIP_NAN of context means ignore_na=True.
+  """
+  if isinstance(input, list):
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in input]
+    _algo.ewm_var(r, input, decay, flags)
+    return r
+  else:
+    input = _to_f64(input)
+    r = np.empty_like(input)
+    _algo.ewm_var(r, input, decay, flags)
+    return r
+
+def EWM_ZSCORE(
+  input: np.ndarray | list[np.ndarray], decay: float, flags: int
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Exponentially Weighted Z-Score
+  
+  (x - ewm mean) / ewm std, same parameters as `EWM_VAR`, NaN input gives NaN.
+  """
+  if isinstance(input, list):
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in input]
+    _algo.ewm_zscore(r, input, decay, flags)
+    return r
+  else:
+    input = _to_f64(input)
+    r = np.empty_like(input)
+    _algo.ewm_zscore(r, input, decay, flags)
+    return r
+
+def FILTER(
+  cond: np.ndarray, n: int
+) -> np.ndarray:
+  """
+  Signal debounce of 通达信 FILTER(X, N), true where `cond` is true and the
+  following `n` bars are forced to false
+  """
+  cond = _to_bool(cond)
+  r = np.empty_like(cond, dtype=bool)
+  _algo.filter(r, cond, n)
+  return r
+
+def FLIP(
+  on: np.ndarray, off: np.ndarray
+) -> np.ndarray:
+  """
+  Flip-flop, true from a bar where `on` is true until a bar where `off` is
+  true, `off` wins when both are true on the same bar
+  
+  Ref: https://www.amibroker.com/guide/afl/flip.html
+  """
+  on = _to_bool(on)
+  off = _to_bool(off)
+  r = np.empty_like(on, dtype=bool)
+  _algo.flip(r, on, off)
+  return r
+
+def FORCE(
+  close: np.ndarray | list[np.ndarray], volume: np.ndarray | list[np.ndarray], periods: int
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Force Index
+  
+  FI = EMA((close - ref(close, 1)) * volume, periods), the EMA is seeded with
+  the first raw value and under FLAG_STRICTLY_CYCLE the first `periods - 1` raw
+  values are NaN.
+  
+  Ref: https://www.investopedia.com/terms/f/force-index.asp
+  """
+  if isinstance(close, list) and isinstance(volume, list):
+    close = [_to_f64(x) for x in close]
+    volume = [_to_f64(x) for x in volume]
+    r = [np.empty_like(x) for x in close]
+    _algo.force(r, close, volume, periods)
+    return r
+  else:
+    close = _to_f64(close)
+    volume = _to_f64(volume)
+    r = np.empty_like(close)
+    _algo.force(r, close, volume, periods)
+    return r
+
+def FRAMA(
+  input: np.ndarray | list[np.ndarray], periods: int
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Fractal Adaptive Moving Average
+  
+  Over the last `periods` (even) bars, N1 / N2 = range of each half / (periods / 2),
+  N3 = range of the window / periods, D = (ln(N1 + N2) - ln(N3)) / ln(2),
+  alpha = clamp(exp(-4.6 * (D - 1)), 0.01, 1) and
+  FRAMA = alpha * input + (1 - alpha) * FRAMA[-1]. Flat windows keep the
+  previous alpha, the recursion starts from the input and outputs begin once
+  the window is full.
+  
+  Ref: https://www.mesasoftware.com/papers/FRAMA.pdf
+  """
+  if isinstance(input, list):
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in input]
+    _algo.frama(r, input, periods)
+    return r
+  else:
+    input = _to_f64(input)
+    r = np.empty_like(input)
+    _algo.frama(r, input, periods)
+    return r
+
+def FRET(
+  open: np.ndarray | list[np.ndarray], close: np.ndarray | list[np.ndarray], is_calc: np.ndarray | list[np.ndarray], delay: int, periods: int
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Future Return
+  
+  Calculates the return from the open price of the delayed day (t+delay) to the close price of the future day (t+delay+periods-1).
+  Return = (Close[t+delay+periods-1] - Open[t+delay]) / Open[t+delay]
+  
+  If n=1, delay=1, it calculates (Close[t+1] - Open[t+1]) / Open[t+1].
+  If `is_calc[t+delay]` is 0, returns NaN.
+  """
+  if isinstance(open, list) and isinstance(close, list) and isinstance(is_calc, list):
+    open = [_to_f64(x) for x in open]
+    close = [_to_f64(x) for x in close]
+    is_calc = [_to_f64(x) for x in is_calc]
+    r = [np.empty_like(x) for x in open]
+    _algo.fret(r, open, close, is_calc, delay, periods)
+    return r
+  else:
+    open = _to_f64(open)
+    close = _to_f64(close)
+    is_calc = _to_f64(is_calc)
+    r = np.empty_like(open)
+    _algo.fret(r, open, close, is_calc, delay, periods)
+    return r
+
+def GARCH(
+  input: np.ndarray | list[np.ndarray], omega: float, alpha: float, beta: float, periods: int, refit: int
+) -> np.ndarray | list[np.ndarray]:
+  """
+  GARCH(1,1) Conditional Variance
+  
+  variance[t + 1] = omega + alpha * input[t]^2 + beta * variance[t], output at
+  t is the forecast of the next bar given data up to t. `input` is a return
+  series with zero mean assumed.
+  
+  With `refit` == 0 the given (omega, alpha, beta) are used, the filter starts
+  from the unconditional variance omega / (1 - alpha - beta).
+  
+  With `refit` > 0 the parameters are fitted by maximum likelihood every
+  `refit` observations on the last `periods` valid values (0 means expanding
+  window, at least 10 values), starting from the given parameters, and the
+  filter is rerun over the window. Output is NaN before the first fit.
+  
+  FLAG_SKIP_NAN skips missing values, otherwise the variance still decays
+  over them as if the squared return equals its expectation.
+  """
+  if isinstance(input, list):
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in input]
+    _algo.garch(r, input, omega, alpha, beta, periods, refit)
+    return r
+  else:
+    input = _to_f64(input)
+    r = np.empty_like(input)
+    _algo.garch(r, input, omega, alpha, beta, periods, refit)
+    return r
+
+def GARMAN_KLASS_VOL(
+  open: np.ndarray, high: np.ndarray, low: np.ndarray, close: np.ndarray, periods: int, annual: float
+) -> np.ndarray:
+  """
+  Garman-Klass Volatility
+  
+  var = mean(0.5 * ln(high / low)^2 - (2 * ln(2) - 1) * ln(close / open)^2),
+  result is sqrt(var * annual).
+  
+  Ref: Garman, M. B., Klass, M. J. (1980). On the Estimation of Security
+  Price Volatilities from Historical Data.
+  """
+  open = _to_f64(open)
+  high = _to_f64(high)
+  low = _to_f64(low)
+  close = _to_f64(close)
+  r = np.empty_like(open, dtype=np.float64)
+  _algo.garman_klass_vol(r, open, high, low, close, periods, annual)
+  return r
+
+def GROUP_BACKFILL(
+  category: np.ndarray | list[np.ndarray], input: np.ndarray | list[np.ndarray], periods: int, std: float
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Fill NaN input with the winsorized mean of the same category group
+  
+  For a NaN value at a time step, collects all valid values of the members
+  in the same category over the preceding `periods` time steps (including the
+  current one), clips them to mean ± std * stddev, and fills with the mean of
+  the clipped values. Valid input is kept as is.
+  
+  Ref: WQ BRAIN group_backfill
+  """
+  if isinstance(category, list) and isinstance(input, list):
+    category = [_to_f64(x) for x in category]
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in category]
+    _algo.group_backfill(r, category, input, periods, std)
+    return r
+  else:
+    category = _to_f64(category)
+    input = _to_f64(input)
+    r = np.empty_like(category)
+    _algo.group_backfill(r, category, input, periods, std)
+    return r
+
+def GROUP_BINS(
+  category: np.ndarray | list[np.ndarray], input: np.ndarray | list[np.ndarray], bins: int, flags: int
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Discretize the input into n bins within each category group at each time step
+  
+  By default bins have equal counts and use the same rule as `ta_bins`:
+  same value are assigned to the same bin, bin = floor((avg_rank - 1) * bins / count).
+  `flags` is a combination of `BINS_EQUAL_WIDTH` (1) to split [min, max] of the group
+  into equal-width ranges, and `BINS_ONE_BASED` (2) to number bins from 1.
+  NaN in category or input produces NaN output.
+  """
+  if isinstance(category, list) and isinstance(input, list):
+    category = [_to_f64(x) for x in category]
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in category]
+    _algo.group_bins(r, category, input, bins, flags)
+    return r
+  else:
+    category = _to_f64(category)
+    input = _to_f64(input)
+    r = np.empty_like(category)
+    _algo.group_bins(r, category, input, bins, flags)
+    return r
+
+def GROUP_COUNT(
+  category: np.ndarray | list[np.ndarray], input: np.ndarray | list[np.ndarray]
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Count valid values of each category group at each time step
+  
+  The number of members with non-NaN input is assigned to every member of the group.
+  NaN in category produces NaN output.
+  """
+  if isinstance(category, list) and isinstance(input, list):
+    category = [_to_f64(x) for x in category]
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in category]
+    _algo.group_count(r, category, input)
+    return r
+  else:
+    category = _to_f64(category)
+    input = _to_f64(input)
+    r = np.empty_like(category)
+    _algo.group_count(r, category, input)
+    return r
+
+def GROUP_MAX(
+  category: np.ndarray | list[np.ndarray], input: np.ndarray | list[np.ndarray]
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Calculate maximum of each category group at each time step
+  
+  The maximum of valid values is assigned to every member of the group.
+  """
+  if isinstance(category, list) and isinstance(input, list):
+    category = [_to_f64(x) for x in category]
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in category]
+    _algo.group_max(r, category, input)
+    return r
+  else:
+    category = _to_f64(category)
+    input = _to_f64(input)
+    r = np.empty_like(category)
+    _algo.group_max(r, category, input)
+    return r
+
+def GROUP_MEAN(
+  category: np.ndarray | list[np.ndarray], input: np.ndarray | list[np.ndarray], weights: np.ndarray | list[np.ndarray]
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Calculate mean of each category group at each time step
+  
+  For each time position, groups items by `category` value and assigns the
+  mean of valid values to every member of the group.
+  If `weights` is not empty, the weighted mean sum(w * x) / sum(w) is used,
+  items with NaN weight are excluded.
+  NaN in category produces NaN output.
+  """
+  if isinstance(category, list) and isinstance(input, list) and isinstance(weights, list):
+    category = [_to_f64(x) for x in category]
+    input = [_to_f64(x) for x in input]
+    weights = [_to_f64(x) for x in weights]
+    r = [np.empty_like(x) for x in category]
+    _algo.group_mean(r, category, input, weights)
+    return r
+  else:
+    category = _to_f64(category)
+    input = _to_f64(input)
+    weights = _to_f64(weights)
+    r = np.empty_like(category)
+    _algo.group_mean(r, category, input, weights)
+    return r
+
+def GROUP_MEDIAN(
+  category: np.ndarray | list[np.ndarray], input: np.ndarray | list[np.ndarray]
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Calculate median of each category group at each time step
+  
+  The median of valid values is assigned to every member of the group,
+  even-sized groups use the average of the two middle values.
+  """
+  if isinstance(category, list) and isinstance(input, list):
+    category = [_to_f64(x) for x in category]
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in category]
+    _algo.group_median(r, category, input)
+    return r
+  else:
+    category = _to_f64(category)
+    input = _to_f64(input)
+    r = np.empty_like(category)
+    _algo.group_median(r, category, input)
+    return r
+
+def GROUP_MIN(
+  category: np.ndarray | list[np.ndarray], input: np.ndarray | list[np.ndarray]
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Calculate minimum of each category group at each time step
+  
+  The minimum of valid values is assigned to every member of the group.
+  """
+  if isinstance(category, list) and isinstance(input, list):
+    category = [_to_f64(x) for x in category]
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in category]
+    _algo.group_min(r, category, input)
+    return r
+  else:
+    category = _to_f64(category)
+    input = _to_f64(input)
+    r = np.empty_like(category)
+    _algo.group_min(r, category, input)
+    return r
+
+def GROUP_NEUTRALIZE(
+  category: np.ndarray | list[np.ndarray], input: np.ndarray | list[np.ndarray]
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Demean the input within each category group at each time step
+  
+  r = x - group_mean, NaN in category or input produces NaN output.
+  
+  Ref: WQ BRAIN group_neutralize
+  """
+  if isinstance(category, list) and isinstance(input, list):
+    category = [_to_f64(x) for x in category]
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in category]
+    _algo.group_neutralize(r, category, input)
+    return r
+  else:
+    category = _to_f64(category)
+    input = _to_f64(input)
+    r = np.empty_like(category)
+    _algo.group_neutralize(r, category, input)
+    return r
+
+def GROUP_RANK(
+  category: np.ndarray | list[np.ndarray], input: np.ndarray | list[np.ndarray]
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Calculate rank percentage within each category group at each time step
+  
+  For each time position, groups items by `category` value, then computes
+  rank percentage within each group. Same value gets averaged rank.
+  A category with a single valid value gets 0.5.
+  NaN in category or input produces NaN output.
+  See `ta_group_rank_ex` for other tie methods.
+  """
+  if isinstance(category, list) and isinstance(input, list):
+    category = [_to_f64(x) for x in category]
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in category]
+    _algo.group_rank(r, category, input)
+    return r
+  else:
+    category = _to_f64(category)
+    input = _to_f64(input)
+    r = np.empty_like(category)
+    _algo.group_rank(r, category, input)
+    return r
+
+def GROUP_RANK_EX(
+  category: np.ndarray | list[np.ndarray], input: np.ndarray | list[np.ndarray], method: int
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Calculate rank within each category group at each time step using a tie `method`
+  
+  `method` is one of `RANK_AVERAGE` (0), `RANK_MIN` (1), `RANK_MAX` (2), `RANK_DENSE` (3),
+  `RANK_ORDINAL` (4), optionally combined with `RANK_PCT` (8) and `RANK_DESCENDING` (16).
+  Ties of `RANK_ORDINAL` are ranked by group order.
+  NaN in category or input produces NaN output.
+  """
+  if isinstance(category, list) and isinstance(input, list):
+    category = [_to_f64(x) for x in category]
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in category]
+    _algo.group_rank_ex(r, category, input, method)
+    return r
+  else:
+    category = _to_f64(category)
+    input = _to_f64(input)
+    r = np.empty_like(category)
+    _algo.group_rank_ex(r, category, input, method)
+    return r
+
+def GROUP_SCALE(
+  category: np.ndarray | list[np.ndarray], input: np.ndarray | list[np.ndarray]
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Scale the input into [0, 1] within each category group at each time step
+  
+  r = (x - group_min) / (group_max - group_min).
+  Groups with zero range produce 0.5, NaN in category or input produces NaN output.
+  
+  Ref: WQ BRAIN group_scale
+  """
+  if isinstance(category, list) and isinstance(input, list):
+    category = [_to_f64(x) for x in category]
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in category]
+    _algo.group_scale(r, category, input)
+    return r
+  else:
+    category = _to_f64(category)
+    input = _to_f64(input)
+    r = np.empty_like(category)
+    _algo.group_scale(r, category, input)
+    return r
+
+def GROUP_SUM(
+  category: np.ndarray | list[np.ndarray], input: np.ndarray | list[np.ndarray]
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Calculate sum of each category group at each time step
+  
+  The sum of valid values is assigned to every member of the group.
+  Groups without valid values produce NaN.
+  """
+  if isinstance(category, list) and isinstance(input, list):
+    category = [_to_f64(x) for x in category]
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in category]
+    _algo.group_sum(r, category, input)
+    return r
+  else:
+    category = _to_f64(category)
+    input = _to_f64(input)
+    r = np.empty_like(category)
+    _algo.group_sum(r, category, input)
+    return r
+
+def GROUP_ZSCORE(
+  category: np.ndarray | list[np.ndarray], input: np.ndarray | list[np.ndarray]
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Calculate Z-Score within each category group at each time step
+  
+  For each time position, groups items by `category` value, then computes
+  (x - group_mean) / group_std within each group.
+  NaN in category or input produces NaN output.
+  Groups with fewer than 2 valid values produce NaN.
+  """
+  if isinstance(category, list) and isinstance(input, list):
+    category = [_to_f64(x) for x in category]
+    input = [_to_f64(x) for x in input]
     r = [np.empty_like(x) for x in category]
     _algo.group_zscore(r, category, input)
     return r
@@ -455,6 +1694,255 @@ def HHVBARS(
     _algo.hhvbars(r, input, periods)
     return r
 
+def HHVSINCE(
+  input: np.ndarray | list[np.ndarray], cond: np.ndarray | list[np.ndarray]
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Highest `input` since the last bar where `cond` is true, that bar
+  included, NaN before `cond` is first true
+  """
+  if isinstance(input, list) and isinstance(cond, list):
+    input = [_to_f64(x) for x in input]
+    cond = [_to_bool(x) for x in cond]
+    r = [np.empty_like(x) for x in input]
+    _algo.hhvsince(r, input, cond)
+    return r
+  else:
+    input = _to_f64(input)
+    cond = _to_bool(cond)
+    r = np.empty_like(input)
+    _algo.hhvsince(r, input, cond)
+    return r
+
+def HMA(
+  input: np.ndarray | list[np.ndarray], periods: int
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Hull Moving Average
+  
+  HMA = LWMA(2 * LWMA(input, periods / 2) - LWMA(input, periods),
+  floor(sqrt(periods))), NaN until the last LWMA has a full window.
+  
+  Ref: https://alanhull.com/hull-moving-average
+  """
+  if isinstance(input, list):
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in input]
+    _algo.hma(r, input, periods)
+    return r
+  else:
+    input = _to_f64(input)
+    r = np.empty_like(input)
+    _algo.hma(r, input, periods)
+    return r
+
+def HOLD(
+  cond: np.ndarray, periods: int
+) -> np.ndarray:
+  """
+  True on the bars where `cond` is true and the following `periods - 1`
+  bars, i.e. `cond` was true within the last `periods` bars
+  
+  Ref: https://www.amibroker.com/guide/afl/hold.html
+  """
+  cond = _to_bool(cond)
+  r = np.empty_like(cond, dtype=bool)
+  _algo.hold(r, cond, periods)
+  return r
+
+def HT_DCPERIOD(
+  input: np.ndarray | list[np.ndarray]
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Hilbert Transform - Dominant Cycle Period
+  
+  Ehlers' homodyne discriminator on a 4 bar WMA of the input, the period is
+  limited to [6, 50] bars and smoothed. First 32 bars are NaN as TA-Lib.
+  
+  Ref: John F. Ehlers, Rocket Science for Traders, chapter 7
+  """
+  if isinstance(input, list):
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in input]
+    _algo.ht_dcperiod(r, input)
+    return r
+  else:
+    input = _to_f64(input)
+    r = np.empty_like(input)
+    _algo.ht_dcperiod(r, input)
+    return r
+
+def HT_DCPHASE(
+  input: np.ndarray | list[np.ndarray]
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Hilbert Transform - Dominant Cycle Phase
+  
+  Phase in degrees of the smoothed input over the last dominant cycle,
+  within (-45, 315]. First 63 bars are NaN as TA-Lib.
+  
+  Ref: John F. Ehlers, Rocket Science for Traders, chapter 9
+  """
+  if isinstance(input, list):
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in input]
+    _algo.ht_dcphase(r, input)
+    return r
+  else:
+    input = _to_f64(input)
+    r = np.empty_like(input)
+    _algo.ht_dcphase(r, input)
+    return r
+
+def HT_PHASOR(
+  input: np.ndarray
+) -> tuple[np.ndarray, np.ndarray]:
+  """
+  Hilbert Transform - Phasor Components
+  
+  In-phase and quadrature components of the detrended smoothed input. First
+  32 bars are NaN as TA-Lib.
+  
+  Ref: John F. Ehlers, Rocket Science for Traders, chapter 7
+  """
+  input = _to_f64(input)
+  in_phase = np.empty_like(input, dtype=np.float64)
+  quadrature = np.empty_like(input, dtype=np.float64)
+  _algo.ht_phasor(in_phase, quadrature, input)
+  return in_phase, quadrature
+
+def HT_SINE(
+  input: np.ndarray
+) -> tuple[np.ndarray, np.ndarray]:
+  """
+  Hilbert Transform - SineWave
+  
+  sine = sin(DCPHASE), lead sine = sin(DCPHASE + 45), a crossing of the two
+  lines marks a cycle turning point. First 63 bars are NaN as TA-Lib.
+  
+  Ref: John F. Ehlers, Rocket Science for Traders, chapter 9
+  """
+  input = _to_f64(input)
+  sine = np.empty_like(input, dtype=np.float64)
+  lead_sine = np.empty_like(input, dtype=np.float64)
+  _algo.ht_sine(sine, lead_sine, input)
+  return sine, lead_sine
+
+def HT_TRENDLINE(
+  input: np.ndarray | list[np.ndarray]
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Hilbert Transform - Instantaneous Trendline
+  
+  4 bar WMA of the mean input over the dominant cycle. First 63 bars are NaN
+  as TA-Lib.
+  
+  Ref: John F. Ehlers, Rocket Science for Traders, chapter 10
+  """
+  if isinstance(input, list):
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in input]
+    _algo.ht_trendline(r, input)
+    return r
+  else:
+    input = _to_f64(input)
+    r = np.empty_like(input)
+    _algo.ht_trendline(r, input)
+    return r
+
+def HT_TRENDMODE(
+  input: np.ndarray | list[np.ndarray]
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Hilbert Transform - Trend vs Cycle Mode
+  
+  1 in trend mode and 0 in cycle mode, from the sine wave crossings, the
+  phase rate of change and the distance to the instantaneous trendline.
+  First 63 bars are NaN as TA-Lib.
+  
+  Ref: John F. Ehlers, Rocket Science for Traders, chapter 10
+  """
+  if isinstance(input, list):
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in input]
+    _algo.ht_trendmode(r, input)
+    return r
+  else:
+    input = _to_f64(input)
+    r = np.empty_like(input)
+    _algo.ht_trendmode(r, input)
+    return r
+
+def IC(
+  factor: np.ndarray | list[np.ndarray], returns: np.ndarray | list[np.ndarray]
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Information Coefficient (IC)
+  
+  Pearson correlation between factor and returns across securities at each
+  date, securities with NaN in either panel are excluded. The IC of a date is
+  written to every security of that date, NaN if fewer than 2 valid pairs.
+  `returns` is usually the forward return, e.g. the output of `FRET`.
+  """
+  if isinstance(factor, list) and isinstance(returns, list):
+    factor = [_to_f64(x) for x in factor]
+    returns = [_to_f64(x) for x in returns]
+    r = [np.empty_like(x) for x in factor]
+    _algo.ic(r, factor, returns)
+    return r
+  else:
+    factor = _to_f64(factor)
+    returns = _to_f64(returns)
+    r = np.empty_like(factor)
+    _algo.ic(r, factor, returns)
+    return r
+
+def IC_SUMMARY(
+  ic: np.ndarray, periods: int
+) -> tuple[np.ndarray, np.ndarray, np.ndarray, np.ndarray]:
+  """
+  IC summary statistics
+  
+  Rolling statistics of an IC series (e.g. output of `IC` or `RANK_IC`) over
+  the last `periods` values, `periods == 0` means expanding window, NaN
+  values are skipped. Outputs:
+  mean: mean IC
+  icir: mean IC / std of IC (sample std)
+  tstat: icir * sqrt(count)
+  hit_rate: ratio of IC > 0
+  """
+  ic = _to_f64(ic)
+  mean = np.empty_like(ic, dtype=np.float64)
+  icir = np.empty_like(ic, dtype=np.float64)
+  tstat = np.empty_like(ic, dtype=np.float64)
+  hit_rate = np.empty_like(ic, dtype=np.float64)
+  _algo.ic_summary(mean, icir, tstat, hit_rate, ic, periods)
+  return mean, icir, tstat, hit_rate
+
+def INFORMATION_RATIO(
+  input: np.ndarray | list[np.ndarray], benchmark: np.ndarray | list[np.ndarray], periods: int, annual: float
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Rolling Information Ratio of returns against a benchmark.
+  
+  IR = mean(input - benchmark) / stddev(input - benchmark) * sqrt(annual),
+  population stddev as `SHARPE`, `annual` is the number of bars per year.
+  
+  Ref: https://en.wikipedia.org/wiki/Information_ratio
+  """
+  if isinstance(input, list) and isinstance(benchmark, list):
+    input = [_to_f64(x) for x in input]
+    benchmark = [_to_f64(x) for x in benchmark]
+    r = [np.empty_like(x) for x in input]
+    _algo.information_ratio(r, input, benchmark, periods, annual)
+    return r
+  else:
+    input = _to_f64(input)
+    benchmark = _to_f64(benchmark)
+    r = np.empty_like(input)
+    _algo.information_ratio(r, input, benchmark, periods, annual)
+    return r
+
 def INTERCEPT(
   input: np.ndarray | list[np.ndarray], periods: int
 ) -> np.ndarray | list[np.ndarray]:
@@ -474,6 +1962,139 @@ def INTERCEPT(
     _algo.intercept(r, input, periods)
     return r
 
+def KALMAN_BETA(
+  input: np.ndarray, benchmark: np.ndarray, delta: float, obs_var: float
+) -> tuple[np.ndarray, np.ndarray, np.ndarray, np.ndarray]:
+  """
+  Kalman Filter Time-Varying Regression
+  
+  input = intercept + beta * benchmark + e, var(e) = obs_var, intercept and
+  beta follow random walks with variance `delta` per bar. Outputs the filtered
+  beta, intercept and their variances given data up to t, starting from a
+  diffuse prior.
+  
+  A bar with either value missing only adds process noise, FLAG_SKIP_NAN
+  skips it entirely, outputs are NaN on such bars.
+  
+  Ref: https://en.wikipedia.org/wiki/Kalman_filter
+  """
+  input = _to_f64(input)
+  benchmark = _to_f64(benchmark)
+  beta = np.empty_like(input, dtype=np.float64)
+  intercept = np.empty_like(input, dtype=np.float64)
+  beta_var = np.empty_like(input, dtype=np.float64)
+  intercept_var = np.empty_like(input, dtype=np.float64)
+  _algo.kalman_beta(beta, intercept, beta_var, intercept_var, input, benchmark, delta, obs_var)
+  return beta, intercept, beta_var, intercept_var
+
+def KALMAN_LEVEL(
+  input: np.ndarray, process_var: float, obs_var: float
+) -> tuple[np.ndarray, np.ndarray]:
+  """
+  Kalman Filter Local Level
+  
+  input = level + e, var(e) = obs_var, level follows a random walk with
+  variance `process_var` per bar. Outputs the filtered level and its variance,
+  the first valid value initializes the level with variance obs_var.
+  
+  A missing value only adds process noise, FLAG_SKIP_NAN skips it entirely,
+  outputs are NaN on such bars.
+  """
+  input = _to_f64(input)
+  level = np.empty_like(input, dtype=np.float64)
+  level_var = np.empty_like(input, dtype=np.float64)
+  _algo.kalman_level(level, level_var, input, process_var, obs_var)
+  return level, level_var
+
+def KALMAN_TREND(
+  input: np.ndarray, level_noise: float, slope_noise: float, obs_var: float
+) -> tuple[np.ndarray, np.ndarray, np.ndarray, np.ndarray]:
+  """
+  Kalman Filter Local Linear Trend
+  
+  input = level + e, var(e) = obs_var, level[t] = level[t - 1] + slope[t - 1]
+  plus noise of variance `level_noise`, slope follows a random walk with
+  variance `slope_noise`. Outputs the filtered level, slope and their
+  variances, the state is initialized from the first two valid values.
+  
+  A missing value only advances the state, FLAG_SKIP_NAN skips it entirely,
+  outputs are NaN on such bars.
+  """
+  input = _to_f64(input)
+  level = np.empty_like(input, dtype=np.float64)
+  slope = np.empty_like(input, dtype=np.float64)
+  level_var = np.empty_like(input, dtype=np.float64)
+  slope_var = np.empty_like(input, dtype=np.float64)
+  _algo.kalman_trend(level, slope, level_var, slope_var, input, level_noise, slope_noise, obs_var)
+  return level, slope, level_var, slope_var
+
+def KAMA(
+  input: np.ndarray | list[np.ndarray], periods: int, fast: int, slow: int
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Kaufman Adaptive Moving Average
+  
+  ER = |input - ref(input, periods)| / sum(|input - ref(input, 1)|, periods),
+  SC = (ER * (2 / (fast + 1) - 2 / (slow + 1)) + 2 / (slow + 1))^2,
+  KAMA = KAMA[-1] + SC * (input - KAMA[-1]), seeded with the previous input
+  and starting at bar `periods` as TA-Lib (which fixes fast = 2, slow = 30).
+  
+  Ref: https://school.stockcharts.com/doku.php?id=technical_indicators:kaufman_s_adaptive_moving_average
+  """
+  if isinstance(input, list):
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in input]
+    _algo.kama(r, input, periods, fast, slow)
+    return r
+  else:
+    input = _to_f64(input)
+    r = np.empty_like(input)
+    _algo.kama(r, input, periods, fast, slow)
+    return r
+
+def KDJ(
+  high: np.ndarray, low: np.ndarray, close: np.ndarray, n: int, m1: int, m2: int
+) -> tuple[np.ndarray, np.ndarray, np.ndarray]:
+  """
+  KDJ Indicator
+  
+  RSV = fast %K of `STOCH` over `n` bars, K = SMA(RSV, m1, 1),
+  D = SMA(K, m2, 1), J = 3 * K - 2 * D, the smoothing is seeded from the
+  first valid value as `SMA`.
+  
+  Ref: https://www.investopedia.com/terms/k/kdj.asp
+  """
+  high = _to_f64(high)
+  low = _to_f64(low)
+  close = _to_f64(close)
+  k = np.empty_like(high, dtype=np.float64)
+  d = np.empty_like(high, dtype=np.float64)
+  j = np.empty_like(high, dtype=np.float64)
+  _algo.kdj(k, d, j, high, low, close, n, m1, m2)
+  return k, d, j
+
+def KELTNER(
+  high: np.ndarray, low: np.ndarray, close: np.ndarray, periods: int, atr_periods: int, multiplier: float
+) -> tuple[np.ndarray, np.ndarray, np.ndarray]:
+  """
+  Keltner Channels
+  
+  middle = EMA(close, periods), upper / lower = middle +/- multiplier *
+  ATR(atr_periods). The EMA is seeded with the first close, under
+  FLAG_STRICTLY_CYCLE the outputs start once both the EMA has `periods`
+  closes and the ATR is warmed up, otherwise as soon as there is a true range.
+  
+  Ref: https://www.investopedia.com/terms/k/keltnerchannel.asp
+  """
+  high = _to_f64(high)
+  low = _to_f64(low)
+  close = _to_f64(close)
+  upper = np.empty_like(high, dtype=np.float64)
+  middle = np.empty_like(high, dtype=np.float64)
+  lower = np.empty_like(high, dtype=np.float64)
+  _algo.keltner(upper, middle, lower, high, low, close, periods, atr_periods, multiplier)
+  return upper, middle, lower
+
 def KURTOSIS(
   input: np.ndarray | list[np.ndarray], periods: int
 ) -> np.ndarray | list[np.ndarray]:
@@ -533,6 +2154,26 @@ def LLVBARS(
     _algo.llvbars(r, input, periods)
     return r
 
+def LLVSINCE(
+  input: np.ndarray | list[np.ndarray], cond: np.ndarray | list[np.ndarray]
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Lowest `input` since the last bar where `cond` is true, that bar included,
+  NaN before `cond` is first true
+  """
+  if isinstance(input, list) and isinstance(cond, list):
+    input = [_to_f64(x) for x in input]
+    cond = [_to_bool(x) for x in cond]
+    r = [np.empty_like(x) for x in input]
+    _algo.llvsince(r, input, cond)
+    return r
+  else:
+    input = _to_f64(input)
+    cond = _to_bool(cond)
+    r = np.empty_like(input)
+    _algo.llvsince(r, input, cond)
+    return r
+
 def LONGCROSS(
   a: np.ndarray | list[np.ndarray], b: np.ndarray | list[np.ndarray], n: int
 ) -> np.ndarray | list[np.ndarray]:
@@ -590,6 +2231,66 @@ def MA(
     _algo.ma(r, input, periods)
     return r
 
+def MACD(
+  input: np.ndarray, fast: int, slow: int, signal_periods: int, signal_type: int
+) -> tuple[np.ndarray, np.ndarray, np.ndarray]:
+  """
+  Moving Average Convergence / Divergence
+  
+  MACD = EMA(input, fast) - EMA(input, slow), signal = EMA or SMA of MACD
+  over `signal_periods` by `signal_type` (SIGNAL_EMA 0, SIGNAL_SMA 1) and
+  histogram = MACD - signal. The EMAs are seeded with the simple mean of
+  their first periods as TA-Lib, so the first max(fast, slow) - 1 MACD values
+  and the first signal_periods - 1 EMA signal values after them are NaN,
+  same as TA-Lib MACD after the lookback of max(fast, slow) + signal_periods - 2.
+  
+  Ref: https://www.investopedia.com/terms/m/macd.asp
+  """
+  input = _to_f64(input)
+  macd = np.empty_like(input, dtype=np.float64)
+  signal = np.empty_like(input, dtype=np.float64)
+  hist = np.empty_like(input, dtype=np.float64)
+  _algo.macd(macd, signal, hist, input, fast, slow, signal_periods, signal_type)
+  return macd, signal, hist
+
+def MACD_TDX(
+  input: np.ndarray, short: int, long: int, mid: int
+) -> tuple[np.ndarray, np.ndarray, np.ndarray]:
+  """
+  MACD of 通达信
+  
+  DIF = EMA(input, short) - EMA(input, long), DEA = EMA(DIF, mid) and
+  MACD = (DIF - DEA) * 2, as `MACD(SHORT, LONG, MID)` of 通达信, usually
+  short = 12, long = 26 and mid = 9. The EMAs are seeded with the first
+  value as 通达信, under FLAG_STRICTLY_CYCLE the first max(short, long) - 1
+  DIF values and the first mid - 1 DEA values are NaN.
+  """
+  input = _to_f64(input)
+  dif = np.empty_like(input, dtype=np.float64)
+  dea = np.empty_like(input, dtype=np.float64)
+  macd = np.empty_like(input, dtype=np.float64)
+  _algo.macd_tdx(dif, dea, macd, input, short, long, mid)
+  return dif, dea, macd
+
+def MAMA(
+  input: np.ndarray, fast_limit: float, slow_limit: float
+) -> tuple[np.ndarray, np.ndarray]:
+  """
+  MESA Adaptive Moving Average
+  
+  alpha = fast_limit / delta phase clamped to [slow_limit, fast_limit],
+  MAMA = alpha * input + (1 - alpha) * MAMA[-1] and
+  FAMA = alpha / 2 * MAMA + (1 - alpha / 2) * FAMA[-1]. Usually
+  fast_limit = 0.5 and slow_limit = 0.05, first 32 bars are NaN as TA-Lib.
+  
+  Ref: https://www.mesasoftware.com/papers/MAMA.pdf
+  """
+  input = _to_f64(input)
+  mama = np.empty_like(input, dtype=np.float64)
+  fama = np.empty_like(input, dtype=np.float64)
+  _algo.mama(mama, fama, input, fast_limit, slow_limit)
+  return mama, fama
+
 def MAX_DRAWDOWN(
   input: np.ndarray | list[np.ndarray], periods: int
 ) -> np.ndarray | list[np.ndarray]:
@@ -613,6 +2314,27 @@ def MAX_DRAWDOWN(
     _algo.max_drawdown(r, input, periods)
     return r
 
+def MFI(
+  high: np.ndarray, low: np.ndarray, close: np.ndarray, volume: np.ndarray, periods: int
+) -> np.ndarray:
+  """
+  Money Flow Index
+  
+  TP = (high + low + close) / 3, money flow TP * volume is positive when TP
+  rises and negative when it falls, MFI = 100 * positive / (positive +
+  negative) over the last `periods` flows, same as TA-Lib MFI. Requires
+  `periods + 1` valid bars.
+  
+  Ref: https://en.wikipedia.org/wiki/Money_flow_index
+  """
+  high = _to_f64(high)
+  low = _to_f64(low)
+  close = _to_f64(close)
+  volume = _to_f64(volume)
+  r = np.empty_like(high, dtype=np.float64)
+  _algo.mfi(r, high, low, close, volume, periods)
+  return r
+
 def MIN_MAX_DIFF(
   input: np.ndarray | list[np.ndarray], periods: int
 ) -> np.ndarray | list[np.ndarray]:
@@ -633,46 +2355,194 @@ def MIN_MAX_DIFF(
     _algo.min_max_diff(r, input, periods)
     return r
 
-def MOMENT(
-  input: np.ndarray | list[np.ndarray], periods: int, k: int
+def MOMENT(
+  input: np.ndarray | list[np.ndarray], periods: int, k: int
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Calculate rolling k-th central moment over a moving window
+  
+  MOMENT(x, d, k) = mean((x - mean)^k) over window of d periods.
+  This is the raw (non-adjusted) sample moment.
+  k=2 gives variance (population), k=3 gives raw third moment, etc.
+  """
+  if isinstance(input, list):
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in input]
+    _algo.moment(r, input, periods, k)
+    return r
+  else:
+    input = _to_f64(input)
+    r = np.empty_like(input)
+    _algo.moment(r, input, periods, k)
+    return r
+
+def NATR(
+  high: np.ndarray | list[np.ndarray], low: np.ndarray | list[np.ndarray], close: np.ndarray | list[np.ndarray], periods: int
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Normalized Average True Range
+  
+  NATR = ATR(periods) / close * 100
+  
+  Ref: https://ta-lib.github.io/ta-lib-python/func_groups/volatility_indicators.html
+  """
+  if isinstance(high, list) and isinstance(low, list) and isinstance(close, list):
+    high = [_to_f64(x) for x in high]
+    low = [_to_f64(x) for x in low]
+    close = [_to_f64(x) for x in close]
+    r = [np.empty_like(x) for x in high]
+    _algo.natr(r, high, low, close, periods)
+    return r
+  else:
+    high = _to_f64(high)
+    low = _to_f64(low)
+    close = _to_f64(close)
+    r = np.empty_like(high)
+    _algo.natr(r, high, low, close, periods)
+    return r
+
+def NEUTRALIZE(
+  category: np.ndarray | list[np.ndarray], input: np.ndarray | list[np.ndarray]
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Neutralize the effect of a categorical variable on a numeric variable
+  """
+  if isinstance(category, list) and isinstance(input, list):
+    category = [_to_f64(x) for x in category]
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in category]
+    _algo.neutralize(r, category, input)
+    return r
+  else:
+    category = _to_f64(category)
+    input = _to_f64(input)
+    r = np.empty_like(category)
+    _algo.neutralize(r, category, input)
+    return r
+
+def OBV(
+  close: np.ndarray | list[np.ndarray], volume: np.ndarray | list[np.ndarray]
+) -> np.ndarray | list[np.ndarray]:
+  """
+  On Balance Volume
+  
+  OBV = OBV[-1] + volume if close > ref(close, 1), - volume if close <
+  ref(close, 1), starting with the first volume as TA-Lib. The running total
+  restarts after a missing bar unless FLAG_SKIP_NAN.
+  
+  Ref: https://www.investopedia.com/terms/o/onbalancevolume.asp
+  """
+  if isinstance(close, list) and isinstance(volume, list):
+    close = [_to_f64(x) for x in close]
+    volume = [_to_f64(x) for x in volume]
+    r = [np.empty_like(x) for x in close]
+    _algo.obv(r, close, volume)
+    return r
+  else:
+    close = _to_f64(close)
+    volume = _to_f64(volume)
+    r = np.empty_like(close)
+    _algo.obv(r, close, volume)
+    return r
+
+def OMEGA(
+  input: np.ndarray | list[np.ndarray], threshold: float, periods: int
 ) -> np.ndarray | list[np.ndarray]:
   """
-  Calculate rolling k-th central moment over a moving window
+  Rolling Omega Ratio of returns.
   
-  MOMENT(x, d, k) = mean((x - mean)^k) over window of d periods.
-  This is the raw (non-adjusted) sample moment.
-  k=2 gives variance (population), k=3 gives raw third moment, etc.
+  Omega = sum(max(input - threshold, 0)) / sum(max(threshold - input, 0)),
+  the probability weighted gains over losses relative to `threshold`.
+  
+  Ref: https://en.wikipedia.org/wiki/Omega_ratio
   """
   if isinstance(input, list):
     input = [_to_f64(x) for x in input]
     r = [np.empty_like(x) for x in input]
-    _algo.moment(r, input, periods, k)
+    _algo.omega(r, input, threshold, periods)
     return r
   else:
     input = _to_f64(input)
     r = np.empty_like(input)
-    _algo.moment(r, input, periods, k)
+    _algo.omega(r, input, threshold, periods)
     return r
 
-def NEUTRALIZE(
-  category: np.ndarray | list[np.ndarray], input: np.ndarray | list[np.ndarray]
+def PARKINSON_VOL(
+  high: np.ndarray | list[np.ndarray], low: np.ndarray | list[np.ndarray], periods: int, annual: float
 ) -> np.ndarray | list[np.ndarray]:
   """
-  Neutralize the effect of a categorical variable on a numeric variable
+  Parkinson Volatility
+  
+  var = mean(ln(high / low)^2) / (4 * ln(2)) over the window,
+  result is sqrt(var * annual), e.g. annual = 252 for daily bars.
+  
+  Ref: Parkinson, M. (1980). The Extreme Value Method for Estimating the
+  Variance of the Rate of Return.
   """
-  if isinstance(category, list) and isinstance(input, list):
-    category = [_to_f64(x) for x in category]
-    input = [_to_f64(x) for x in input]
-    r = [np.empty_like(x) for x in category]
-    _algo.neutralize(r, category, input)
+  if isinstance(high, list) and isinstance(low, list):
+    high = [_to_f64(x) for x in high]
+    low = [_to_f64(x) for x in low]
+    r = [np.empty_like(x) for x in high]
+    _algo.parkinson_vol(r, high, low, periods, annual)
     return r
   else:
-    category = _to_f64(category)
-    input = _to_f64(input)
-    r = np.empty_like(category)
-    _algo.neutralize(r, category, input)
+    high = _to_f64(high)
+    low = _to_f64(low)
+    r = np.empty_like(high)
+    _algo.parkinson_vol(r, high, low, periods, annual)
     return r
 
+def PORTFOLIO_SIM(
+  target: np.ndarray, returns: np.ndarray, is_calc: np.ndarray, cost: float, delay: int
+) -> tuple[np.ndarray, np.ndarray, np.ndarray, np.ndarray, np.ndarray, np.ndarray]:
+  """
+  Weight based portfolio simulator
+  
+  `target` is the target weight (relative to NAV) decided at each date, NaN
+  means 0. It is traded `delay` dates later, except for securities whose
+  `is_calc` is 0 or NaN at the trading date (e.g. suspended or limit up/down),
+  which keep their drifted weight. Holdings at date t earn `returns` at t+1,
+  `returns` is the period return ending at each date (e.g. close / prev close - 1),
+  NaN return counts as 0. Trading costs `cost` per unit of traded weight.
+  Outputs, per date values are written to every security of the date:
+  ret: portfolio return before cost
+  turnover: sum of absolute weight changes at the date
+  gross: gross exposure, sum of absolute holdings
+  net: net exposure, sum of holdings
+  pnl: portfolio return after cost, ret - cost * turnover
+  holdings: realized weight of each security after trading
+  """
+  target = _to_f64(target)
+  returns = _to_f64(returns)
+  is_calc = _to_f64(is_calc)
+  ret = np.empty_like(target, dtype=np.float64)
+  turnover = np.empty_like(target, dtype=np.float64)
+  gross = np.empty_like(target, dtype=np.float64)
+  net = np.empty_like(target, dtype=np.float64)
+  pnl = np.empty_like(target, dtype=np.float64)
+  holdings = np.empty_like(target, dtype=np.float64)
+  _algo.portfolio_sim(ret, turnover, gross, net, pnl, holdings, target, returns, is_calc, cost, delay)
+  return ret, turnover, gross, net, pnl, holdings
+
+def PPO(
+  input: np.ndarray, fast: int, slow: int, signal_periods: int, signal_type: int
+) -> tuple[np.ndarray, np.ndarray, np.ndarray]:
+  """
+  Percentage Price Oscillator
+  
+  PPO = (EMA(input, fast) - EMA(input, slow)) / EMA(input, slow) * 100, with
+  signal line and histogram as `MACD`, NaN when the slow EMA is 0. Same
+  warm-up as `MACD`, same as TA-Lib PPO with matype = 1 (EMA).
+  
+  Ref: https://www.investopedia.com/terms/p/ppo.asp
+  """
+  input = _to_f64(input)
+  ppo = np.empty_like(input, dtype=np.float64)
+  signal = np.empty_like(input, dtype=np.float64)
+  hist = np.empty_like(input, dtype=np.float64)
+  _algo.ppo(ppo, signal, hist, input, fast, slow, signal_periods, signal_type)
+  return ppo, signal, hist
+
 def PRODUCT(
   input: np.ndarray | list[np.ndarray], periods: int
 ) -> np.ndarray | list[np.ndarray]:
@@ -726,6 +2596,7 @@ def RANK(
   
   Uses min-rank method for ties (same as pandas rankdata method='min').
   NaN values are treated as larger than all non-NaN values.
+  See `ta_rank_ex` for other tie methods.
   """
   if isinstance(input, list):
     input = [_to_f64(x) for x in input]
@@ -738,6 +2609,75 @@ def RANK(
     _algo.rank(r, input, periods)
     return r
 
+def RANK_AUTOCORR(
+  input: np.ndarray | list[np.ndarray], lag: int
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Factor rank autocorrelation
+  
+  Spearman correlation between the cross-section of `input` at each date and
+  the cross-section `lag` dates before, securities with NaN at either date
+  are excluded. Low values mean the factor ranking changes quickly (high
+  turnover). The value of a date is written to every security of that date.
+  """
+  if isinstance(input, list):
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in input]
+    _algo.rank_autocorr(r, input, lag)
+    return r
+  else:
+    input = _to_f64(input)
+    r = np.empty_like(input)
+    _algo.rank_autocorr(r, input, lag)
+    return r
+
+def RANK_EX(
+  input: np.ndarray | list[np.ndarray], periods: int, method: int
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Calculate rank in a sliding window with size `periods` using a tie `method`
+  
+  `method` is one of `RANK_AVERAGE` (0), `RANK_MIN` (1), `RANK_MAX` (2), `RANK_DENSE` (3),
+  `RANK_ORDINAL` (4), optionally combined with `RANK_PCT` (8) and `RANK_DESCENDING` (16).
+  The current value is the latest one of its ties for `RANK_ORDINAL`.
+  NaN values are excluded from the window, NaN input produces NaN output.
+  If periods is 0, the window expands from the first value.
+  
+  Ref: https://pandas.pydata.org/docs/reference/api/pandas.core.window.rolling.Rolling.rank.html
+  """
+  if isinstance(input, list):
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in input]
+    _algo.rank_ex(r, input, periods, method)
+    return r
+  else:
+    input = _to_f64(input)
+    r = np.empty_like(input)
+    _algo.rank_ex(r, input, periods, method)
+    return r
+
+def RANK_IC(
+  factor: np.ndarray | list[np.ndarray], returns: np.ndarray | list[np.ndarray]
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Rank Information Coefficient (RankIC)
+  
+  Spearman correlation between factor and returns across securities at each
+  date, ties get the average rank. Same layout and NaN handling as `IC`.
+  """
+  if isinstance(factor, list) and isinstance(returns, list):
+    factor = [_to_f64(x) for x in factor]
+    returns = [_to_f64(x) for x in returns]
+    r = [np.empty_like(x) for x in factor]
+    _algo.rank_ic(r, factor, returns)
+    return r
+  else:
+    factor = _to_f64(factor)
+    returns = _to_f64(returns)
+    r = np.empty_like(factor)
+    _algo.rank_ic(r, factor, returns)
+    return r
+
 def RCROSS(
   a: np.ndarray | list[np.ndarray], b: np.ndarray | list[np.ndarray]
 ) -> np.ndarray | list[np.ndarray]:
@@ -838,6 +2778,72 @@ def RLONGCROSS(
     _algo.rlongcross(r, a, b, n)
     return r
 
+def ROGERS_SATCHELL_VOL(
+  open: np.ndarray, high: np.ndarray, low: np.ndarray, close: np.ndarray, periods: int, annual: float
+) -> np.ndarray:
+  """
+  Rogers-Satchell Volatility
+  
+  var = mean(ln(high / close) * ln(high / open) + ln(low / close) * ln(low / open)),
+  result is sqrt(var * annual), unbiased under a drift.
+  
+  Ref: Rogers, L. C. G., Satchell, S. E. (1991). Estimating Variance From
+  High, Low and Closing Prices.
+  """
+  open = _to_f64(open)
+  high = _to_f64(high)
+  low = _to_f64(low)
+  close = _to_f64(close)
+  r = np.empty_like(open, dtype=np.float64)
+  _algo.rogers_satchell_vol(r, open, high, low, close, periods, annual)
+  return r
+
+def RSI(
+  input: np.ndarray | list[np.ndarray], periods: int
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Relative Strength Index
+  
+  RSI = 100 * avg gain / (avg gain + avg loss), averages start as the mean of
+  the first `periods` changes and follow Wilder smoothing
+  avg = (avg * (periods - 1) + x) / periods, same as TA-Lib RSI, NaN during
+  the warm-up of `periods` changes.
+  
+  With FLAG_SKIP_NAN missing values are skipped, otherwise a missing value
+  restarts the warm-up.
+  
+  Ref: https://en.wikipedia.org/wiki/Relative_strength_index
+  """
+  if isinstance(input, list):
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in input]
+    _algo.rsi(r, input, periods)
+    return r
+  else:
+    input = _to_f64(input)
+    r = np.empty_like(input)
+    _algo.rsi(r, input, periods)
+    return r
+
+def SAR(
+  high: np.ndarray, low: np.ndarray, acceleration: float, maximum: float
+) -> tuple[np.ndarray, np.ndarray]:
+  """
+  Parabolic SAR
+  
+  Stop and reverse points with acceleration factor starting at
+  `acceleration`, increased by it at each new extreme up to `maximum`, same
+  as TA-Lib SAR. `direction` is 1 for long and -1 for short after the bar.
+  
+  Ref: https://en.wikipedia.org/wiki/Parabolic_SAR
+  """
+  high = _to_f64(high)
+  low = _to_f64(low)
+  sar = np.empty_like(high, dtype=np.float64)
+  direction = np.empty_like(high, dtype=np.float64)
+  _algo.sar(sar, direction, high, low, acceleration, maximum)
+  return sar, direction
+
 def SCAN_ADD(
   input: np.ndarray | list[np.ndarray], condition: np.ndarray | list[np.ndarray]
 ) -> np.ndarray | list[np.ndarray]:
@@ -963,6 +2969,48 @@ def SMA(
     _algo.sma(r, input, n, m)
     return r
 
+def SMA_EX(
+  input: np.ndarray | list[np.ndarray], n: int, m: int, flags: int
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Exponential Moving Average, pandas compatible, weight = m / n
+  
+  Same options as `EMA_EX`.
+  """
+  if isinstance(input, list):
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in input]
+    _algo.sma_ex(r, input, n, m, flags)
+    return r
+  else:
+    input = _to_f64(input)
+    r = np.empty_like(input)
+    _algo.sma_ex(r, input, n, m, flags)
+    return r
+
+def SORTINO(
+  input: np.ndarray | list[np.ndarray], target: float, periods: int, annual: float
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Rolling Sortino Ratio of returns.
+  
+  Sortino = mean(input - target) / sqrt(mean(min(input - target, 0)^2)) * sqrt(annual),
+  `target` is the per bar minimum acceptable return, `annual` the number of
+  bars per year (1 for no annualisation).
+  
+  Ref: https://en.wikipedia.org/wiki/Sortino_ratio
+  """
+  if isinstance(input, list):
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in input]
+    _algo.sortino(r, input, target, periods, annual)
+    return r
+  else:
+    input = _to_f64(input)
+    r = np.empty_like(input)
+    _algo.sortino(r, input, target, periods, annual)
+    return r
+
 def STDDEV(
   input: np.ndarray | list[np.ndarray], periods: int
 ) -> np.ndarray | list[np.ndarray]:
@@ -982,6 +3030,26 @@ def STDDEV(
     _algo.stddev(r, input, periods)
     return r
 
+def STOCH(
+  high: np.ndarray, low: np.ndarray, close: np.ndarray, fastk: int, slowk: int, slowd: int
+) -> tuple[np.ndarray, np.ndarray]:
+  """
+  Stochastic Oscillator
+  
+  fast %K = 100 * (close - LLV(low, fastk)) / (HHV(high, fastk) - LLV(low, fastk)),
+  %K = MA(fast %K, slowk), %D = MA(%K, slowd), same as TA-Lib STOCH with
+  SMA smoothing after the warm-up.
+  
+  Ref: https://en.wikipedia.org/wiki/Stochastic_oscillator
+  """
+  high = _to_f64(high)
+  low = _to_f64(low)
+  close = _to_f64(close)
+  k = np.empty_like(high, dtype=np.float64)
+  d = np.empty_like(high, dtype=np.float64)
+  _algo.stoch(k, d, high, low, close, fastk, slowk, slowd)
+  return k, d
+
 def SUM(
   input: np.ndarray | list[np.ndarray], periods: int
 ) -> np.ndarray | list[np.ndarray]:
@@ -1043,6 +3111,214 @@ def SUMIF(
     _algo.sumif(r, input, condition, periods)
     return r
 
+def SUMSINCE(
+  input: np.ndarray | list[np.ndarray], cond: np.ndarray | list[np.ndarray]
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Sum of `input` since the last bar where `cond` is true, that bar included,
+  NaN before `cond` is first true
+  
+  Ref: https://www.amibroker.com/guide/afl/sumsince.html
+  """
+  if isinstance(input, list) and isinstance(cond, list):
+    input = [_to_f64(x) for x in input]
+    cond = [_to_bool(x) for x in cond]
+    r = [np.empty_like(x) for x in input]
+    _algo.sumsince(r, input, cond)
+    return r
+  else:
+    input = _to_f64(input)
+    cond = _to_bool(cond)
+    r = np.empty_like(input)
+    _algo.sumsince(r, input, cond)
+    return r
+
+def SUPERTREND(
+  high: np.ndarray, low: np.ndarray, close: np.ndarray, periods: int, multiplier: float
+) -> tuple[np.ndarray, np.ndarray]:
+  """
+  Supertrend
+  
+  Bands at (high + low) / 2 -/+ multiplier * ATR(periods), the lower band
+  only rises in an up trend and the upper band only falls in a down trend,
+  the trend flips when close crosses the opposite band. Outputs the lower
+  band in an up trend and the upper band in a down trend, `direction` is 1
+  or -1, the first direction is up when close >= (high + low) / 2.
+  
+  Ref: https://www.investopedia.com/supertrend-indicator-7976167
+  """
+  high = _to_f64(high)
+  low = _to_f64(low)
+  close = _to_f64(close)
+  line = np.empty_like(high, dtype=np.float64)
+  direction = np.empty_like(high, dtype=np.float64)
+  _algo.supertrend(line, direction, high, low, close, periods, multiplier)
+  return line, direction
+
+def T3(
+  input: np.ndarray | list[np.ndarray], periods: int, vfactor: float
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Tillson T3 Moving Average
+  
+  GD(x) = EMA(x) * (1 + vfactor) - EMA(EMA(x)) * vfactor, T3 = GD(GD(GD(input)))
+  computed from a chain of six EMAs, the first 6 * (periods - 1) bars are NaN
+  as TA-Lib. `vfactor` is usually 0.7.
+  
+  Ref: https://www.tradingpedia.com/forex-trading-indicators/t3-moving-average-indicator/
+  """
+  if isinstance(input, list):
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in input]
+    _algo.t3(r, input, periods, vfactor)
+    return r
+  else:
+    input = _to_f64(input)
+    r = np.empty_like(input)
+    _algo.t3(r, input, periods, vfactor)
+    return r
+
+def TEMA(
+  input: np.ndarray | list[np.ndarray], periods: int
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Triple Exponential Moving Average
+  
+  TEMA = 3 * EMA1 - 3 * EMA2 + EMA3 with EMA2 = EMA(EMA1), EMA3 = EMA(EMA2),
+  the first 3 * (periods - 1) bars are NaN as TA-Lib.
+  
+  Ref: https://www.investopedia.com/terms/t/triple-exponential-moving-average.asp
+  """
+  if isinstance(input, list):
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in input]
+    _algo.tema(r, input, periods)
+    return r
+  else:
+    input = _to_f64(input)
+    r = np.empty_like(input)
+    _algo.tema(r, input, periods)
+    return r
+
+def TR(
+  high: np.ndarray | list[np.ndarray], low: np.ndarray | list[np.ndarray], close: np.ndarray | list[np.ndarray]
+) -> np.ndarray | list[np.ndarray]:
+  """
+  True Range
+  
+  TR = max(high - low, |high - ref(close, 1)|, |low - ref(close, 1)|), NaN on
+  the first bar as there is no previous close.
+  
+  Ref: https://www.investopedia.com/terms/a/atr.asp
+  """
+  if isinstance(high, list) and isinstance(low, list) and isinstance(close, list):
+    high = [_to_f64(x) for x in high]
+    low = [_to_f64(x) for x in low]
+    close = [_to_f64(x) for x in close]
+    r = [np.empty_like(x) for x in high]
+    _algo.tr(r, high, low, close)
+    return r
+  else:
+    high = _to_f64(high)
+    low = _to_f64(low)
+    close = _to_f64(close)
+    r = np.empty_like(high)
+    _algo.tr(r, high, low, close)
+    return r
+
+def TREYNOR(
+  input: np.ndarray | list[np.ndarray], benchmark: np.ndarray | list[np.ndarray], periods: int, annual: float
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Rolling Treynor Ratio of returns against a benchmark.
+  
+  Treynor = mean(input) * annual / beta, beta = cov(input, benchmark) /
+  var(benchmark) as `BETA`, returns are expected in excess of the risk free
+  rate, `annual` is the number of bars per year.
+  
+  Ref: https://en.wikipedia.org/wiki/Treynor_ratio
+  """
+  if isinstance(input, list) and isinstance(benchmark, list):
+    input = [_to_f64(x) for x in input]
+    benchmark = [_to_f64(x) for x in benchmark]
+    r = [np.empty_like(x) for x in input]
+    _algo.treynor(r, input, benchmark, periods, annual)
+    return r
+  else:
+    input = _to_f64(input)
+    benchmark = _to_f64(benchmark)
+    r = np.empty_like(input)
+    _algo.treynor(r, input, benchmark, periods, annual)
+    return r
+
+def TRIX(
+  input: np.ndarray, periods: int, signal_periods: int, signal_type: int
+) -> tuple[np.ndarray, np.ndarray, np.ndarray]:
+  """
+  Triple Exponential Average
+  
+  TRIX = (EMA3 - ref(EMA3, 1)) / ref(EMA3, 1) * 100 with EMA3 =
+  EMA(EMA(EMA(input, periods), periods), periods), with signal line and
+  histogram as `MACD`. Each EMA is seeded with the simple mean of its first
+  `periods` values, so the first 3 * (periods - 1) + 1 values are NaN, same
+  as TA-Lib TRIX.
+  
+  Ref: https://www.investopedia.com/terms/t/trix.asp
+  """
+  input = _to_f64(input)
+  trix = np.empty_like(input, dtype=np.float64)
+  signal = np.empty_like(input, dtype=np.float64)
+  hist = np.empty_like(input, dtype=np.float64)
+  _algo.trix(trix, signal, hist, input, periods, signal_periods, signal_type)
+  return trix, signal, hist
+
+def TS_VWAP(
+  price: np.ndarray | list[np.ndarray], volume: np.ndarray | list[np.ndarray], periods: int
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Rolling Volume Weighted Average Price
+  
+  VWAP = sum(price * volume, periods) / sum(volume, periods), NaN when the
+  window has no volume.
+  
+  Ref: https://www.investopedia.com/terms/v/vwap.asp
+  """
+  if isinstance(price, list) and isinstance(volume, list):
+    price = [_to_f64(x) for x in price]
+    volume = [_to_f64(x) for x in volume]
+    r = [np.empty_like(x) for x in price]
+    _algo.ts_vwap(r, price, volume, periods)
+    return r
+  else:
+    price = _to_f64(price)
+    volume = _to_f64(volume)
+    r = np.empty_like(price)
+    _algo.ts_vwap(r, price, volume, periods)
+    return r
+
+def VALUEWHEN(
+  input: np.ndarray | list[np.ndarray], cond: np.ndarray | list[np.ndarray], n: int
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Value of `input` at the `n`th most recent bar where `cond` is true, n = 1
+  is the last one, NaN until `cond` has been true `n` times. With
+  FLAG_SKIP_NAN a bar where `input` is NaN does not count as an occurrence.
+  
+  Ref: https://www.amibroker.com/guide/afl/valuewhen.html
+  """
+  if isinstance(input, list) and isinstance(cond, list):
+    input = [_to_f64(x) for x in input]
+    cond = [_to_bool(x) for x in cond]
+    r = [np.empty_like(x) for x in input]
+    _algo.valuewhen(r, input, cond, n)
+    return r
+  else:
+    input = _to_f64(input)
+    cond = _to_bool(cond)
+    r = np.empty_like(input)
+    _algo.valuewhen(r, input, cond, n)
+    return r
+
 def VAR(
   input: np.ndarray | list[np.ndarray], periods: int
 ) -> np.ndarray | list[np.ndarray]:
@@ -1082,6 +3358,119 @@ def WEIGHTED_DELAY(
     _algo.weighted_delay(r, input, periods)
     return r
 
+def WILLR(
+  high: np.ndarray | list[np.ndarray], low: np.ndarray | list[np.ndarray], close: np.ndarray | list[np.ndarray], periods: int
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Williams %R
+  
+  %R = -100 * (HHV(high, n) - close) / (HHV(high, n) - LLV(low, n)),
+  0 for a flat window, same as TA-Lib WILLR after the warm-up.
+  
+  Ref: https://en.wikipedia.org/wiki/Williams_%25R
+  """
+  if isinstance(high, list) and isinstance(low, list) and isinstance(close, list):
+    high = [_to_f64(x) for x in high]
+    low = [_to_f64(x) for x in low]
+    close = [_to_f64(x) for x in close]
+    r = [np.empty_like(x) for x in high]
+    _algo.willr(r, high, low, close, periods)
+    return r
+  else:
+    high = _to_f64(high)
+    low = _to_f64(low)
+    close = _to_f64(close)
+    r = np.empty_like(high)
+    _algo.willr(r, high, low, close, periods)
+    return r
+
+def YANG_ZHANG_VOL(
+  open: np.ndarray, high: np.ndarray, low: np.ndarray, close: np.ndarray, periods: int, annual: float
+) -> np.ndarray:
+  """
+  Yang-Zhang Volatility
+  
+  var = var(overnight) + k * var(open to close) + (1 - k) * RS,
+  k = 0.34 / (1.34 + (n + 1) / (n - 1)), overnight = ln(open / previous close),
+  RS is the Rogers-Satchell variance, result is sqrt(var * annual).
+  A bar needs the previous close, at least 2 bars are required.
+  
+  Ref: Yang, D., Zhang, Q. (2000). Drift-Independent Volatility Estimation
+  Based on High, Low, Open, and Close Prices.
+  """
+  open = _to_f64(open)
+  high = _to_f64(high)
+  low = _to_f64(low)
+  close = _to_f64(close)
+  r = np.empty_like(open, dtype=np.float64)
+  _algo.yang_zhang_vol(r, open, high, low, close, periods, annual)
+  return r
+
+def ZIG(
+  input: np.ndarray, percent: float
+) -> tuple[np.ndarray, np.ndarray]:
+  """
+  ZigZag line with peak / trough markers
+  
+  A pivot is a running high (low) followed by a fall (rise) of at least
+  `percent` %, the line joins the first bar, the pivots, the extreme of the
+  last leg and the last bar linearly, `marker` is 1 at peaks, -1 at troughs
+  and 0 elsewhere, as ZIG / PEAK / TROUGH of 通达信. Without FLAG_SKIP_NAN
+  a missing bar starts a new line.
+  
+  NOTE: pivots are only known once the reversal happens, the line and
+  markers look ahead and must not be used as signals, see `ZIG_CONFIRMED`.
+  
+  Ref: https://www.investopedia.com/terms/z/zig_zag_indicator.asp
+  """
+  input = _to_f64(input)
+  zig = np.empty_like(input, dtype=np.float64)
+  marker = np.empty_like(input, dtype=np.float64)
+  _algo.zig(zig, marker, input, percent)
+  return zig, marker
+
+def ZIG_CONFIRMED(
+  input: np.ndarray, percent: float
+) -> tuple[np.ndarray, np.ndarray, np.ndarray]:
+  """
+  ZigZag pivots without look-ahead
+  
+  Same pivots as `ZIG`, but reported on the bar where the `percent` %
+  reversal confirms them: `marker` is 1 when a peak is confirmed, -1 when a
+  trough is confirmed and 0 elsewhere, `pivot` holds the value of the last
+  confirmed pivot and `bars` the number of bars since it, NaN before the
+  first confirmation. Safe to use in backtests. Without FLAG_SKIP_NAN a
+  missing bar restarts the detection.
+  """
+  input = _to_f64(input)
+  pivot = np.empty_like(input, dtype=np.float64)
+  marker = np.empty_like(input, dtype=np.float64)
+  bars = np.empty_like(input, dtype=np.float64)
+  _algo.zig_confirmed(pivot, marker, bars, input, percent)
+  return pivot, marker, bars
+
+def ZLEMA(
+  input: np.ndarray | list[np.ndarray], periods: int
+) -> np.ndarray | list[np.ndarray]:
+  """
+  Zero Lag Exponential Moving Average
+  
+  ZLEMA = EMA(input + (input - ref(input, (periods - 1) / 2)), periods),
+  the EMA is seeded with the mean of its first `periods` values.
+  
+  Ref: https://en.wikipedia.org/wiki/Zero_lag_exponential_moving_average
+  """
+  if isinstance(input, list):
+    input = [_to_f64(x) for x in input]
+    r = [np.empty_like(x) for x in input]
+    _algo.zlema(r, input, periods)
+    return r
+  else:
+    input = _to_f64(input)
+    r = np.empty_like(input)
+    _algo.zlema(r, input, periods)
+    return r
+
 def ZSCORE(
   input: np.ndarray | list[np.ndarray], periods: int
 ) -> np.ndarray | list[np.ndarray]:
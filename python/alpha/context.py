@@ -203,13 +203,7 @@ class ExecContext:
     )
 
   def _calc_TR(self):
-    return np.maximum(
-      np.maximum(
-        self.HIGH - self.LOW,
-        np.abs(self.HIGH - alpha.REF(self.CLOSE, 1)),
-      ),
-      np.abs(self.LOW - alpha.REF(self.CLOSE, 1)),
-    )
+    return alpha.TR(self.HIGH, self.LOW, self.CLOSE)
 
   # ====================================================================
   #  TS_ — Time-Series / Rolling Window Operators
@@ -252,6 +246,74 @@ class ExecContext:
   def DMA(self, a: np.ndarray, weight: float) -> np.ndarray:
     return alpha.DMA(a, float(weight))
 
+  def EMA_EX(self, a: np.ndarray, w: int, flags: int = 0) -> np.ndarray:
+    """pandas `ewm(span=w).mean()`, flags: EWM_ADJUST."""
+    return alpha.EMA_EX(a, int(w), int(flags))
+
+  def SMA_EX(self, a: np.ndarray, n: int, m: int, flags: int = 0) -> np.ndarray:
+    return alpha.SMA_EX(a, int(n), int(m), int(flags))
+
+  def DMA_EX(self, a: np.ndarray, weight: float, flags: int = 0) -> np.ndarray:
+    return alpha.DMA_EX(a, float(weight), int(flags))
+
+  # ── TS: Adaptive / Low-Lag Moving Averages ────────────────────────
+  #   TA-Lib: KAMA, DEMA, TEMA, T3
+
+  def KAMA(
+    self, a: np.ndarray, w: int = 10, fast: int = 2, slow: int = 30
+  ) -> np.ndarray:
+    return alpha.KAMA(a, int(w), int(fast), int(slow))
+
+  def DEMA(self, a: np.ndarray, w: int) -> np.ndarray:
+    return alpha.DEMA(a, int(w))
+
+  def TEMA(self, a: np.ndarray, w: int) -> np.ndarray:
+    return alpha.TEMA(a, int(w))
+
+  def T3(self, a: np.ndarray, w: int = 5, vfactor: float = 0.7) -> np.ndarray:
+    return alpha.T3(a, int(w), float(vfactor))
+
+  def HMA(self, a: np.ndarray, w: int) -> np.ndarray:
+    return alpha.HMA(a, int(w))
+
+  def ZLEMA(self, a: np.ndarray, w: int) -> np.ndarray:
+    return alpha.ZLEMA(a, int(w))
+
+  def ALMA(
+    self, a: np.ndarray, w: int = 9, offset: float = 0.85, sigma: float = 6.0
+  ) -> np.ndarray:
+    return alpha.ALMA(a, int(w), float(offset), float(sigma))
+
+  def FRAMA(self, a: np.ndarray, w: int = 16) -> np.ndarray:
+    return alpha.FRAMA(a, int(w))
+
+  # ── TS: Exponentially Weighted Statistics ─────────────────────────
+  #   pandas ewm compatible, `flags` combines EWM_* constants
+
+  def EWM_VAR(self, a: np.ndarray, decay: float, flags: int = 0) -> np.ndarray:
+    return alpha.EWM_VAR(a, float(decay), int(flags))
+
+  def EWM_STD(self, a: np.ndarray, decay: float, flags: int = 0) -> np.ndarray:
+    return alpha.EWM_STD(a, float(decay), int(flags))
+
+  def EWM_COV(
+    self, a: np.ndarray, b: np.ndarray, decay: float, flags: int = 0
+  ) -> np.ndarray:
+    return alpha.EWM_COV(a, b, float(decay), int(flags))
+
+  def EWM_CORR(
+    self, a: np.ndarray, b: np.ndarray, decay: float, flags: int = 0
+  ) -> np.ndarray:
+    return alpha.EWM_CORR(a, b, float(decay), int(flags))
+
+  def EWM_BETA(
+    self, a: np.ndarray, b: np.ndarray, decay: float, flags: int = 0
+  ) -> np.ndarray:
+    return alpha.EWM_BETA(a, b, float(decay), int(flags))
+
+  def EWM_ZSCORE(self, a: np.ndarray, decay: float, flags: int = 0) -> np.ndarray:
+    return alpha.EWM_ZSCORE(a, float(decay), int(flags))
+
   # ── TS: Std Dev / Variance ─────────────────────────────────────────
   #   BRAIN: ts_std_dev    GTJA: STD               wq101: STDDEV
 
@@ -266,6 +328,44 @@ class ExecContext:
 
   VAR = TS_VARIANCE  # gtja191
 
+  # ── TS: Range-Based Volatility (OHLC) ─────────────────────────────
+  #   `annual` scales the per-bar variance, e.g. 252 for daily bars
+
+  def PARKINSON_VOL(
+    self, high: np.ndarray, low: np.ndarray, w: int, annual: float = 1.0
+  ) -> np.ndarray:
+    return alpha.PARKINSON_VOL(high, low, int(w), float(annual))
+
+  def GARMAN_KLASS_VOL(
+    self, open: np.ndarray, high: np.ndarray, low: np.ndarray, close: np.ndarray, w: int, annual: float = 1.0
+  ) -> np.ndarray:
+    return alpha.GARMAN_KLASS_VOL(open, high, low, close, int(w), float(annual))
+
+  def ROGERS_SATCHELL_VOL(
+    self, open: np.ndarray, high: np.ndarray, low: np.ndarray, close: np.ndarray, w: int, annual: float = 1.0
+  ) -> np.ndarray:
+    return alpha.ROGERS_SATCHELL_VOL(open, high, low, close, int(w), float(annual))
+
+  def YANG_ZHANG_VOL(
+    self, open: np.ndarray, high: np.ndarray, low: np.ndarray, close: np.ndarray, w: int, annual: float = 1.0
+  ) -> np.ndarray:
+    return alpha.YANG_ZHANG_VOL(open, high, low, close, int(w), float(annual))
+
+  # ── TS: GARCH ─────────────────────────────────────────────────────
+  #   one step ahead conditional variance, refit > 0 refits by MLE every
+  #   `refit` bars on the last `w` values (0 = expanding)
+
+  def GARCH(
+    self,
+    a: np.ndarray,
+    omega: float,
+    alpha_: float,
+    beta: float,
+    w: int = 0,
+    refit: int = 0,
+  ) -> np.ndarray:
+    return alpha.GARCH(a, float(omega), float(alpha_), float(beta), int(w), int(refit))
+
   # ── TS: Correlation / Covariance (two-input) ───────────────────────
   #   BRAIN: ts_correlation, ts_covariance
   #   wq101: CORRELATION, COVARIANCE
@@ -313,7 +413,9 @@ class ExecContext:
   # ── TS: Rank ───────────────────────────────────────────────────────
   #   BRAIN: ts_rank       GTJA: TSRANK
 
-  def TS_RANK(self, a: np.ndarray, w: int) -> np.ndarray:
+  def TS_RANK(self, a: np.ndarray, w: int, method: int = None) -> np.ndarray:
+    if method is not None:
+      return alpha.RANK_EX(a, int(w), int(method))
     return alpha.RANK(a, int(w))
 
   TSRANK = TS_RANK  # gtja191
@@ -372,6 +474,27 @@ class ExecContext:
 
   REGRESI = TS_REGRESI  # gtja191
 
+  # ── TS: Kalman Filter ─────────────────────────────────────────────
+  #   filtered state given data up to t, each returns (state..., variance...)
+
+  def KALMAN_BETA(
+    self, a: np.ndarray, b: np.ndarray, delta: float, obs_var: float
+  ) -> tuple[np.ndarray, np.ndarray, np.ndarray, np.ndarray]:
+    """Time-varying regression of a on b -> (beta, intercept, beta_var, intercept_var)."""
+    return alpha.KALMAN_BETA(a, b, float(delta), float(obs_var))
+
+  def KALMAN_LEVEL(
+    self, a: np.ndarray, process_var: float, obs_var: float
+  ) -> tuple[np.ndarray, np.ndarray]:
+    """Local level -> (level, level_var)."""
+    return alpha.KALMAN_LEVEL(a, float(process_var), float(obs_var))
+
+  def KALMAN_TREND(
+    self, a: np.ndarray, level_noise: float, slope_noise: float, obs_var: float
+  ) -> tuple[np.ndarray, np.ndarray, np.ndarray, np.ndarray]:
+    """Local linear trend -> (level, slope, level_var, slope_var)."""
+    return alpha.KALMAN_TREND(a, float(level_noise), float(slope_noise), float(obs_var))
+
   # ── TS: Slope / Intercept ───────────────────────────────────────
 
   def SLOPE(self, a: np.ndarray, w: int) -> np.ndarray:
@@ -406,6 +529,52 @@ class ExecContext:
   def BARSSINCE(self, cond: np.ndarray) -> np.ndarray:
     return alpha.BARSSINCE(np.asarray(cond, dtype=bool))
 
+  def BARSLASTCOUNT(self, cond: np.ndarray) -> np.ndarray:
+    return alpha.BARSLASTCOUNT(np.asarray(cond, dtype=bool))
+
+  # ── TS: State Functions ─────────────────────────────────────────────
+  #   AmiBroker: VALUEWHEN, HOLD, FLIP, SUMSINCE    通达信: FILTER, CONST, BETWEEN
+
+  def VALUEWHEN(self, cond: np.ndarray, a: np.ndarray, n: int = 1) -> np.ndarray:
+    return alpha.VALUEWHEN(
+      np.asarray(a, dtype=np.float64), np.asarray(cond, dtype=bool), int(n)
+    )
+
+  def TS_FILTER(self, cond: np.ndarray, n: int) -> np.ndarray:
+    """通达信 FILTER(X, N), `FILTER` is the element-wise mask."""
+    return alpha.FILTER(np.asarray(cond, dtype=bool), int(n))
+
+  def HOLD(self, cond: np.ndarray, n: int) -> np.ndarray:
+    return alpha.HOLD(np.asarray(cond, dtype=bool), int(n))
+
+  def FLIP(self, on: np.ndarray, off: np.ndarray) -> np.ndarray:
+    return alpha.FLIP(np.asarray(on, dtype=bool), np.asarray(off, dtype=bool))
+
+  def SUMSINCE(self, cond: np.ndarray, a: np.ndarray) -> np.ndarray:
+    return alpha.SUMSINCE(
+      np.asarray(a, dtype=np.float64), np.asarray(cond, dtype=bool)
+    )
+
+  def HHVSINCE(self, cond: np.ndarray, a: np.ndarray) -> np.ndarray:
+    return alpha.HHVSINCE(
+      np.asarray(a, dtype=np.float64), np.asarray(cond, dtype=bool)
+    )
+
+  def LLVSINCE(self, cond: np.ndarray, a: np.ndarray) -> np.ndarray:
+    return alpha.LLVSINCE(
+      np.asarray(a, dtype=np.float64), np.asarray(cond, dtype=bool)
+    )
+
+  def CONST(self, a: np.ndarray) -> np.ndarray:
+    return alpha.CONSTANT(np.asarray(a, dtype=np.float64))
+
+  def BETWEEN(self, a: np.ndarray, b: np.ndarray, c: np.ndarray) -> np.ndarray:
+    return alpha.BETWEEN(
+      np.asarray(a, dtype=np.float64),
+      np.asarray(b, dtype=np.float64),
+      np.asarray(c, dtype=np.float64),
+    )
+
   # ── TS: Conditional Scan (SELF recursion) ───────────────────────────
   #   GTJA: SELF-referencing patterns
 
@@ -472,6 +641,391 @@ class ExecContext:
   def BINS(self, a: np.ndarray, n: int) -> np.ndarray:
     return alpha.BINS(a, int(n))
 
+  # ── TS: Momentum Oscillators ──────────────────────────────────────
+  #   TA-Lib: RSI, STOCH, WILLR, CCI, MFI    通达信: KDJ
+
+  def RSI(self, a: np.ndarray, w: int = 14) -> np.ndarray:
+    return alpha.RSI(a, int(w))
+
+  def STOCH(
+    self,
+    high: np.ndarray,
+    low: np.ndarray,
+    close: np.ndarray,
+    fastk: int = 5,
+    slowk: int = 3,
+    slowd: int = 3,
+  ) -> tuple[np.ndarray, np.ndarray]:
+    """-> (%K, %D)"""
+    return alpha.STOCH(high, low, close, int(fastk), int(slowk), int(slowd))
+
+  def KDJ(
+    self,
+    high: np.ndarray,
+    low: np.ndarray,
+    close: np.ndarray,
+    n: int = 9,
+    m1: int = 3,
+    m2: int = 3,
+  ) -> tuple[np.ndarray, np.ndarray, np.ndarray]:
+    """-> (K, D, J)"""
+    return alpha.KDJ(high, low, close, int(n), int(m1), int(m2))
+
+  def WILLR(
+    self, high: np.ndarray, low: np.ndarray, close: np.ndarray, w: int = 14
+  ) -> np.ndarray:
+    return alpha.WILLR(high, low, close, int(w))
+
+  def CCI(
+    self, high: np.ndarray, low: np.ndarray, close: np.ndarray, w: int = 14
+  ) -> np.ndarray:
+    return alpha.CCI(high, low, close, int(w))
+
+  def MFI(
+    self,
+    high: np.ndarray,
+    low: np.ndarray,
+    close: np.ndarray,
+    volume: np.ndarray,
+    w: int = 14,
+  ) -> np.ndarray:
+    return alpha.MFI(high, low, close, volume, int(w))
+
+  # ── TS: MACD / Price Oscillators ──────────────────────────────────
+  #   TA-Lib: MACD, PPO, APO, TRIX    通达信: MACD (DIF, DEA, MACD)
+
+  def MACD(
+    self,
+    a: np.ndarray,
+    fast: int = 12,
+    slow: int = 26,
+    signal: int = 9,
+    signal_type: int = 0,
+  ) -> tuple[np.ndarray, np.ndarray, np.ndarray]:
+    """-> (macd, signal, hist), signal_type: SIGNAL_EMA / SIGNAL_SMA"""
+    return alpha.MACD(a, int(fast), int(slow), int(signal), int(signal_type))
+
+  def MACD_TDX(
+    self, a: np.ndarray, short: int = 12, long: int = 26, mid: int = 9
+  ) -> tuple[np.ndarray, np.ndarray, np.ndarray]:
+    """-> (DIF, DEA, MACD)"""
+    return alpha.MACD_TDX(a, int(short), int(long), int(mid))
+
+  def PPO(
+    self,
+    a: np.ndarray,
+    fast: int = 12,
+    slow: int = 26,
+    signal: int = 9,
+    signal_type: int = 0,
+  ) -> tuple[np.ndarray, np.ndarray, np.ndarray]:
+    """-> (ppo, signal, hist)"""
+    return alpha.PPO(a, int(fast), int(slow), int(signal), int(signal_type))
+
+  def APO(self, a: np.ndarray, fast: int = 12, slow: int = 26) -> np.ndarray:
+    return alpha.APO(a, int(fast), int(slow))
+
+  def TRIX(
+    self, a: np.ndarray, w: int = 30, signal: int = 9, signal_type: int = 0
+  ) -> tuple[np.ndarray, np.ndarray, np.ndarray]:
+    """-> (trix, signal, hist)"""
+    return alpha.TRIX(a, int(w), int(signal), int(signal_type))
+
+  # ── TS: Trend Indicators ──────────────────────────────────────────
+  #   TA-Lib: PLUS_DI, MINUS_DI, ADX, ADXR, AROON, AROONOSC, SAR
+
+  def DMI(
+    self, high: np.ndarray, low: np.ndarray, close: np.ndarray, w: int = 14
+  ) -> tuple[np.ndarray, np.ndarray, np.ndarray, np.ndarray]:
+    """-> (+DI, -DI, ADX, ADXR)"""
+    return alpha.DMI(high, low, close, int(w))
+
+  def AROON(
+    self, high: np.ndarray, low: np.ndarray, w: int = 14
+  ) -> tuple[np.ndarray, np.ndarray, np.ndarray]:
+    """-> (up, down, oscillator)"""
+    return alpha.AROON(high, low, int(w))
+
+  def SAR(
+    self,
+    high: np.ndarray,
+    low: np.ndarray,
+    acceleration: float = 0.02,
+    maximum: float = 0.2,
+  ) -> tuple[np.ndarray, np.ndarray]:
+    """-> (sar, direction)"""
+    return alpha.SAR(high, low, float(acceleration), float(maximum))
+
+  def SUPERTREND(
+    self,
+    high: np.ndarray,
+    low: np.ndarray,
+    close: np.ndarray,
+    w: int = 10,
+    multiplier: float = 3.0,
+  ) -> tuple[np.ndarray, np.ndarray]:
+    """-> (supertrend, direction)"""
+    return alpha.SUPERTREND(high, low, close, int(w), float(multiplier))
+
+  # ── TS: Hilbert Transform Cycle Indicators ────────────────────────
+  #   TA-Lib: HT_DCPERIOD, HT_DCPHASE, HT_PHASOR, HT_SINE, HT_TRENDLINE,
+  #   HT_TRENDMODE, MAMA
+
+  def HT_DCPERIOD(self, a: np.ndarray) -> np.ndarray:
+    return alpha.HT_DCPERIOD(a)
+
+  def HT_DCPHASE(self, a: np.ndarray) -> np.ndarray:
+    return alpha.HT_DCPHASE(a)
+
+  def HT_PHASOR(self, a: np.ndarray) -> tuple[np.ndarray, np.ndarray]:
+    """-> (in-phase, quadrature)"""
+    return alpha.HT_PHASOR(a)
+
+  def HT_SINE(self, a: np.ndarray) -> tuple[np.ndarray, np.ndarray]:
+    """-> (sine, lead sine)"""
+    return alpha.HT_SINE(a)
+
+  def HT_TRENDLINE(self, a: np.ndarray) -> np.ndarray:
+    return alpha.HT_TRENDLINE(a)
+
+  def HT_TRENDMODE(self, a: np.ndarray) -> np.ndarray:
+    return alpha.HT_TRENDMODE(a)
+
+  def MAMA(
+    self, a: np.ndarray, fast_limit: float = 0.5, slow_limit: float = 0.05
+  ) -> tuple[np.ndarray, np.ndarray]:
+    """-> (mama, fama)"""
+    return alpha.MAMA(a, float(fast_limit), float(slow_limit))
+
+  # ── TS: Volatility Bands ──────────────────────────────────────────
+  #   TA-Lib: TRANGE, ATR, NATR, BBANDS
+
+  def ATR(
+    self, high: np.ndarray, low: np.ndarray, close: np.ndarray, w: int = 14
+  ) -> np.ndarray:
+    return alpha.ATR(high, low, close, int(w))
+
+  def NATR(
+    self, high: np.ndarray, low: np.ndarray, close: np.ndarray, w: int = 14
+  ) -> np.ndarray:
+    return alpha.NATR(high, low, close, int(w))
+
+  def BBANDS(
+    self, a: np.ndarray, w: int = 20, k: float = 2.0
+  ) -> tuple[np.ndarray, np.ndarray, np.ndarray, np.ndarray, np.ndarray]:
+    """-> (upper, middle, lower, %B, bandwidth)"""
+    return alpha.BBANDS(a, int(w), float(k))
+
+  BOLL = BBANDS
+
+  def KELTNER(
+    self,
+    high: np.ndarray,
+    low: np.ndarray,
+    close: np.ndarray,
+    w: int = 20,
+    atr_w: int = 10,
+    multiplier: float = 2.0,
+  ) -> tuple[np.ndarray, np.ndarray, np.ndarray]:
+    """-> (upper, middle, lower)"""
+    return alpha.KELTNER(high, low, close, int(w), int(atr_w), float(multiplier))
+
+  def DONCHIAN(
+    self, high: np.ndarray, low: np.ndarray, w: int = 20
+  ) -> tuple[np.ndarray, np.ndarray, np.ndarray]:
+    """-> (upper, middle, lower)"""
+    return alpha.DONCHIAN(high, low, int(w))
+
+  # ── TS: Volume Indicators ─────────────────────────────────────────
+  #   TA-Lib: OBV, AD, ADOSC
+
+  def OBV(self, close: np.ndarray, volume: np.ndarray) -> np.ndarray:
+    return alpha.OBV(close, volume)
+
+  def AD(
+    self, high: np.ndarray, low: np.ndarray, close: np.ndarray, volume: np.ndarray
+  ) -> np.ndarray:
+    return alpha.AD(high, low, close, volume)
+
+  def ADOSC(
+    self,
+    high: np.ndarray,
+    low: np.ndarray,
+    close: np.ndarray,
+    volume: np.ndarray,
+    fast: int = 3,
+    slow: int = 10,
+  ) -> np.ndarray:
+    return alpha.ADOSC(high, low, close, volume, int(fast), int(slow))
+
+  def CMF(
+    self,
+    high: np.ndarray,
+    low: np.ndarray,
+    close: np.ndarray,
+    volume: np.ndarray,
+    w: int = 20,
+  ) -> np.ndarray:
+    return alpha.CMF(high, low, close, volume, int(w))
+
+  def FORCE(self, close: np.ndarray, volume: np.ndarray, w: int = 13) -> np.ndarray:
+    return alpha.FORCE(close, volume, int(w))
+
+  def EMV(
+    self,
+    high: np.ndarray,
+    low: np.ndarray,
+    volume: np.ndarray,
+    w: int = 14,
+    scale: float = 1e8,
+  ) -> np.ndarray:
+    return alpha.EMV(high, low, volume, int(w), float(scale))
+
+  def TS_VWAP(self, price: np.ndarray, volume: np.ndarray, w: int) -> np.ndarray:
+    """rolling sum(price * volume) / sum(volume), NaN without volume."""
+    return alpha.TS_VWAP(price, volume, int(w))
+
+  # ── TS: Candlestick Patterns ──────────────────────────────────────
+  #   TA-Lib: CDL*, +100 bullish / -100 bearish / 0, NaN during warm-up
+
+  def CDLDOJI(
+    self, open: np.ndarray, high: np.ndarray, low: np.ndarray, close: np.ndarray
+  ) -> np.ndarray:
+    return alpha.CDLDOJI(open, high, low, close)
+
+  def CDLDRAGONFLYDOJI(
+    self, open: np.ndarray, high: np.ndarray, low: np.ndarray, close: np.ndarray
+  ) -> np.ndarray:
+    return alpha.CDLDRAGONFLYDOJI(open, high, low, close)
+
+  def CDLGRAVESTONEDOJI(
+    self, open: np.ndarray, high: np.ndarray, low: np.ndarray, close: np.ndarray
+  ) -> np.ndarray:
+    return alpha.CDLGRAVESTONEDOJI(open, high, low, close)
+
+  def CDLMARUBOZU(
+    self, open: np.ndarray, high: np.ndarray, low: np.ndarray, close: np.ndarray
+  ) -> np.ndarray:
+    return alpha.CDLMARUBOZU(open, high, low, close)
+
+  def CDLHAMMER(
+    self, open: np.ndarray, high: np.ndarray, low: np.ndarray, close: np.ndarray
+  ) -> np.ndarray:
+    return alpha.CDLHAMMER(open, high, low, close)
+
+  def CDLHANGINGMAN(
+    self, open: np.ndarray, high: np.ndarray, low: np.ndarray, close: np.ndarray
+  ) -> np.ndarray:
+    return alpha.CDLHANGINGMAN(open, high, low, close)
+
+  def CDLINVERTEDHAMMER(
+    self, open: np.ndarray, high: np.ndarray, low: np.ndarray, close: np.ndarray
+  ) -> np.ndarray:
+    return alpha.CDLINVERTEDHAMMER(open, high, low, close)
+
+  def CDLSHOOTINGSTAR(
+    self, open: np.ndarray, high: np.ndarray, low: np.ndarray, close: np.ndarray
+  ) -> np.ndarray:
+    return alpha.CDLSHOOTINGSTAR(open, high, low, close)
+
+  def CDLENGULFING(
+    self, open: np.ndarray, high: np.ndarray, low: np.ndarray, close: np.ndarray
+  ) -> np.ndarray:
+    return alpha.CDLENGULFING(open, high, low, close)
+
+  def CDLHARAMI(
+    self, open: np.ndarray, high: np.ndarray, low: np.ndarray, close: np.ndarray
+  ) -> np.ndarray:
+    return alpha.CDLHARAMI(open, high, low, close)
+
+  def CDL3WHITESOLDIERS(
+    self, open: np.ndarray, high: np.ndarray, low: np.ndarray, close: np.ndarray
+  ) -> np.ndarray:
+    return alpha.CDL3WHITESOLDIERS(open, high, low, close)
+
+  def CDL3BLACKCROWS(
+    self, open: np.ndarray, high: np.ndarray, low: np.ndarray, close: np.ndarray
+  ) -> np.ndarray:
+    return alpha.CDL3BLACKCROWS(open, high, low, close)
+
+  def CDLMORNINGSTAR(
+    self,
+    open: np.ndarray,
+    high: np.ndarray,
+    low: np.ndarray,
+    close: np.ndarray,
+    penetration: float = 0.3,
+  ) -> np.ndarray:
+    return alpha.CDLMORNINGSTAR(open, high, low, close, float(penetration))
+
+  def CDLEVENINGSTAR(
+    self,
+    open: np.ndarray,
+    high: np.ndarray,
+    low: np.ndarray,
+    close: np.ndarray,
+    penetration: float = 0.3,
+  ) -> np.ndarray:
+    return alpha.CDLEVENINGSTAR(open, high, low, close, float(penetration))
+
+  # ── TS: ZigZag ────────────────────────────────────────────────────
+  #   通达信: ZIG, PEAK, TROUGH, PEAKBARS, TROUGHBARS, all of them look ahead,
+  #   ZIG_CONFIRMED reports pivots when confirmed and is safe for backtests
+
+  def ZIG(self, a: np.ndarray, n: float) -> np.ndarray:
+    return alpha.ZIG(a, float(n))[0]
+
+  def _zig_pivot(self, a: np.ndarray, n: float, m: int, side: int) -> np.ndarray:
+    zig, marker = alpha.ZIG(a, float(n))
+    return alpha.VALUEWHEN(zig, marker == side, int(m))
+
+  def _zig_bars(self, a: np.ndarray, n: float, m: int, side: int) -> np.ndarray:
+    _, marker = alpha.ZIG(a, float(n))
+    bar = alpha.BARSSINCE(np.ones_like(marker, dtype=bool))
+    return bar - alpha.VALUEWHEN(bar, marker == side, int(m))
+
+  def PEAK(self, a: np.ndarray, n: float, m: int = 1) -> np.ndarray:
+    return self._zig_pivot(a, n, m, 1)
+
+  def TROUGH(self, a: np.ndarray, n: float, m: int = 1) -> np.ndarray:
+    return self._zig_pivot(a, n, m, -1)
+
+  def PEAKBARS(self, a: np.ndarray, n: float, m: int = 1) -> np.ndarray:
+    return self._zig_bars(a, n, m, 1)
+
+  def TROUGHBARS(self, a: np.ndarray, n: float, m: int = 1) -> np.ndarray:
+    return self._zig_bars(a, n, m, -1)
+
+  def ZIG_CONFIRMED(
+    self, a: np.ndarray, n: float
+  ) -> tuple[np.ndarray, np.ndarray, np.ndarray]:
+    """-> (pivot, marker, bars)"""
+    return alpha.ZIG_CONFIRMED(a, float(n))
+
+  # ── TS: Performance Ratios ────────────────────────────────────────
+  #   `annual` is the number of bars per year, 1 for per-bar ratios
+
+  def SORTINO(
+    self, a: np.ndarray, w: int, target: float = 0.0, annual: float = 1.0
+  ) -> np.ndarray:
+    return alpha.SORTINO(a, float(target), int(w), float(annual))
+
+  def CALMAR(self, a: np.ndarray, w: int, annual: float = 1.0) -> np.ndarray:
+    return alpha.CALMAR(a, int(w), float(annual))
+
+  def INFORMATION_RATIO(
+    self, a: np.ndarray, b: np.ndarray, w: int, annual: float = 1.0
+  ) -> np.ndarray:
+    return alpha.INFORMATION_RATIO(a, b, int(w), float(annual))
+
+  def OMEGA(self, a: np.ndarray, w: int, threshold: float = 0.0) -> np.ndarray:
+    return alpha.OMEGA(a, float(threshold), int(w))
+
+  def TREYNOR(
+    self, a: np.ndarray, b: np.ndarray, w: int, annual: float = 1.0
+  ) -> np.ndarray:
+    return alpha.TREYNOR(a, b, int(w), float(annual))
+
   # ── TS: Future Return ──────────────────────────────────────────
 
   def FRET(self, open: np.ndarray, close: np.ndarray, is_calc: np.ndarray, delay: int, periods: int) -> np.ndarray:
@@ -501,7 +1055,9 @@ class ExecContext:
   # ── Rank ────────────────────────────────────────────────────────────
   #   BRAIN: rank          wq101/GTJA: RANK
 
-  def RANK(self, a: np.ndarray) -> np.ndarray:
+  def RANK(self, a: np.ndarray, method: int = None) -> np.ndarray:
+    if method is not None:
+      return alpha.CC_RANK_EX(a, int(method))
     return alpha.CC_RANK(a)
 
   # ── Z-Score ─────────────────────────────────────────────────────────
@@ -531,7 +1087,11 @@ class ExecContext:
   # ── Group Rank ────────────────────────────────────────────────────
   #   BRAIN: group_rank
 
-  def GROUP_RANK(self, a: np.ndarray, group: np.ndarray) -> np.ndarray:
+  def GROUP_RANK(
+    self, a: np.ndarray, group: np.ndarray, method: int = None
+  ) -> np.ndarray:
+    if method is not None:
+      return alpha.GROUP_RANK_EX(group, a, int(method))
     return alpha.GROUP_RANK(group, a)
 
   # ── Group Z-Score ─────────────────────────────────────────────────
@@ -540,6 +1100,115 @@ class ExecContext:
   def GROUP_ZSCORE(self, a: np.ndarray, group: np.ndarray) -> np.ndarray:
     return alpha.GROUP_ZSCORE(group, a)
 
+  # ── Group Aggregates ──────────────────────────────────────────────
+  #   BRAIN: group_mean, group_sum, group_max, group_min, group_median, group_count
+
+  def GROUP_MEAN(self, a: np.ndarray, weights, group: np.ndarray = None) -> np.ndarray:
+    """BRAIN group_mean(x, weight, group), or GROUP_MEAN(x, group) for the unweighted mean."""
+    if group is None:
+      group, weights = weights, np.empty(0)
+    return alpha.GROUP_MEAN(group, a, weights)
+
+  def GROUP_SUM(self, a: np.ndarray, group: np.ndarray) -> np.ndarray:
+    return alpha.GROUP_SUM(group, a)
+
+  def GROUP_MAX(self, a: np.ndarray, group: np.ndarray) -> np.ndarray:
+    return alpha.GROUP_MAX(group, a)
+
+  def GROUP_MIN(self, a: np.ndarray, group: np.ndarray) -> np.ndarray:
+    return alpha.GROUP_MIN(group, a)
+
+  def GROUP_MEDIAN(self, a: np.ndarray, group: np.ndarray) -> np.ndarray:
+    return alpha.GROUP_MEDIAN(group, a)
+
+  def GROUP_COUNT(self, a: np.ndarray, group: np.ndarray) -> np.ndarray:
+    return alpha.GROUP_COUNT(group, a)
+
+  # ── Group Normalization ───────────────────────────────────────────
+  #   BRAIN: group_neutralize, group_scale, group_backfill
+
+  def GROUP_NEUTRALIZE(self, a: np.ndarray, group: np.ndarray) -> np.ndarray:
+    return alpha.GROUP_NEUTRALIZE(group, a)
+
+  def GROUP_SCALE(self, a: np.ndarray, group: np.ndarray) -> np.ndarray:
+    return alpha.GROUP_SCALE(group, a)
+
+  def GROUP_BACKFILL(
+    self, a: np.ndarray, group: np.ndarray, d: int, std: float = 4.0
+  ) -> np.ndarray:
+    return alpha.GROUP_BACKFILL(group, a, int(d), float(std))
+
+  # ── Group Binning ─────────────────────────────────────────────────
+
+  def GROUP_BINS(
+    self, a: np.ndarray, group: np.ndarray, n: int, flags: int = 0
+  ) -> np.ndarray:
+    return alpha.GROUP_BINS(group, a, int(n), int(flags))
+
+  # ── Factor Evaluation ─────────────────────────────────────────────
+
+  def IC(self, factor: np.ndarray, returns: np.ndarray) -> np.ndarray:
+    return alpha.IC(factor, returns)
+
+  def RANK_IC(self, factor: np.ndarray, returns: np.ndarray) -> np.ndarray:
+    return alpha.RANK_IC(factor, returns)
+
+  def IC_SUMMARY(
+    self, ic: np.ndarray, d: int = 0
+  ) -> tuple[np.ndarray, np.ndarray, np.ndarray, np.ndarray]:
+    return alpha.IC_SUMMARY(ic, int(d))
+
+  def IC_DECAY(
+    self, factor: np.ndarray, price: np.ndarray, n: int, rank: bool = False
+  ) -> np.ndarray:
+    """-> (n, T) IC matrix, row h-1 is horizon h"""
+    if rank:
+      return alpha.RANK_IC_DECAY(factor, price, int(n))
+    return alpha.IC_DECAY(factor, price, int(n))
+
+  def RANK_AUTOCORR(self, a: np.ndarray, k: int = 1) -> np.ndarray:
+    return alpha.RANK_AUTOCORR(a, int(k))
+
+  def BINS_TURNOVER(
+    self, a: np.ndarray, n: int, bin: int
+  ) -> tuple[np.ndarray, np.ndarray]:
+    return alpha.BINS_TURNOVER(a, int(n), int(bin))
+
+  def LAYER_BACKTEST(
+    self, factor: np.ndarray, returns: np.ndarray, n: int, weights: np.ndarray | None = None
+  ) -> tuple[np.ndarray, np.ndarray, np.ndarray, np.ndarray]:
+    """-> (n, T) layer returns, NAV and turnover, T spread"""
+    if weights is None:
+      weights = np.empty(0)
+    return alpha.LAYER_BACKTEST(factor, returns, weights, int(n))
+
+  def PORTFOLIO_SIM(
+    self,
+    target: np.ndarray,
+    returns: np.ndarray,
+    is_calc: np.ndarray,
+    cost: float = 0.0,
+    delay: int = 1,
+  ) -> tuple[np.ndarray, np.ndarray, np.ndarray, np.ndarray, np.ndarray, np.ndarray]:
+    return alpha.PORTFOLIO_SIM(target, returns, is_calc, float(cost), int(delay))
+
+  # ── Risk Model ────────────────────────────────────────────────────
+
+  def COV_MATRIX(
+    self,
+    a: np.ndarray,
+    dates: list[int] | np.ndarray,
+    d: int,
+    halflife: float = 0.0,
+    target: int = 0,
+  ) -> tuple[np.ndarray, np.ndarray]:
+    return alpha.COV_MATRIX(a, dates, int(d), float(halflife), int(target))
+
+  def PCA(
+    self, a: np.ndarray, d: int, k: int
+  ) -> tuple[np.ndarray, np.ndarray, np.ndarray, np.ndarray]:
+    return alpha.PCA(a, int(d), int(k))
+
   # ====================================================================
   #  Element-wise Operators
   # ====================================================================
@@ -47,7 +47,163 @@ def test_sma_dma_smoke():
   assert sma_list[0].shape == close.shape
 
 
+def _ohlcv(n=200, seed=7):
+  rng = np.random.default_rng(seed)
+  close = 10 + np.cumsum(rng.normal(0, 0.2, n))
+  high = close + rng.uniform(0, 0.3, n)
+  low = close - rng.uniform(0, 0.3, n)
+  volume = rng.uniform(100, 1000, n)
+  return high, low, close, volume
+
+
+def test_oscillators():
+  high, low, close, volume = _ohlcv()
+
+  x1 = talib.RSI(close, 14)
+  x2 = algo.RSI(close, 14)
+  assert np.allclose(x1[14:], x2[14:])
+
+  k1, d1 = talib.STOCH(high, low, close, 5, 3, 0, 3, 0)
+  k2, d2 = algo.STOCH(high, low, close, 5, 3, 3)
+  assert np.allclose(k1[8:], k2[8:])
+  assert np.allclose(d1[8:], d2[8:])
+
+  x1 = talib.WILLR(high, low, close, 14)
+  x2 = algo.WILLR(high, low, close, 14)
+  assert np.allclose(x1[13:], x2[13:])
+
+  x1 = talib.CCI(high, low, close, 14)
+  x2 = algo.CCI(high, low, close, 14)
+  assert np.allclose(x1[13:], x2[13:])
+
+  x1 = talib.MFI(high, low, close, volume, 14)
+  x2 = algo.MFI(high, low, close, volume, 14)
+  assert np.allclose(x1[14:], x2[14:])
+
+
+def test_trend():
+  high, low, close, _ = _ohlcv()
+
+  p, m, adx, adxr = algo.DMI(high, low, close, 14)
+  assert np.allclose(talib.PLUS_DI(high, low, close, 14)[14:], p[14:])
+  assert np.allclose(talib.MINUS_DI(high, low, close, 14)[14:], m[14:])
+  assert np.allclose(talib.ADX(high, low, close, 14)[27:], adx[27:])
+  assert np.allclose(talib.ADXR(high, low, close, 14)[40:], adxr[40:])
+
+  down1, up1 = talib.AROON(high, low, 14)
+  up2, down2, osc2 = algo.AROON(high, low, 14)
+  assert np.allclose(up1[14:], up2[14:])
+  assert np.allclose(down1[14:], down2[14:])
+  assert np.allclose(talib.AROONOSC(high, low, 14)[14:], osc2[14:])
+
+  sar, _ = algo.SAR(high, low, 0.02, 0.2)
+  assert np.allclose(talib.SAR(high, low, 0.02, 0.2)[1:], sar[1:])
+
+
+def test_bands():
+  high, low, close, _ = _ohlcv()
+
+  assert np.allclose(talib.TRANGE(high, low, close)[1:], algo.TR(high, low, close)[1:])
+  assert np.allclose(talib.ATR(high, low, close, 14)[14:], algo.ATR(high, low, close, 14)[14:])
+  assert np.allclose(talib.NATR(high, low, close, 14)[14:], algo.NATR(high, low, close, 14)[14:])
+
+  up1, mid1, lo1 = talib.BBANDS(close, 20, 2.0, 2.0)
+  up2, mid2, lo2, _, _ = algo.BBANDS(close, 20, 2.0)
+  assert np.allclose(up1[19:], up2[19:])
+  assert np.allclose(mid1[19:], mid2[19:])
+  assert np.allclose(lo1[19:], lo2[19:])
+
+
+def test_adaptive_ma():
+  _, _, close, _ = _ohlcv()
+
+  assert np.allclose(talib.KAMA(close, 10)[10:], algo.KAMA(close, 10, 2, 30)[10:])
+  assert np.allclose(talib.DEMA(close, 10)[18:], algo.DEMA(close, 10)[18:])
+  assert np.allclose(talib.TEMA(close, 10)[27:], algo.TEMA(close, 10)[27:])
+  assert np.allclose(talib.T3(close, 5, 0.7)[24:], algo.T3(close, 5, 0.7)[24:])
+
+
+def test_volume():
+  high, low, close, volume = _ohlcv()
+
+  assert np.allclose(talib.OBV(close, volume), algo.OBV(close, volume))
+  assert np.allclose(talib.AD(high, low, close, volume), algo.AD(high, low, close, volume))
+  assert np.allclose(
+    talib.ADOSC(high, low, close, volume, 3, 10)[9:],
+    algo.ADOSC(high, low, close, volume, 3, 10)[9:],
+  )
+
+
+def test_candlestick():
+  high, low, close, _ = _ohlcv(1000)
+  open_ = np.r_[close[0], close[:-1]] + np.random.default_rng(11).normal(0, 0.05, len(close))
+  high = np.maximum(high, open_)
+  low = np.minimum(low, open_)
+
+  for name, lookback in [
+    ("CDLDOJI", 10),
+    ("CDLDRAGONFLYDOJI", 10),
+    ("CDLGRAVESTONEDOJI", 10),
+    ("CDLMARUBOZU", 10),
+    ("CDLENGULFING", 2),
+    ("CDLHARAMI", 11),
+    ("CDLHAMMER", 11),
+    ("CDLHANGINGMAN", 11),
+    ("CDLINVERTEDHAMMER", 11),
+    ("CDLSHOOTINGSTAR", 11),
+    ("CDL3WHITESOLDIERS", 12),
+    ("CDL3BLACKCROWS", 13),
+  ]:
+    x1 = getattr(talib, name)(open_, high, low, close)
+    x2 = getattr(algo, name)(open_, high, low, close)
+    assert np.array_equal(x1[lookback:], x2[lookback:]), name
+
+  for name in ["CDLMORNINGSTAR", "CDLEVENINGSTAR"]:
+    x1 = getattr(talib, name)(open_, high, low, close, 0.3)
+    x2 = getattr(algo, name)(open_, high, low, close, 0.3)
+    assert np.array_equal(x1[12:], x2[12:]), name
+
+
+def test_hilbert():
+  _, _, close, _ = _ohlcv(500)
+
+  assert np.allclose(talib.HT_DCPERIOD(close)[32:], algo.HT_DCPERIOD(close)[32:])
+  assert np.allclose(talib.HT_DCPHASE(close)[63:], algo.HT_DCPHASE(close)[63:])
+  assert np.allclose(talib.HT_TRENDLINE(close)[63:], algo.HT_TRENDLINE(close)[63:])
+  assert np.array_equal(talib.HT_TRENDMODE(close)[63:], algo.HT_TRENDMODE(close)[63:])
+  for x1, x2 in zip(talib.HT_PHASOR(close), algo.HT_PHASOR(close)):
+    assert np.allclose(x1[32:], x2[32:])
+  for x1, x2 in zip(talib.HT_SINE(close), algo.HT_SINE(close)):
+    assert np.allclose(x1[63:], x2[63:])
+  for x1, x2 in zip(talib.MAMA(close, 0.5, 0.05), algo.MAMA(close, 0.5, 0.05)):
+    assert np.allclose(x1[32:], x2[32:])
+
+
+def test_macd():
+  _, _, close, _ = _ohlcv(1000)
+
+  # EMAs seeded with the mean of their first periods, lookback 25 + 8
+  m1, s1, h1 = talib.MACD(close, 12, 26, 9)
+  m2, s2, h2 = algo.MACD(close, 12, 26, 9, 0)
+  assert np.allclose(m1[33:], m2[33:])
+  assert np.allclose(s1[33:], s2[33:])
+  assert np.allclose(h1[33:], h2[33:])
+  assert np.isnan(m2[:25]).all() and np.isnan(s2[:33]).all()
+
+  assert np.allclose(talib.APO(close, 12, 26, 1)[25:], algo.APO(close, 12, 26)[25:])
+  assert np.allclose(talib.PPO(close, 12, 26, 1)[25:], algo.PPO(close, 12, 26, 9, 0)[0][25:])
+  assert np.allclose(talib.TRIX(close, 15)[43:], algo.TRIX(close, 15, 9, 0)[0][43:])
+
+
 if __name__ == "__main__":
   test_correct()
   test_sma_dma_smoke()
+  test_oscillators()
+  test_trend()
+  test_bands()
+  test_adaptive_ma()
+  test_volume()
+  test_candlestick()
+  test_hilbert()
+  test_macd()
   test_performance()
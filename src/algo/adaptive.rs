@@ -0,0 +1,479 @@
+// Copyright 2026 MSD-RS Project LiJia
+// SPDX-License-Identifier: BSD-2-Clause
+
+use std::collections::VecDeque;
+
+use num_traits::Float;
+
+use super::{bars::run_bars, ema::Ema};
+use crate::algo::{Context, Error};
+
+/// Fixed weight moving window, weights[0] is the oldest
+struct Weighted<NumT> {
+  weights: Vec<NumT>,
+  window: VecDeque<NumT>,
+}
+
+impl<NumT: Float> Weighted<NumT> {
+  fn new(weights: Vec<NumT>) -> Self {
+    let sum = weights.iter().fold(NumT::zero(), |s, w| s + *w);
+    Self {
+      window: VecDeque::with_capacity(weights.len()),
+      weights: weights.into_iter().map(|w| w / sum).collect(),
+    }
+  }
+
+  /// Linear weights 1..n as `LWMA`
+  fn linear(n: usize) -> Self {
+    Self::new((1..=n).map(|w| NumT::from(w).unwrap()).collect())
+  }
+
+  fn update(&mut self, x: NumT) -> Option<NumT> {
+    if self.window.len() == self.weights.len() {
+      self.window.pop_front();
+    }
+    self.window.push_back(x);
+    if self.window.len() < self.weights.len() {
+      return None;
+    }
+    Some(
+      self
+        .window
+        .iter()
+        .zip(&self.weights)
+        .fold(NumT::zero(), |s, (x, w)| s + *x * *w),
+    )
+  }
+}
+
+fn check_periods(name: &str, periods: usize, min: usize) -> Result<(), Error> {
+  if periods < min {
+    return Err(Error::InvalidPeriod(format!(
+      "{} requires periods >= {}, got {}",
+      name, min, periods
+    )));
+  }
+  Ok(())
+}
+
+/// Kaufman Adaptive Moving Average
+///
+/// ER = |input - ref(input, periods)| / sum(|input - ref(input, 1)|, periods),
+/// SC = (ER * (2 / (fast + 1) - 2 / (slow + 1)) + 2 / (slow + 1))^2,
+/// KAMA = KAMA[-1] + SC * (input - KAMA[-1]), seeded with the previous input
+/// and starting at bar `periods` as TA-Lib (which fixes fast = 2, slow = 30).
+///
+/// Ref: https://school.stockcharts.com/doku.php?id=technical_indicators:kaufman_s_adaptive_moving_average
+pub fn ta_kama<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+  periods: usize,
+  fast: usize,
+  slow: usize,
+) -> Result<(), Error> {
+  check_periods("kama", periods, 1)?;
+  if fast == 0 || slow < fast {
+    return Err(Error::InvalidParameter(
+      "kama requires 0 < fast <= slow".to_string(),
+    ));
+  }
+
+  let two = NumT::from(2.0).unwrap();
+  let fast = two / NumT::from(fast + 1).unwrap();
+  let slow = two / NumT::from(slow + 1).unwrap();
+  run_bars(
+    ctx,
+    [r],
+    [input],
+    || (VecDeque::with_capacity(periods + 1), NumT::nan()),
+    |(window, kama): &mut (VecDeque<NumT>, NumT), [x]| {
+      if window.len() > periods {
+        window.pop_front();
+      }
+      window.push_back(x);
+      if window.len() <= periods {
+        *kama = x;
+        return None;
+      }
+
+      let roc = (x - window[0]).abs();
+      let noise = window
+        .iter()
+        .zip(window.iter().skip(1))
+        .fold(NumT::zero(), |s, (a, b)| s + (*b - *a).abs());
+      let er = if noise <= roc || noise == NumT::zero() {
+        NumT::one()
+      } else {
+        roc / noise
+      };
+      let sc = (er * (fast - slow) + slow).powi(2);
+      *kama = *kama + sc * (x - *kama);
+      Some([*kama])
+    },
+  )
+}
+
+/// Double Exponential Moving Average
+///
+/// DEMA = 2 * EMA(input, periods) - EMA(EMA(input, periods), periods), each
+/// EMA seeded with the mean of its first `periods` values, so the first
+/// 2 * (periods - 1) bars are NaN as TA-Lib.
+///
+/// Ref: https://www.investopedia.com/terms/d/double-exponential-moving-average.asp
+pub fn ta_dema<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+  periods: usize,
+) -> Result<(), Error> {
+  check_periods("dema", periods, 1)?;
+
+  let two = NumT::from(2.0).unwrap();
+  run_bars(
+    ctx,
+    [r],
+    [input],
+    || [Ema::sma_seeded(periods), Ema::sma_seeded(periods)],
+    |[e1, e2], [x]| {
+      let v1 = e1.update(x)?;
+      let v2 = e2.update(v1)?;
+      Some([two * v1 - v2])
+    },
+  )
+}
+
+/// Triple Exponential Moving Average
+///
+/// TEMA = 3 * EMA1 - 3 * EMA2 + EMA3 with EMA2 = EMA(EMA1), EMA3 = EMA(EMA2),
+/// the first 3 * (periods - 1) bars are NaN as TA-Lib.
+///
+/// Ref: https://www.investopedia.com/terms/t/triple-exponential-moving-average.asp
+pub fn ta_tema<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+  periods: usize,
+) -> Result<(), Error> {
+  check_periods("tema", periods, 1)?;
+
+  let three = NumT::from(3.0).unwrap();
+  run_bars(
+    ctx,
+    [r],
+    [input],
+    || {
+      [
+        Ema::sma_seeded(periods),
+        Ema::sma_seeded(periods),
+        Ema::sma_seeded(periods),
+      ]
+    },
+    |[e1, e2, e3], [x]| {
+      let v1 = e1.update(x)?;
+      let v2 = e2.update(v1)?;
+      let v3 = e3.update(v2)?;
+      Some([three * (v1 - v2) + v3])
+    },
+  )
+}
+
+/// Tillson T3 Moving Average
+///
+/// GD(x) = EMA(x) * (1 + vfactor) - EMA(EMA(x)) * vfactor, T3 = GD(GD(GD(input)))
+/// computed from a chain of six EMAs, the first 6 * (periods - 1) bars are NaN
+/// as TA-Lib. `vfactor` is usually 0.7.
+///
+/// Ref: https://www.tradingpedia.com/forex-trading-indicators/t3-moving-average-indicator/
+pub fn ta_t3<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+  periods: usize,
+  vfactor: NumT,
+) -> Result<(), Error> {
+  check_periods("t3", periods, 1)?;
+
+  let v = vfactor;
+  let three = NumT::from(3.0).unwrap();
+  let c1 = -v * v * v;
+  let c2 = three * v * v + three * v * v * v;
+  let c3 = -NumT::from(6.0).unwrap() * v * v - three * v - three * v * v * v;
+  let c4 = NumT::one() + three * v + v * v * v + three * v * v;
+  run_bars(
+    ctx,
+    [r],
+    [input],
+    || std::array::from_fn::<_, 6, _>(|_| Ema::sma_seeded(periods)),
+    |emas, [x]| {
+      let mut e = [NumT::zero(); 6];
+      let mut v = x;
+      for (e, ema) in e.iter_mut().zip(emas.iter_mut()) {
+        v = ema.update(v)?;
+        *e = v;
+      }
+      Some([c1 * e[5] + c2 * e[4] + c3 * e[3] + c4 * e[2]])
+    },
+  )
+}
+
+/// Hull Moving Average
+///
+/// HMA = LWMA(2 * LWMA(input, periods / 2) - LWMA(input, periods),
+/// floor(sqrt(periods))), NaN until the last LWMA has a full window.
+///
+/// Ref: https://alanhull.com/hull-moving-average
+pub fn ta_hma<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+  periods: usize,
+) -> Result<(), Error> {
+  check_periods("hma", periods, 2)?;
+
+  let two = NumT::from(2.0).unwrap();
+  let sqrt_n = (periods as f64).sqrt().floor() as usize;
+  run_bars(
+    ctx,
+    [r],
+    [input],
+    || {
+      (
+        Weighted::linear(periods / 2),
+        Weighted::linear(periods),
+        Weighted::linear(sqrt_n),
+      )
+    },
+    |(half, full, hull), [x]| {
+      let h = half.update(x);
+      let f = full.update(x)?;
+      let v = hull.update(two * h? - f)?;
+      Some([v])
+    },
+  )
+}
+
+/// Zero Lag Exponential Moving Average
+///
+/// ZLEMA = EMA(input + (input - ref(input, (periods - 1) / 2)), periods),
+/// the EMA is seeded with the mean of its first `periods` values.
+///
+/// Ref: https://en.wikipedia.org/wiki/Zero_lag_exponential_moving_average
+pub fn ta_zlema<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+  periods: usize,
+) -> Result<(), Error> {
+  check_periods("zlema", periods, 1)?;
+
+  let lag = (periods - 1) / 2;
+  run_bars(
+    ctx,
+    [r],
+    [input],
+    || (VecDeque::with_capacity(lag + 1), Ema::sma_seeded(periods)),
+    |(window, ema): &mut (VecDeque<NumT>, Ema<NumT>), [x]| {
+      if window.len() > lag {
+        window.pop_front();
+      }
+      window.push_back(x);
+      if window.len() <= lag {
+        return None;
+      }
+      ema.update(x + x - window[0]).map(|v| [v])
+    },
+  )
+}
+
+/// Arnaud Legoux Moving Average
+///
+/// ALMA = sum(w_i * input_i) / sum(w_i) over the last `periods` bars,
+/// w_i = exp(-(i - offset * (periods - 1))^2 / (2 * (periods / sigma)^2)) with
+/// i = 0 for the oldest bar. Usually offset = 0.85 and sigma = 6.
+///
+/// Ref: https://www.prorealcode.com/prorealtime-indicators/alma-arnaud-legoux-moving-average/
+pub fn ta_alma<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+  periods: usize,
+  offset: NumT,
+  sigma: NumT,
+) -> Result<(), Error> {
+  check_periods("alma", periods, 1)?;
+  if sigma.is_nan() || sigma <= NumT::zero() {
+    return Err(Error::InvalidParameter(
+      "alma requires sigma > 0".to_string(),
+    ));
+  }
+
+  let n = NumT::from(periods).unwrap();
+  let m = offset * (n - NumT::one());
+  let s = n / sigma;
+  let two = NumT::from(2.0).unwrap();
+  let weights: Vec<NumT> = (0..periods)
+    .map(|i| (-(NumT::from(i).unwrap() - m).powi(2) / (two * s * s)).exp())
+    .collect();
+  run_bars(
+    ctx,
+    [r],
+    [input],
+    || Weighted::new(weights.clone()),
+    |alma, [x]| alma.update(x).map(|v| [v]),
+  )
+}
+
+/// Fractal Adaptive Moving Average
+///
+/// Over the last `periods` (even) bars, N1 / N2 = range of each half / (periods / 2),
+/// N3 = range of the window / periods, D = (ln(N1 + N2) - ln(N3)) / ln(2),
+/// alpha = clamp(exp(-4.6 * (D - 1)), 0.01, 1) and
+/// FRAMA = alpha * input + (1 - alpha) * FRAMA[-1]. Flat windows keep the
+/// previous alpha, the recursion starts from the input and outputs begin once
+/// the window is full.
+///
+/// Ref: https://www.mesasoftware.com/papers/FRAMA.pdf
+pub fn ta_frama<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+  periods: usize,
+) -> Result<(), Error> {
+  check_periods("frama", periods, 2)?;
+  if !periods.is_multiple_of(2) {
+    return Err(Error::InvalidPeriod(format!(
+      "frama requires an even periods, got {}",
+      periods
+    )));
+  }
+
+  let half = periods / 2;
+  let range = |w: &mut dyn Iterator<Item = &NumT>| {
+    let (hi, lo) = w.fold((NumT::neg_infinity(), NumT::infinity()), |(h, l), x| {
+      (h.max(*x), l.min(*x))
+    });
+    hi - lo
+  };
+  let min_alpha = NumT::from(0.01).unwrap();
+  run_bars(
+    ctx,
+    [r],
+    [input],
+    || (VecDeque::with_capacity(periods), NumT::nan(), NumT::one()),
+    |(window, frama, alpha): &mut (VecDeque<NumT>, NumT, NumT), [x]| {
+      if window.len() == periods {
+        window.pop_front();
+      }
+      window.push_back(x);
+      if window.len() < periods {
+        *frama = x;
+        return None;
+      }
+
+      let h = NumT::from(half).unwrap();
+      let n1 = range(&mut window.iter().take(half)) / h;
+      let n2 = range(&mut window.iter().skip(half)) / h;
+      let n3 = range(&mut window.iter()) / (h + h);
+      if n1 > NumT::zero() && n2 > NumT::zero() && n3 > NumT::zero() {
+        let d = ((n1 + n2).ln() - n3.ln()) / NumT::from(2.0).unwrap().ln();
+        *alpha = (NumT::from(-4.6).unwrap() * (d - NumT::one()))
+          .exp()
+          .max(min_alpha)
+          .min(NumT::one());
+      }
+      *frama = *alpha * x + (NumT::one() - *alpha) * *frama;
+      Some([*frama])
+    },
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::algo::{assert_vec_eq_nan, context::FLAG_SKIP_NAN};
+
+  #[test]
+  fn test_dema_tema_t3() {
+    // every chain is lag free on a straight line
+    let input: Vec<f64> = (1..=8).map(|i| i as f64).collect();
+    let mut r = vec![0.0; 8];
+    let ctx = Context::new(0, 1, 0);
+
+    ta_dema(&ctx, &mut r, &input, 2).unwrap();
+    assert_vec_eq_nan(&r, &[f64::NAN, f64::NAN, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0]);
+
+    ta_tema(&ctx, &mut r, &input, 2).unwrap();
+    let nan = f64::NAN;
+    assert_vec_eq_nan(&r, &[nan, nan, nan, 4.0, 5.0, 6.0, 7.0, 8.0]);
+
+    ta_t3(&ctx, &mut r, &input, 2, 1.0).unwrap();
+    assert_vec_eq_nan(&r, &[nan, nan, nan, nan, nan, nan, 7.0, 8.0]);
+
+    let flat = vec![3.0; 8];
+    ta_t3(&ctx, &mut r, &flat, 2, 0.7).unwrap();
+    assert!((r[7] - 3.0).abs() < 1e-12);
+  }
+
+  #[test]
+  fn test_hma_zlema_alma() {
+    let input: Vec<f64> = (1..=6).map(|i| i as f64).collect();
+    let mut r = vec![0.0; 6];
+    let ctx = Context::new(0, 1, 0);
+    let nan = f64::NAN;
+
+    ta_hma(&ctx, &mut r, &input, 4).unwrap();
+    assert_vec_eq_nan(&r, &[nan, nan, nan, nan, 5.0, 6.0]);
+
+    // data = 2 * x - ref(x, 1) = x + 1, EMA(3) lags it by 1
+    ta_zlema(&ctx, &mut r, &input, 3).unwrap();
+    assert_vec_eq_nan(&r, &[nan, nan, nan, 4.0, 5.0, 6.0]);
+
+    // symmetric weights give the centre of the window
+    ta_alma(&ctx, &mut r, &input, 3, 0.5, 6.0).unwrap();
+    assert!((r[2] - 2.0).abs() < 1e-12 && (r[5] - 5.0).abs() < 1e-12);
+    assert!(r[1].is_nan());
+  }
+
+  #[test]
+  fn test_kama_frama() {
+    let input = vec![1.0, 2.0, 3.0, 2.0];
+    let mut r = vec![0.0; 4];
+    let ctx = Context::new(0, 1, 0);
+
+    ta_kama(&ctx, &mut r, &input, 2, 2, 30).unwrap();
+    assert!(r[1].is_nan());
+    // efficiency 1 gives the fast constant, seeded from the previous input
+    let k2 = 2.0 + (2.0f64 / 3.0).powi(2);
+    assert!((r[2] - k2).abs() < 1e-12);
+    // no net change gives the slow constant
+    let k3 = k2 + (2.0f64 / 31.0).powi(2) * (2.0 - k2);
+    assert!((r[3] - k3).abs() < 1e-12);
+
+    // D = 2 on a zig-zag, alpha = exp(-4.6)
+    let input = vec![1.0, 2.0, 1.0, 2.0, 1.0];
+    let mut r = vec![0.0; 5];
+    ta_frama(&ctx, &mut r, &input, 4).unwrap();
+    let a = (-4.6f64).exp();
+    assert!(r[2].is_nan());
+    assert!((r[3] - (1.0 + a)).abs() < 1e-12);
+    assert!((r[4] - (1.0 + a) * (1.0 - a) - a).abs() < 1e-12);
+    assert!(ta_frama(&ctx, &mut r, &input, 3).is_err());
+  }
+
+  #[test]
+  fn test_adaptive_nan() {
+    let input = vec![1.0, 2.0, f64::NAN, 3.0, 4.0, 5.0];
+    let mut r = vec![0.0; 6];
+    let nan = f64::NAN;
+
+    // a missing bar restarts the warm-up
+    let ctx = Context::new(0, 1, 0);
+    ta_dema(&ctx, &mut r, &input, 2).unwrap();
+    assert_vec_eq_nan(&r, &[nan, nan, nan, nan, nan, 5.0]);
+
+    let ctx = Context::new(0, 1, FLAG_SKIP_NAN);
+    ta_dema(&ctx, &mut r, &input, 2).unwrap();
+    assert_vec_eq_nan(&r, &[nan, nan, nan, 3.0, 4.0, 5.0]);
+  }
+}
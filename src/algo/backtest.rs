@@ -0,0 +1,462 @@
+// Copyright 2026 MSD-RS Project LiJia
+// SPDX-License-Identifier: BSD-2-Clause
+
+use num_traits::Float;
+use rayon::prelude::*;
+
+use crate::algo::{
+  Context, Error, RANK_AVERAGE, is_normal,
+  rank::{bin_of, rank_items},
+};
+
+/// Layer holdings and layer returns of one date
+struct LayerDate<NumT> {
+  /// (layer, normalized weight) of each security, None if not held
+  holdings: Vec<Option<(usize, NumT)>>,
+  /// return of each layer, NaN if no member has a valid return
+  returns: Vec<NumT>,
+}
+
+/// Quantile portfolio (layered) backtest
+///
+/// At each date the securities with valid factor (and valid positive weight if
+/// value-weighted) are split into `layers` buckets with the `ta_bins` rule,
+/// layer 0 holds the lowest factor values. Each layer is weighted equally, or
+/// by `weights` (e.g. market value) when it is not empty, and earns `returns`,
+/// which should be one period forward returns (e.g. `FRET(open, close, is_calc, 1, 1)`)
+/// for the NAV to be meaningful. Outputs, `ret`, `nav` and `turnover` are
+/// row-major (layers, T) matrices, `layers * T` values, row `k` holds layer `k`:
+/// ret: layer return
+/// nav: cumulative product of (1 + layer return), NaN returns count as 0
+/// turnover: half the sum of absolute weight changes from the previous date, 1 means fully replaced
+/// spread: top minus bottom layer return, `T` values
+#[allow(clippy::too_many_arguments)]
+pub fn ta_layer_backtest<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  ret: &mut [NumT],
+  nav: &mut [NumT],
+  turnover: &mut [NumT],
+  spread: &mut [NumT],
+  factor: &[NumT],
+  returns: &[NumT],
+  weights: &[NumT],
+  layers: usize,
+) -> Result<(), Error> {
+  if factor.len() != returns.len() {
+    return Err(Error::LengthMismatch(factor.len(), returns.len()));
+  }
+  if !weights.is_empty() && weights.len() != factor.len() {
+    return Err(Error::LengthMismatch(factor.len(), weights.len()));
+  }
+
+  let group_size = ctx.chunk_size(factor.len());
+  let groups = ctx.groups();
+  if factor.len() != group_size * groups {
+    return Err(Error::LengthMismatch(factor.len(), group_size * groups));
+  }
+  if layers == 0 {
+    return Err(Error::InvalidParameter("layers must be >= 1".to_string()));
+  }
+  for len in [ret.len(), nav.len(), turnover.len()] {
+    if len != layers * group_size {
+      return Err(Error::LengthMismatch(len, layers * group_size));
+    }
+  }
+  if spread.len() != group_size {
+    return Err(Error::LengthMismatch(spread.len(), group_size));
+  }
+
+  let layers_t = NumT::from(layers).unwrap();
+  let dates: Vec<LayerDate<NumT>> = (0..group_size)
+    .into_par_iter()
+    .map(|j| {
+      let mut items: Vec<(NumT, usize)> = Vec::with_capacity(groups);
+      for i in 0..groups {
+        let idx = i * group_size + j;
+        let valid_weight =
+          weights.is_empty() || (is_normal(&weights[idx]) && weights[idx] > NumT::zero());
+        if is_normal(&factor[idx]) && valid_weight {
+          items.push((factor[idx], i));
+        }
+      }
+
+      let mut holdings = vec![None; groups];
+      let mut layer_sum = vec![NumT::zero(); layers];
+      let total = NumT::from(items.len()).unwrap();
+      let mut ranks = vec![NumT::nan(); groups];
+      rank_items(&mut items, RANK_AVERAGE, &mut ranks);
+      for &(_, i) in &items {
+        let k = bin_of(ranks[i], total, layers_t).to_usize().unwrap();
+        let w = if weights.is_empty() {
+          NumT::one()
+        } else {
+          weights[i * group_size + j]
+        };
+        layer_sum[k] = layer_sum[k] + w;
+        holdings[i] = Some((k, w));
+      }
+
+      let mut ret_sum = vec![NumT::zero(); layers];
+      let mut ret_weight = vec![NumT::zero(); layers];
+      for (i, h) in holdings.iter_mut().enumerate() {
+        if let Some((k, w)) = h {
+          *w = *w / layer_sum[*k];
+          let x = returns[i * group_size + j];
+          if is_normal(&x) {
+            ret_sum[*k] = ret_sum[*k] + *w * x;
+            ret_weight[*k] = ret_weight[*k] + *w;
+          }
+        }
+      }
+
+      let returns = ret_sum
+        .iter()
+        .zip(&ret_weight)
+        .map(|(&s, &w)| if w > NumT::zero() { s / w } else { NumT::nan() })
+        .collect();
+      LayerDate { holdings, returns }
+    })
+    .collect();
+
+  ret.fill(NumT::nan());
+  nav.fill(NumT::nan());
+  turnover.fill(NumT::nan());
+
+  for k in 0..layers {
+    let row = k * group_size;
+    let mut value = NumT::nan();
+    for (j, date) in dates.iter().enumerate() {
+      let x = date.returns[k];
+      ret[row + j] = x;
+      if is_normal(&x) {
+        value = if value.is_nan() { NumT::one() } else { value } * (NumT::one() + x);
+      }
+      nav[row + j] = value;
+
+      if j == 0 {
+        continue;
+      }
+      let prev = &dates[j - 1].holdings;
+      let layer_weight = |h: &Option<(usize, NumT)>| match h {
+        Some((layer, w)) if *layer == k => Some(*w),
+        _ => None,
+      };
+      let held = |h: &[Option<(usize, NumT)>]| h.iter().any(|h| layer_weight(h).is_some());
+      if !held(prev) || !held(&date.holdings) {
+        continue;
+      }
+      let mut change = NumT::zero();
+      for (a, b) in prev.iter().zip(&date.holdings) {
+        let a = layer_weight(a).unwrap_or(NumT::zero());
+        let b = layer_weight(b).unwrap_or(NumT::zero());
+        change = change + (b - a).abs();
+      }
+      turnover[row + j] = change / NumT::from(2usize).unwrap();
+    }
+  }
+
+  for (s, date) in spread.iter_mut().zip(&dates) {
+    *s = date.returns[layers - 1] - date.returns[0];
+  }
+
+  Ok(())
+}
+
+/// Weight based portfolio simulator
+///
+/// `target` is the target weight (relative to NAV) decided at each date, NaN
+/// means 0. It is traded `delay` dates later, except for securities whose
+/// `is_calc` is 0 or NaN at the trading date (e.g. suspended or limit up/down),
+/// which keep their drifted weight. Holdings at date t earn `returns` at t+1,
+/// `returns` is the period return ending at each date (e.g. close / prev close - 1),
+/// NaN return counts as 0. Trading costs `cost` per unit of traded weight.
+/// Outputs, per date values are written to every security of the date:
+/// ret: portfolio return before cost
+/// turnover: sum of absolute weight changes at the date
+/// gross: gross exposure, sum of absolute holdings
+/// net: net exposure, sum of holdings
+/// pnl: portfolio return after cost, ret - cost * turnover
+/// holdings: realized weight of each security after trading
+#[allow(clippy::too_many_arguments)]
+pub fn ta_portfolio_sim<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  ret: &mut [NumT],
+  turnover: &mut [NumT],
+  gross: &mut [NumT],
+  net: &mut [NumT],
+  pnl: &mut [NumT],
+  holdings: &mut [NumT],
+  target: &[NumT],
+  returns: &[NumT],
+  is_calc: &[NumT],
+  cost: NumT,
+  delay: usize,
+) -> Result<(), Error> {
+  for len in [
+    turnover.len(),
+    gross.len(),
+    net.len(),
+    pnl.len(),
+    holdings.len(),
+    target.len(),
+    returns.len(),
+    is_calc.len(),
+  ] {
+    if ret.len() != len {
+      return Err(Error::LengthMismatch(ret.len(), len));
+    }
+  }
+
+  let group_size = ctx.chunk_size(ret.len());
+  let groups = ctx.groups();
+  if ret.len() != group_size * groups {
+    return Err(Error::LengthMismatch(ret.len(), group_size * groups));
+  }
+
+  // (ret, turnover, gross, net, pnl) of each date
+  let mut series = Vec::with_capacity(group_size);
+  let mut weights = vec![NumT::zero(); groups];
+  for j in 0..group_size {
+    // drift the weights of the previous date with this date's returns
+    let mut port_ret = NumT::zero();
+    for (i, w) in weights.iter().enumerate() {
+      let x = returns[i * group_size + j];
+      if is_normal(&x) {
+        port_ret = port_ret + *w * x;
+      }
+    }
+    let nav = NumT::one() + port_ret;
+    for (i, w) in weights.iter_mut().enumerate() {
+      let x = returns[i * group_size + j];
+      if is_normal(&x) {
+        *w = *w * (NumT::one() + x);
+      }
+      if nav > NumT::zero() {
+        *w = *w / nav;
+      }
+    }
+
+    let mut traded = NumT::zero();
+    let mut gross_exposure = NumT::zero();
+    let mut net_exposure = NumT::zero();
+    for (i, w) in weights.iter_mut().enumerate() {
+      let idx = i * group_size + j;
+      let m = is_calc[idx];
+      if is_normal(&m) && m != NumT::zero() {
+        let desired = if j >= delay && is_normal(&target[idx - delay]) {
+          target[idx - delay]
+        } else {
+          NumT::zero()
+        };
+        traded = traded + (desired - *w).abs();
+        *w = desired;
+      }
+      holdings[idx] = *w;
+      gross_exposure = gross_exposure + w.abs();
+      net_exposure = net_exposure + *w;
+    }
+
+    series.push((
+      port_ret,
+      traded,
+      gross_exposure,
+      net_exposure,
+      port_ret - cost * traded,
+    ));
+  }
+
+  ret
+    .par_chunks_mut(group_size)
+    .zip(turnover.par_chunks_mut(group_size))
+    .zip(gross.par_chunks_mut(group_size))
+    .zip(net.par_chunks_mut(group_size))
+    .zip(pnl.par_chunks_mut(group_size))
+    .for_each(|((((ret, turnover), gross), net), pnl)| {
+      for (j, s) in series.iter().enumerate() {
+        ret[j] = s.0;
+        turnover[j] = s.1;
+        gross[j] = s.2;
+        net[j] = s.3;
+        pnl[j] = s.4;
+      }
+    });
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::algo::assert_vec_eq_nan;
+
+  #[test]
+  fn test_layer_backtest_equal_weight() {
+    // 4 securities, 3 dates, 2 layers
+    let ctx = Context::new(0, 4, 0);
+    let factor = [
+      1.0,
+      1.0,
+      4.0, //
+      2.0,
+      3.0,
+      3.0, //
+      3.0,
+      2.0,
+      2.0, //
+      4.0,
+      4.0,
+      f64::NAN,
+    ];
+    let returns = [
+      0.1,
+      0.1,
+      0.0, //
+      0.3,
+      0.2,
+      0.0, //
+      0.0,
+      -0.1,
+      0.0, //
+      0.2,
+      f64::NAN,
+      0.0,
+    ];
+    let (mut ret, mut nav, mut turnover, mut spread) =
+      (vec![0.0; 6], vec![0.0; 6], vec![0.0; 6], vec![0.0; 3]);
+    ta_layer_backtest(
+      &ctx,
+      &mut ret,
+      &mut nav,
+      &mut turnover,
+      &mut spread,
+      &factor,
+      &returns,
+      &[],
+      2,
+    )
+    .unwrap();
+
+    // date 0: bottom {0, 1}, top {2, 3}
+    // date 1: bottom {0, 2}, top {1, 3}, security 3 has NaN return
+    // date 2: 3 valid, ranks 1, 2, 3 -> bins 0, 0, 1: bottom {1, 2}, top {0}
+    let nan = f64::NAN;
+    assert_vec_eq_nan(&ret, &[0.2, 0.0, 0.0, 0.1, 0.2, 0.0]);
+    assert_vec_eq_nan(&nav, &[1.2, 1.2, 1.2, 1.1, 1.32, 1.32]);
+    assert_vec_eq_nan(&turnover, &[nan, 0.5, 0.5, nan, 0.5, 1.0]);
+    assert_vec_eq_nan(&spread, &[-0.1, 0.2, 0.0]);
+  }
+
+  #[test]
+  fn test_layer_backtest_more_layers() {
+    // 2 securities, 2 dates, 3 layers: ranks 1, 2 of 2 -> bins 0, 1, layer 2 is empty
+    let ctx = Context::new(0, 2, 0);
+    let factor = [1.0, 2.0, 2.0, 1.0];
+    let returns = [0.1, 0.2, 0.3, 0.4];
+    let (mut ret, mut nav, mut turnover, mut spread) = ([0.0; 6], [0.0; 6], [0.0; 6], [0.0; 2]);
+    ta_layer_backtest(
+      &ctx,
+      &mut ret,
+      &mut nav,
+      &mut turnover,
+      &mut spread,
+      &factor,
+      &returns,
+      &[],
+      3,
+    )
+    .unwrap();
+    let nan = f64::NAN;
+    assert_vec_eq_nan(&ret, &[0.1, 0.4, 0.3, 0.2, nan, nan]);
+    assert_vec_eq_nan(&nav, &[1.1, 1.54, 1.3, 1.56, nan, nan]);
+    assert_vec_eq_nan(&turnover, &[nan, 1.0, nan, 1.0, nan, nan]);
+    assert_vec_eq_nan(&spread, &[nan, nan]);
+  }
+
+  #[test]
+  fn test_layer_backtest_value_weight() {
+    // 2 securities, 1 date, 1 layer
+    let ctx = Context::new(0, 2, 0);
+    let factor = [1.0, 2.0];
+    let returns = [0.1, 0.4];
+    let weights = [3.0, 1.0];
+    let (mut ret, mut nav, mut turnover, mut spread) = ([0.0; 1], [0.0; 1], [0.0; 1], [0.0; 1]);
+    ta_layer_backtest(
+      &ctx,
+      &mut ret,
+      &mut nav,
+      &mut turnover,
+      &mut spread,
+      &factor,
+      &returns,
+      &weights,
+      1,
+    )
+    .unwrap();
+    assert_vec_eq_nan(&ret, &[0.175]);
+    assert_vec_eq_nan(&nav, &[1.175]);
+    assert_vec_eq_nan(&spread, &[0.0]);
+
+    assert!(
+      ta_layer_backtest(
+        &ctx,
+        &mut ret,
+        &mut nav,
+        &mut turnover,
+        &mut spread,
+        &factor,
+        &returns,
+        &weights,
+        0
+      )
+      .is_err()
+    );
+  }
+
+  #[test]
+  fn test_portfolio_sim() {
+    // 2 securities, 3 dates, security 1 can not trade at date 2
+    let ctx = Context::new(0, 2, 0);
+    let target = [0.5, 0.5, 0.5, 0.5, 0.5, 0.0];
+    let returns = [0.0, 0.1, 0.0, 0.0, -0.1, 0.2];
+    let is_calc = [1.0, 1.0, 1.0, 1.0, 1.0, 0.0];
+    let n = target.len();
+    let mut out = vec![vec![0.0; n]; 6];
+    let [ret, turnover, gross, net, pnl, holdings] =
+      out.get_disjoint_mut([0, 1, 2, 3, 4, 5]).unwrap();
+    ta_portfolio_sim(
+      &ctx, ret, turnover, gross, net, pnl, holdings, &target, &returns, &is_calc, 0.01, 0,
+    )
+    .unwrap();
+
+    // date 2: drifted weights are 0.5 / 1.1 and 0.6 / 1.1, only security 0 trades
+    let w0 = 0.5 / 1.1;
+    let w1 = 0.6 / 1.1;
+    assert_vec_eq_nan(ret, &[0.0, 0.0, 0.1, 0.0, 0.0, 0.1]);
+    assert_vec_eq_nan(turnover, &[1.0, 0.1, 0.5 - w0, 1.0, 0.1, 0.5 - w0]);
+    assert_vec_eq_nan(gross, &[1.0, 1.0, 0.5 + w1, 1.0, 1.0, 0.5 + w1]);
+    assert_vec_eq_nan(net, &[1.0, 1.0, 0.5 + w1, 1.0, 1.0, 0.5 + w1]);
+    let pnl2 = 0.1 - 0.01 * (0.5 - w0);
+    assert_vec_eq_nan(pnl, &[-0.01, -0.001, pnl2, -0.01, -0.001, pnl2]);
+    assert_vec_eq_nan(holdings, &[0.5, 0.5, 0.5, 0.5, 0.5, w1]);
+  }
+
+  #[test]
+  fn test_portfolio_sim_delay() {
+    // 1 security, target is traded one date later
+    let ctx = Context::new(0, 1, 0);
+    let target = [1.0, f64::NAN, -1.0];
+    let returns = [0.0, 0.1, 0.1];
+    let is_calc = [1.0, 1.0, 1.0];
+    let mut out = vec![vec![0.0; 3]; 6];
+    let [ret, turnover, gross, net, pnl, holdings] =
+      out.get_disjoint_mut([0, 1, 2, 3, 4, 5]).unwrap();
+    ta_portfolio_sim(
+      &ctx, ret, turnover, gross, net, pnl, holdings, &target, &returns, &is_calc, 0.0, 1,
+    )
+    .unwrap();
+    assert_vec_eq_nan(holdings, &[0.0, 1.0, 0.0]);
+    assert_vec_eq_nan(ret, &[0.0, 0.0, 0.1]);
+    assert_vec_eq_nan(turnover, &[0.0, 1.0, 1.0]);
+    assert_vec_eq_nan(net, &[0.0, 1.0, 0.0]);
+  }
+}
@@ -0,0 +1,383 @@
+// Copyright 2026 MSD-RS Project LiJia
+// SPDX-License-Identifier: BSD-2-Clause
+
+use num_traits::Float;
+
+use super::bars::{run_bars, run_window};
+use crate::algo::{Context, Error};
+
+pub(crate) fn true_range<NumT: Float>(h: NumT, l: NumT, prev_close: NumT) -> NumT {
+  (h - l)
+    .max((h - prev_close).abs())
+    .max((l - prev_close).abs())
+}
+
+/// Wilder's Average True Range, first value is the mean of `n` true ranges
+pub(crate) struct Atr<NumT> {
+  n: usize,
+  prev_close: Option<NumT>,
+  count: usize,
+  atr: NumT,
+}
+
+impl<NumT: Float> Atr<NumT> {
+  pub fn new(n: usize) -> Self {
+    Self {
+      n,
+      prev_close: None,
+      count: 0,
+      atr: NumT::zero(),
+    }
+  }
+
+  /// ATR after this bar, None during the warm-up of `n` true ranges
+  pub fn update(&mut self, h: NumT, l: NumT, c: NumT) -> Option<NumT> {
+    let prev_close = self.prev_close.replace(c)?;
+    let tr = true_range(h, l, prev_close);
+    let n = NumT::from(self.n).unwrap();
+    self.count += 1;
+    if self.count < self.n {
+      self.atr = self.atr + tr;
+      return None;
+    }
+    self.atr = if self.count == self.n {
+      (self.atr + tr) / n
+    } else {
+      (self.atr * (n - NumT::one()) + tr) / n
+    };
+    Some(self.atr)
+  }
+
+  /// Mean of the true ranges seen so far during the warm-up
+  pub fn partial(&self) -> Option<NumT> {
+    if self.count > 0 && self.count < self.n {
+      Some(self.atr / NumT::from(self.count).unwrap())
+    } else {
+      None
+    }
+  }
+
+  /// ATR after this bar, the partial mean is used during the warm-up unless
+  /// `strict`
+  fn next(&mut self, h: NumT, l: NumT, c: NumT, strict: bool) -> Option<NumT> {
+    match self.update(h, l, c) {
+      Some(v) => Some(v),
+      None if !strict => self.partial(),
+      None => None,
+    }
+  }
+}
+
+/// True Range
+///
+/// TR = max(high - low, |high - ref(close, 1)|, |low - ref(close, 1)|), NaN on
+/// the first bar as there is no previous close.
+///
+/// Ref: https://www.investopedia.com/terms/a/atr.asp
+pub fn ta_tr<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  high: &[NumT],
+  low: &[NumT],
+  close: &[NumT],
+) -> Result<(), Error> {
+  run_bars(
+    ctx,
+    [r],
+    [high, low, close],
+    || None,
+    |prev_close: &mut Option<NumT>, [h, l, c]| {
+      let pc = prev_close.replace(c)?;
+      Some([true_range(h, l, pc)])
+    },
+  )
+}
+
+/// Average True Range
+///
+/// Wilder's smoothing of TR, ATR = (ATR[-1] * (periods - 1) + TR) / periods,
+/// seeded with the mean of the first `periods` true ranges as TA-Lib. Without
+/// FLAG_STRICTLY_CYCLE the mean of the true ranges so far is output during the
+/// warm-up.
+///
+/// Ref: https://www.investopedia.com/terms/a/atr.asp
+pub fn ta_atr<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  high: &[NumT],
+  low: &[NumT],
+  close: &[NumT],
+  periods: usize,
+) -> Result<(), Error> {
+  if periods == 0 {
+    return Err(Error::InvalidPeriod(
+      "atr requires periods >= 1".to_string(),
+    ));
+  }
+
+  let strict = ctx.is_strictly_cycle();
+  run_bars(
+    ctx,
+    [r],
+    [high, low, close],
+    || Atr::new(periods),
+    |atr, [h, l, c]| atr.next(h, l, c, strict).map(|v| [v]),
+  )
+}
+
+/// Normalized Average True Range
+///
+/// NATR = ATR(periods) / close * 100
+///
+/// Ref: https://ta-lib.github.io/ta-lib-python/func_groups/volatility_indicators.html
+pub fn ta_natr<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  high: &[NumT],
+  low: &[NumT],
+  close: &[NumT],
+  periods: usize,
+) -> Result<(), Error> {
+  if periods == 0 {
+    return Err(Error::InvalidPeriod(
+      "natr requires periods >= 1".to_string(),
+    ));
+  }
+
+  let strict = ctx.is_strictly_cycle();
+  let hundred = NumT::from(100).unwrap();
+  run_bars(
+    ctx,
+    [r],
+    [high, low, close],
+    || Atr::new(periods),
+    |atr, [h, l, c]| atr.next(h, l, c, strict).map(|v| [v / c * hundred]),
+  )
+}
+
+/// Bollinger Bands
+///
+/// middle = MA(input, periods), upper / lower = middle +/- k * STD(input,
+/// periods) with the population stddev as TA-Lib BBANDS,
+/// %B = (input - lower) / (upper - lower), bandwidth = (upper - lower) / middle.
+///
+/// Ref: https://www.investopedia.com/terms/b/bollingerbands.asp
+#[allow(clippy::too_many_arguments)]
+pub fn ta_bbands<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  upper: &mut [NumT],
+  middle: &mut [NumT],
+  lower: &mut [NumT],
+  percent_b: &mut [NumT],
+  bandwidth: &mut [NumT],
+  input: &[NumT],
+  periods: usize,
+  k: NumT,
+) -> Result<(), Error> {
+  run_window(
+    ctx,
+    [upper, middle, lower, percent_b, bandwidth],
+    [input],
+    periods,
+    |w| {
+      let n = NumT::from(w.len()).unwrap();
+      let mean = w.iter().fold(NumT::zero(), |s, [x]| s + *x) / n;
+      let var = w
+        .iter()
+        .fold(NumT::zero(), |s, [x]| s + (*x - mean).powi(2))
+        / n;
+      let width = k * var.sqrt();
+      let (up, lo) = (mean + width, mean - width);
+      let [x] = *w.back().unwrap();
+      [up, mean, lo, (x - lo) / (up - lo), (up - lo) / mean]
+    },
+  )
+}
+
+/// Keltner Channels
+///
+/// middle = EMA(close, periods), upper / lower = middle +/- multiplier *
+/// ATR(atr_periods). The EMA is seeded with the first close, under
+/// FLAG_STRICTLY_CYCLE the outputs start once both the EMA has `periods`
+/// closes and the ATR is warmed up, otherwise as soon as there is a true range.
+///
+/// Ref: https://www.investopedia.com/terms/k/keltnerchannel.asp
+#[allow(clippy::too_many_arguments)]
+pub fn ta_keltner<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  upper: &mut [NumT],
+  middle: &mut [NumT],
+  lower: &mut [NumT],
+  high: &[NumT],
+  low: &[NumT],
+  close: &[NumT],
+  periods: usize,
+  atr_periods: usize,
+  multiplier: NumT,
+) -> Result<(), Error> {
+  if periods == 0 || atr_periods == 0 {
+    return Err(Error::InvalidPeriod(
+      "keltner requires periods >= 1".to_string(),
+    ));
+  }
+
+  let strict = ctx.is_strictly_cycle();
+  let alpha = NumT::from(2.0).unwrap() / NumT::from(periods + 1).unwrap();
+  run_bars(
+    ctx,
+    [upper, middle, lower],
+    [high, low, close],
+    || (Atr::new(atr_periods), NumT::nan(), 0),
+    |(atr, ema, count), [h, l, c]| {
+      *ema = if *count == 0 {
+        c
+      } else {
+        alpha * c + (NumT::one() - alpha) * *ema
+      };
+      *count += 1;
+      let atr = atr.next(h, l, c, strict)?;
+      if strict && *count < periods {
+        return None;
+      }
+      Some([*ema + multiplier * atr, *ema, *ema - multiplier * atr])
+    },
+  )
+}
+
+/// Donchian Channels
+///
+/// upper = HHV(high, periods), lower = LLV(low, periods),
+/// middle = (upper + lower) / 2
+///
+/// Ref: https://www.investopedia.com/terms/d/donchianchannels.asp
+pub fn ta_donchian<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  upper: &mut [NumT],
+  middle: &mut [NumT],
+  lower: &mut [NumT],
+  high: &[NumT],
+  low: &[NumT],
+  periods: usize,
+) -> Result<(), Error> {
+  run_window(ctx, [upper, middle, lower], [high, low], periods, |w| {
+    let (up, lo) = w.iter().fold(
+      (NumT::neg_infinity(), NumT::infinity()),
+      |(u, l), [h, lo]| (u.max(*h), l.min(*lo)),
+    );
+    [up, (up + lo) / NumT::from(2).unwrap(), lo]
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::algo::{
+    assert_vec_eq_nan,
+    context::{FLAG_SKIP_NAN, FLAG_STRICTLY_CYCLE},
+  };
+
+  #[test]
+  fn test_tr_atr() {
+    let high = vec![10.0, 12.0, 11.0, 13.0];
+    let low = vec![9.0, 10.0, 8.0, 11.0];
+    let close = vec![9.5, 11.0, 9.0, 12.0];
+    let mut r = vec![0.0; 4];
+
+    let ctx = Context::new(0, 1, 0);
+    ta_tr(&ctx, &mut r, &high, &low, &close).unwrap();
+    assert_vec_eq_nan(&r, &[f64::NAN, 2.5, 3.0, 4.0]);
+
+    let ctx = Context::new(0, 1, FLAG_STRICTLY_CYCLE);
+    ta_atr(&ctx, &mut r, &high, &low, &close, 2).unwrap();
+    // seed (2.5 + 3) / 2, then (2.75 + 4) / 2
+    assert_vec_eq_nan(&r, &[f64::NAN, f64::NAN, 2.75, 3.375]);
+
+    let ctx = Context::new(0, 1, 0);
+    ta_atr(&ctx, &mut r, &high, &low, &close, 2).unwrap();
+    assert_vec_eq_nan(&r, &[f64::NAN, 2.5, 2.75, 3.375]);
+
+    ta_natr(&ctx, &mut r, &high, &low, &close, 2).unwrap();
+    assert!((r[3] - 3.375 / 12.0 * 100.0).abs() < 1e-12);
+  }
+
+  #[test]
+  fn test_bbands() {
+    let input = vec![1.0, 2.0, 3.0, 4.0];
+    let n = input.len();
+    let (mut up, mut mid, mut lo, mut pb, mut bw) = (
+      vec![0.0; n],
+      vec![0.0; n],
+      vec![0.0; n],
+      vec![0.0; n],
+      vec![0.0; n],
+    );
+    let ctx = Context::new(0, 1, FLAG_STRICTLY_CYCLE);
+    ta_bbands(
+      &ctx, &mut up, &mut mid, &mut lo, &mut pb, &mut bw, &input, 3, 2.0,
+    )
+    .unwrap();
+    // window 2, 3, 4: mean 3, population stddev sqrt(2 / 3)
+    let sd = (2.0f64 / 3.0).sqrt();
+    assert_vec_eq_nan(&mid, &[f64::NAN, f64::NAN, 2.0, 3.0]);
+    assert!((up[3] - (3.0 + 2.0 * sd)).abs() < 1e-12);
+    assert!((lo[3] - (3.0 - 2.0 * sd)).abs() < 1e-12);
+    assert!((pb[3] - (1.0 + 2.0 * sd) / (4.0 * sd)).abs() < 1e-12);
+    assert!((bw[3] - 4.0 * sd / 3.0).abs() < 1e-12);
+
+    let ctx = Context::new(0, 1, 0);
+    ta_bbands(
+      &ctx, &mut up, &mut mid, &mut lo, &mut pb, &mut bw, &input, 3, 2.0,
+    )
+    .unwrap();
+    assert_vec_eq_nan(&mid, &[1.0, 1.5, 2.0, 3.0]);
+    // flat window, %B is undefined
+    assert!(pb[0].is_nan());
+    assert_eq!(bw[0], 0.0);
+  }
+
+  #[test]
+  fn test_keltner_donchian() {
+    let high = vec![10.0, 12.0, 11.0, 13.0];
+    let low = vec![9.0, 10.0, 8.0, 11.0];
+    let close = vec![9.5, 11.0, 9.0, 12.0];
+    let n = high.len();
+    let (mut up, mut mid, mut lo) = (vec![0.0; n], vec![0.0; n], vec![0.0; n]);
+
+    let ctx = Context::new(0, 1, FLAG_STRICTLY_CYCLE);
+    ta_keltner(
+      &ctx, &mut up, &mut mid, &mut lo, &high, &low, &close, 3, 2, 2.0,
+    )
+    .unwrap();
+    // ema 9.5, 10.25, 9.625; atr 2.75 at the third bar
+    assert_vec_eq_nan(&mid, &[f64::NAN, f64::NAN, 9.625, 10.8125]);
+    assert_vec_eq_nan(&up, &[f64::NAN, f64::NAN, 15.125, 17.5625]);
+    assert_vec_eq_nan(&lo, &[f64::NAN, f64::NAN, 4.125, 4.0625]);
+
+    ta_donchian(&ctx, &mut up, &mut mid, &mut lo, &high, &low, 2).unwrap();
+    assert_vec_eq_nan(&up, &[f64::NAN, 12.0, 12.0, 13.0]);
+    assert_vec_eq_nan(&lo, &[f64::NAN, 9.0, 8.0, 8.0]);
+    assert_vec_eq_nan(&mid, &[f64::NAN, 10.5, 10.0, 10.5]);
+  }
+
+  #[test]
+  fn test_band_nan() {
+    let high = vec![10.0, f64::NAN, 11.0, 13.0, 12.0];
+    let low = vec![9.0, 10.0, 8.0, 11.0, 10.0];
+    let n = high.len();
+    let (mut up, mut mid, mut lo) = (vec![0.0; n], vec![0.0; n], vec![0.0; n]);
+
+    // the window restarts after a missing bar
+    let ctx = Context::new(0, 1, 0);
+    ta_donchian(&ctx, &mut up, &mut mid, &mut lo, &high, &low, 2).unwrap();
+    assert_vec_eq_nan(&up, &[10.0, f64::NAN, f64::NAN, 13.0, 13.0]);
+
+    let ctx = Context::new(0, 1, FLAG_SKIP_NAN);
+    ta_donchian(&ctx, &mut up, &mut mid, &mut lo, &high, &low, 2).unwrap();
+    assert_vec_eq_nan(&up, &[10.0, f64::NAN, 11.0, 13.0, 13.0]);
+    assert_vec_eq_nan(&lo, &[9.0, f64::NAN, 8.0, 8.0, 10.0]);
+
+    let ctx = Context::new(2, 1, 0);
+    ta_donchian(&ctx, &mut up, &mut mid, &mut lo, &high, &low, 2).unwrap();
+    assert_vec_eq_nan(&up, &[f64::NAN, f64::NAN, f64::NAN, 13.0, 13.0]);
+  }
+}
@@ -0,0 +1,155 @@
+// Copyright 2026 MSD-RS Project LiJia
+// SPDX-License-Identifier: BSD-2-Clause
+
+use std::collections::VecDeque;
+
+use num_traits::Float;
+use rayon::prelude::*;
+
+use crate::algo::{Context, Error, is_normal};
+
+fn check_len<NumT, const M: usize, const K: usize>(
+  outputs: &[&mut [NumT]; K],
+  inputs: &[&[NumT]; M],
+) -> Result<usize, Error> {
+  let len = inputs[0].len();
+  for l in outputs
+    .iter()
+    .map(|o| o.len())
+    .chain(inputs.iter().map(|i| i.len()))
+  {
+    if l != len {
+      return Err(Error::LengthMismatch(l, len));
+    }
+  }
+  Ok(len)
+}
+
+/// Write the per group results back into the outputs
+fn scatter<NumT: Float + Send + Sync, const K: usize>(
+  outputs: [&mut [NumT]; K],
+  results: &[Vec<[NumT; K]>],
+  group_size: usize,
+) {
+  for (k, output) in outputs.into_iter().enumerate() {
+    output
+      .par_chunks_mut(group_size)
+      .zip(results.par_iter())
+      .for_each(|(r, res)| {
+        r.iter_mut().zip(res).for_each(|(r, v)| *r = v[k]);
+      });
+  }
+}
+
+/// Run a per bar state machine over each group
+///
+/// `step` gets the state and the values of `inputs` at a bar where all of them
+/// are valid, and returns the values of `outputs` once warmed up. Without
+/// FLAG_SKIP_NAN an invalid bar resets the state to `init()`, with
+/// FLAG_SKIP_NAN it is skipped. Outputs are NaN on invalid bars, during the
+/// warm-up and before `ctx.start`.
+pub(crate) fn run_bars<NumT, S, I, F, const M: usize, const K: usize>(
+  ctx: &Context,
+  outputs: [&mut [NumT]; K],
+  inputs: [&[NumT]; M],
+  init: I,
+  step: F,
+) -> Result<(), Error>
+where
+  NumT: Float + Send + Sync,
+  I: Fn() -> S + Send + Sync,
+  F: Fn(&mut S, [NumT; M]) -> Option<[NumT; K]> + Send + Sync,
+{
+  let len = check_len(&outputs, &inputs)?;
+  let group_size = ctx.chunk_size(len);
+  let results: Vec<Vec<[NumT; K]>> = (0..len / group_size.max(1))
+    .into_par_iter()
+    .map(|g| {
+      let start = ctx.start(group_size);
+      let end = ctx.end(group_size);
+      let mut out = vec![[NumT::nan(); K]; group_size];
+
+      let mut state = init();
+      for (i, out) in out.iter_mut().enumerate().take(end) {
+        let values: [NumT; M] = std::array::from_fn(|m| inputs[m][g * group_size + i]);
+        if !values.iter().all(is_normal) {
+          if !ctx.is_skip_nan() {
+            state = init();
+          }
+          continue;
+        }
+        if let Some(v) = step(&mut state, values)
+          && i >= start
+        {
+          *out = v;
+        }
+      }
+      out
+    })
+    .collect();
+
+  scatter(outputs, &results, group_size);
+  Ok(())
+}
+
+/// Run a rolling window function over each group
+///
+/// `f` gets the last `periods` bars where all `inputs` are valid, in time
+/// order, and returns the values of `outputs`. Without FLAG_SKIP_NAN an
+/// invalid bar restarts the window and the outputs stay NaN until it is full
+/// again, with FLAG_SKIP_NAN invalid bars are skipped. FLAG_STRICTLY_CYCLE
+/// requires a full window, otherwise partial windows at the head are used.
+pub(crate) fn run_window<NumT, F, const M: usize, const K: usize>(
+  ctx: &Context,
+  outputs: [&mut [NumT]; K],
+  inputs: [&[NumT]; M],
+  periods: usize,
+  f: F,
+) -> Result<(), Error>
+where
+  NumT: Float + Send + Sync,
+  F: Fn(&VecDeque<[NumT; M]>) -> [NumT; K] + Send + Sync,
+{
+  if periods == 0 {
+    return Err(Error::InvalidPeriod(
+      "window requires periods >= 1".to_string(),
+    ));
+  }
+
+  let len = check_len(&outputs, &inputs)?;
+  let group_size = ctx.chunk_size(len);
+  let results: Vec<Vec<[NumT; K]>> = (0..len / group_size.max(1))
+    .into_par_iter()
+    .map(|g| {
+      let start = ctx.start(group_size);
+      let end = ctx.end(group_size);
+      let mut out = vec![[NumT::nan(); K]; group_size];
+
+      let mut window = VecDeque::with_capacity(periods);
+      let mut gap = false;
+      for (i, out) in out.iter_mut().enumerate().take(end) {
+        let values: [NumT; M] = std::array::from_fn(|m| inputs[m][g * group_size + i]);
+        if !values.iter().all(is_normal) {
+          if !ctx.is_skip_nan() {
+            window.clear();
+            gap = true;
+          }
+          continue;
+        }
+        if window.len() == periods {
+          window.pop_front();
+        }
+        window.push_back(values);
+
+        let full = window.len() == periods;
+        if i >= start && (full || !(gap || ctx.is_strictly_cycle())) {
+          *out = f(&window);
+        }
+      }
+      out
+    })
+    .collect();
+
+  scatter(outputs, &results, group_size);
+  Ok(())
+}
@@ -82,12 +82,7 @@ pub fn ta_beta<NumT: Float + Send + Sync>(
             continue;
           }
 
-          let nf = NumT::from(n).unwrap();
-          let denom = nf * sum_y2 - sum_y * sum_y;
-          if denom == NumT::zero() {
-            continue;
-          }
-          r[i] = (nf * sum_xy - sum_x * sum_y) / denom;
+          r[i] = beta_of(n, sum_x, sum_y, sum_xy, sum_y2);
         }
       } else {
         let mut sum_x = NumT::zero();
@@ -147,12 +142,7 @@ pub fn ta_beta<NumT: Float + Send + Sync>(
           if n < 2 {
             continue;
           }
-          let nf = NumT::from(n).unwrap();
-          let denom = nf * sum_y2 - sum_y * sum_y;
-          if denom == NumT::zero() {
-            continue;
-          }
-          r[i] = (nf * sum_xy - sum_x * sum_y) / denom;
+          r[i] = beta_of(n, sum_x, sum_y, sum_xy, sum_y2);
         }
       }
     });
@@ -160,6 +150,23 @@ pub fn ta_beta<NumT: Float + Send + Sync>(
   Ok(())
 }
 
+/// Beta of `n` (x, y) pairs from sum(x), sum(y), sum(x * y) and sum(y * y),
+/// NaN if y has no variance
+pub(crate) fn beta_of<NumT: Float>(
+  n: usize,
+  sum_x: NumT,
+  sum_y: NumT,
+  sum_xy: NumT,
+  sum_y2: NumT,
+) -> NumT {
+  let nf = NumT::from(n).unwrap();
+  let denom = nf * sum_y2 - sum_y * sum_y;
+  if denom == NumT::zero() {
+    return NumT::nan();
+  }
+  (nf * sum_xy - sum_x * sum_y) / denom
+}
+
 #[cfg(test)]
 mod tests {
   use crate::algo::{
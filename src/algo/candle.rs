@@ -0,0 +1,710 @@
+// Copyright 2026 MSD-RS Project LiJia
+// SPDX-License-Identifier: BSD-2-Clause
+
+use num_traits::Float;
+use rayon::prelude::*;
+
+use crate::algo::{Context, Error, is_normal};
+
+/// Part of a candle a setting is measured on
+#[derive(Clone, Copy)]
+enum RangeType {
+  RealBody,
+  HighLow,
+}
+
+/// TA-Lib candle setting, the threshold is `factor` times the average range
+/// of the previous `period` bars, or of the bar itself when `period` is 0
+struct Setting {
+  range: RangeType,
+  period: usize,
+  factor: f64,
+}
+
+const BODY_LONG: Setting = Setting {
+  range: RangeType::RealBody,
+  period: 10,
+  factor: 1.0,
+};
+const BODY_SHORT: Setting = Setting {
+  range: RangeType::RealBody,
+  period: 10,
+  factor: 1.0,
+};
+const BODY_DOJI: Setting = Setting {
+  range: RangeType::HighLow,
+  period: 10,
+  factor: 0.1,
+};
+const SHADOW_LONG: Setting = Setting {
+  range: RangeType::RealBody,
+  period: 0,
+  factor: 1.0,
+};
+const SHADOW_VERY_SHORT: Setting = Setting {
+  range: RangeType::HighLow,
+  period: 10,
+  factor: 0.1,
+};
+const NEAR: Setting = Setting {
+  range: RangeType::HighLow,
+  period: 5,
+  factor: 0.2,
+};
+const FAR: Setting = Setting {
+  range: RangeType::HighLow,
+  period: 5,
+  factor: 0.6,
+};
+
+/// Bars of a group as [open, high, low, close]
+struct Candles<NumT> {
+  bars: Vec<[NumT; 4]>,
+}
+
+impl<NumT: Float> Candles<NumT> {
+  fn open(&self, i: usize) -> NumT {
+    self.bars[i][0]
+  }
+
+  fn high(&self, i: usize) -> NumT {
+    self.bars[i][1]
+  }
+
+  fn low(&self, i: usize) -> NumT {
+    self.bars[i][2]
+  }
+
+  fn close(&self, i: usize) -> NumT {
+    self.bars[i][3]
+  }
+
+  fn body(&self, i: usize) -> NumT {
+    (self.close(i) - self.open(i)).abs()
+  }
+
+  fn body_top(&self, i: usize) -> NumT {
+    self.close(i).max(self.open(i))
+  }
+
+  fn body_bottom(&self, i: usize) -> NumT {
+    self.close(i).min(self.open(i))
+  }
+
+  fn upper_shadow(&self, i: usize) -> NumT {
+    self.high(i) - self.body_top(i)
+  }
+
+  fn lower_shadow(&self, i: usize) -> NumT {
+    self.body_bottom(i) - self.low(i)
+  }
+
+  fn white(&self, i: usize) -> bool {
+    self.close(i) >= self.open(i)
+  }
+
+  /// 1 for a white candle, -1 for a black one
+  fn color(&self, i: usize) -> i32 {
+    if self.white(i) { 1 } else { -1 }
+  }
+
+  fn range(&self, range: RangeType, i: usize) -> NumT {
+    match range {
+      RangeType::RealBody => self.body(i),
+      RangeType::HighLow => self.high(i) - self.low(i),
+    }
+  }
+
+  /// Threshold of `setting` at bar `i`
+  fn avg(&self, setting: &Setting, i: usize) -> NumT {
+    let v = if setting.period == 0 {
+      self.range(setting.range, i)
+    } else {
+      (i - setting.period..i).fold(NumT::zero(), |s, j| s + self.range(setting.range, j))
+        / NumT::from(setting.period).unwrap()
+    };
+    v * NumT::from(setting.factor).unwrap()
+  }
+}
+
+/// Evaluate a pattern on each bar of each group
+///
+/// `f` returns the signal of the pattern ending at bar `i` of the group's
+/// candles, which needs the `lookback` bars before it. Without FLAG_SKIP_NAN
+/// the output is NaN while one of those bars is missing, with FLAG_SKIP_NAN
+/// missing bars are dropped before matching.
+#[allow(clippy::too_many_arguments)]
+fn run_pattern<NumT, F>(
+  ctx: &Context,
+  r: &mut [NumT],
+  open: &[NumT],
+  high: &[NumT],
+  low: &[NumT],
+  close: &[NumT],
+  lookback: usize,
+  f: F,
+) -> Result<(), Error>
+where
+  NumT: Float + Send + Sync,
+  F: Fn(&Candles<NumT>, usize) -> i32 + Send + Sync,
+{
+  for l in [open.len(), high.len(), low.len(), close.len()] {
+    if l != r.len() {
+      return Err(Error::LengthMismatch(r.len(), l));
+    }
+  }
+
+  let chunk = ctx.chunk_size(r.len());
+  r.par_chunks_mut(chunk).enumerate().for_each(|(g, r)| {
+    let start = ctx.start(r.len());
+    let end = ctx.end(r.len());
+    r.fill(NumT::nan());
+
+    let base = g * chunk;
+    let (index, bars): (Vec<usize>, Vec<[NumT; 4]>) = (0..end)
+      .map(|i| {
+        let j = base + i;
+        (i, [open[j], high[j], low[j], close[j]])
+      })
+      .filter(|(_, b)| !ctx.is_skip_nan() || b.iter().all(is_normal))
+      .unzip();
+    let candles = Candles { bars };
+
+    let mut last_invalid = None;
+    for (k, i) in index.into_iter().enumerate() {
+      if !candles.bars[k].iter().all(is_normal) {
+        last_invalid = Some(k);
+        continue;
+      }
+      if k < lookback || i < start || last_invalid.is_some_and(|j| j + lookback >= k) {
+        continue;
+      }
+      r[i] = NumT::from(f(&candles, k)).unwrap();
+    }
+  });
+
+  Ok(())
+}
+
+/// Doji
+///
+/// Body <= 10% of the average high - low range, +100 on a match.
+///
+/// Ref: https://ta-lib.org/functions/
+pub fn ta_cdldoji<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  open: &[NumT],
+  high: &[NumT],
+  low: &[NumT],
+  close: &[NumT],
+) -> Result<(), Error> {
+  run_pattern(ctx, r, open, high, low, close, 10, |c, i| {
+    if c.body(i) <= c.avg(&BODY_DOJI, i) {
+      100
+    } else {
+      0
+    }
+  })
+}
+
+/// Dragonfly Doji
+///
+/// Doji with a very short upper shadow and a lower shadow that is not very
+/// short, +100 on a match.
+///
+/// Ref: https://ta-lib.org/functions/
+pub fn ta_cdldragonflydoji<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  open: &[NumT],
+  high: &[NumT],
+  low: &[NumT],
+  close: &[NumT],
+) -> Result<(), Error> {
+  run_pattern(ctx, r, open, high, low, close, 10, |c, i| {
+    if c.body(i) <= c.avg(&BODY_DOJI, i)
+      && c.upper_shadow(i) < c.avg(&SHADOW_VERY_SHORT, i)
+      && c.lower_shadow(i) > c.avg(&SHADOW_VERY_SHORT, i)
+    {
+      100
+    } else {
+      0
+    }
+  })
+}
+
+/// Gravestone Doji
+///
+/// Doji with a very short lower shadow and an upper shadow that is not very
+/// short, +100 on a match.
+///
+/// Ref: https://ta-lib.org/functions/
+pub fn ta_cdlgravestonedoji<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  open: &[NumT],
+  high: &[NumT],
+  low: &[NumT],
+  close: &[NumT],
+) -> Result<(), Error> {
+  run_pattern(ctx, r, open, high, low, close, 10, |c, i| {
+    if c.body(i) <= c.avg(&BODY_DOJI, i)
+      && c.lower_shadow(i) < c.avg(&SHADOW_VERY_SHORT, i)
+      && c.upper_shadow(i) > c.avg(&SHADOW_VERY_SHORT, i)
+    {
+      100
+    } else {
+      0
+    }
+  })
+}
+
+/// Marubozu
+///
+/// Long body with very short shadows on both ends, +100 for a white and -100
+/// for a black candle.
+///
+/// Ref: https://ta-lib.org/functions/
+pub fn ta_cdlmarubozu<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  open: &[NumT],
+  high: &[NumT],
+  low: &[NumT],
+  close: &[NumT],
+) -> Result<(), Error> {
+  run_pattern(ctx, r, open, high, low, close, 10, |c, i| {
+    if c.body(i) > c.avg(&BODY_LONG, i)
+      && c.upper_shadow(i) < c.avg(&SHADOW_VERY_SHORT, i)
+      && c.lower_shadow(i) < c.avg(&SHADOW_VERY_SHORT, i)
+    {
+      c.color(i) * 100
+    } else {
+      0
+    }
+  })
+}
+
+/// Small body with a long lower and a very short upper shadow
+fn hammer_shape<NumT: Float>(c: &Candles<NumT>, i: usize) -> bool {
+  c.body(i) < c.avg(&BODY_SHORT, i)
+    && c.lower_shadow(i) > c.avg(&SHADOW_LONG, i)
+    && c.upper_shadow(i) < c.avg(&SHADOW_VERY_SHORT, i)
+}
+
+/// Small body with a long upper and a very short lower shadow
+fn star_shape<NumT: Float>(c: &Candles<NumT>, i: usize) -> bool {
+  c.body(i) < c.avg(&BODY_SHORT, i)
+    && c.upper_shadow(i) > c.avg(&SHADOW_LONG, i)
+    && c.lower_shadow(i) < c.avg(&SHADOW_VERY_SHORT, i)
+}
+
+/// Hammer
+///
+/// Hammer shaped candle with the body below or near the low of the previous
+/// candle, +100 on a match.
+///
+/// Ref: https://ta-lib.org/functions/
+pub fn ta_cdlhammer<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  open: &[NumT],
+  high: &[NumT],
+  low: &[NumT],
+  close: &[NumT],
+) -> Result<(), Error> {
+  run_pattern(ctx, r, open, high, low, close, 11, |c, i| {
+    if hammer_shape(c, i) && c.body_bottom(i) <= c.low(i - 1) + c.avg(&NEAR, i - 1) {
+      100
+    } else {
+      0
+    }
+  })
+}
+
+/// Hanging Man
+///
+/// Hammer shaped candle with the body above or near the high of the previous
+/// candle, -100 on a match.
+///
+/// Ref: https://ta-lib.org/functions/
+pub fn ta_cdlhangingman<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  open: &[NumT],
+  high: &[NumT],
+  low: &[NumT],
+  close: &[NumT],
+) -> Result<(), Error> {
+  run_pattern(ctx, r, open, high, low, close, 11, |c, i| {
+    if hammer_shape(c, i) && c.body_bottom(i) >= c.high(i - 1) - c.avg(&NEAR, i - 1) {
+      -100
+    } else {
+      0
+    }
+  })
+}
+
+/// Inverted Hammer
+///
+/// Small body with a long upper shadow gapping down from the previous body,
+/// +100 on a match.
+///
+/// Ref: https://ta-lib.org/functions/
+pub fn ta_cdlinvertedhammer<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  open: &[NumT],
+  high: &[NumT],
+  low: &[NumT],
+  close: &[NumT],
+) -> Result<(), Error> {
+  run_pattern(ctx, r, open, high, low, close, 11, |c, i| {
+    if star_shape(c, i) && c.body_top(i) < c.body_bottom(i - 1) {
+      100
+    } else {
+      0
+    }
+  })
+}
+
+/// Shooting Star
+///
+/// Small body with a long upper shadow gapping up from the previous body,
+/// -100 on a match.
+///
+/// Ref: https://ta-lib.org/functions/
+pub fn ta_cdlshootingstar<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  open: &[NumT],
+  high: &[NumT],
+  low: &[NumT],
+  close: &[NumT],
+) -> Result<(), Error> {
+  run_pattern(ctx, r, open, high, low, close, 11, |c, i| {
+    if star_shape(c, i) && c.body_bottom(i) > c.body_top(i - 1) {
+      -100
+    } else {
+      0
+    }
+  })
+}
+
+/// Engulfing Pattern
+///
+/// The body of the candle engulfs the opposite colored body of the previous
+/// one, +100 when bullish and -100 when bearish. As TA-Lib >= 0.5 one end of
+/// the bodies may be equal, which scores +80 / -80.
+///
+/// Ref: https://ta-lib.org/functions/
+pub fn ta_cdlengulfing<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  open: &[NumT],
+  high: &[NumT],
+  low: &[NumT],
+  close: &[NumT],
+) -> Result<(), Error> {
+  run_pattern(ctx, r, open, high, low, close, 2, |c, i| {
+    let (open, close) = (c.open(i), c.close(i));
+    let (prev_open, prev_close) = (c.open(i - 1), c.close(i - 1));
+    let bullish = c.white(i)
+      && !c.white(i - 1)
+      && ((close >= prev_open && open < prev_close) || (close > prev_open && open <= prev_close));
+    let bearish = !c.white(i)
+      && c.white(i - 1)
+      && ((open >= prev_close && close < prev_open) || (open > prev_close && close <= prev_open));
+    if !(bullish || bearish) {
+      0
+    } else if open != prev_close && close != prev_open {
+      c.color(i) * 100
+    } else {
+      c.color(i) * 80
+    }
+  })
+}
+
+/// Harami Pattern
+///
+/// A short body inside the long body of the previous candle, +100 after a
+/// black and -100 after a white candle. As TA-Lib >= 0.5 a body sharing its
+/// top or bottom with the previous one scores +80 / -80.
+///
+/// Ref: https://ta-lib.org/functions/
+pub fn ta_cdlharami<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  open: &[NumT],
+  high: &[NumT],
+  low: &[NumT],
+  close: &[NumT],
+) -> Result<(), Error> {
+  run_pattern(ctx, r, open, high, low, close, 11, |c, i| {
+    if c.body(i - 1) <= c.avg(&BODY_LONG, i - 1) || c.body(i) > c.avg(&BODY_SHORT, i) {
+      return 0;
+    }
+    let (top, prev_top) = (c.body_top(i), c.body_top(i - 1));
+    let (bottom, prev_bottom) = (c.body_bottom(i), c.body_bottom(i - 1));
+    if top < prev_top && bottom > prev_bottom {
+      -c.color(i - 1) * 100
+    } else if top <= prev_top && bottom >= prev_bottom {
+      -c.color(i - 1) * 80
+    } else {
+      0
+    }
+  })
+}
+
+/// Morning Star
+///
+/// A long black candle, a short body gapping down and a white candle closing
+/// above `penetration` (usually 0.3) of the first body, +100 on a match.
+///
+/// Ref: https://ta-lib.org/functions/
+pub fn ta_cdlmorningstar<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  open: &[NumT],
+  high: &[NumT],
+  low: &[NumT],
+  close: &[NumT],
+  penetration: NumT,
+) -> Result<(), Error> {
+  run_pattern(ctx, r, open, high, low, close, 12, |c, i| {
+    if c.body(i - 2) > c.avg(&BODY_LONG, i - 2)
+      && !c.white(i - 2)
+      && c.body(i - 1) <= c.avg(&BODY_SHORT, i - 1)
+      && c.body_top(i - 1) < c.body_bottom(i - 2)
+      && c.body(i) > c.avg(&BODY_SHORT, i)
+      && c.white(i)
+      && c.close(i) > c.close(i - 2) + c.body(i - 2) * penetration
+    {
+      100
+    } else {
+      0
+    }
+  })
+}
+
+/// Evening Star
+///
+/// A long white candle, a short body gapping up and a black candle closing
+/// below `penetration` (usually 0.3) of the first body, -100 on a match.
+///
+/// Ref: https://ta-lib.org/functions/
+pub fn ta_cdleveningstar<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  open: &[NumT],
+  high: &[NumT],
+  low: &[NumT],
+  close: &[NumT],
+  penetration: NumT,
+) -> Result<(), Error> {
+  run_pattern(ctx, r, open, high, low, close, 12, |c, i| {
+    if c.body(i - 2) > c.avg(&BODY_LONG, i - 2)
+      && c.white(i - 2)
+      && c.body(i - 1) <= c.avg(&BODY_SHORT, i - 1)
+      && c.body_bottom(i - 1) > c.body_top(i - 2)
+      && c.body(i) > c.avg(&BODY_SHORT, i)
+      && !c.white(i)
+      && c.close(i) < c.close(i - 2) - c.body(i - 2) * penetration
+    {
+      -100
+    } else {
+      0
+    }
+  })
+}
+
+/// Three Advancing White Soldiers
+///
+/// Three white candles with rising closes and very short upper shadows, each
+/// opening within or near the previous body and not far shorter than it, the
+/// last one not short, +100 on a match.
+///
+/// Ref: https://ta-lib.org/functions/
+pub fn ta_cdl3whitesoldiers<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  open: &[NumT],
+  high: &[NumT],
+  low: &[NumT],
+  close: &[NumT],
+) -> Result<(), Error> {
+  run_pattern(ctx, r, open, high, low, close, 12, |c, i| {
+    let soldier = |j: usize| c.white(j) && c.upper_shadow(j) < c.avg(&SHADOW_VERY_SHORT, j);
+    let advance = |j: usize| {
+      c.close(j) > c.close(j - 1)
+        && c.open(j) > c.open(j - 1)
+        && c.open(j) <= c.close(j - 1) + c.avg(&NEAR, j - 1)
+        && c.body(j) > c.body(j - 1) - c.avg(&FAR, j - 1)
+    };
+    if soldier(i - 2)
+      && soldier(i - 1)
+      && soldier(i)
+      && advance(i - 1)
+      && advance(i)
+      && c.body(i) > c.avg(&BODY_SHORT, i)
+    {
+      100
+    } else {
+      0
+    }
+  })
+}
+
+/// Three Black Crows
+///
+/// A white candle followed by three black candles with very short lower
+/// shadows, each opening within the previous body and closing lower, the
+/// first closing under the high of the white candle, -100 on a match.
+///
+/// Ref: https://ta-lib.org/functions/
+pub fn ta_cdl3blackcrows<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  open: &[NumT],
+  high: &[NumT],
+  low: &[NumT],
+  close: &[NumT],
+) -> Result<(), Error> {
+  run_pattern(ctx, r, open, high, low, close, 13, |c, i| {
+    let crow = |j: usize| !c.white(j) && c.lower_shadow(j) < c.avg(&SHADOW_VERY_SHORT, j);
+    let decline = |j: usize| {
+      c.open(j) < c.open(j - 1) && c.open(j) > c.close(j - 1) && c.close(j) < c.close(j - 1)
+    };
+    if c.white(i - 3)
+      && crow(i - 2)
+      && crow(i - 1)
+      && crow(i)
+      && decline(i - 1)
+      && decline(i)
+      && c.high(i - 3) > c.close(i - 2)
+    {
+      -100
+    } else {
+      0
+    }
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::algo::{assert_vec_eq_nan, context::FLAG_SKIP_NAN};
+
+  type Pattern = fn(&Context, &mut [f64], &[f64], &[f64], &[f64], &[f64]) -> Result<(), Error>;
+
+  /// 12 white candles with body 1 and range 2, then `bars`
+  fn fixture(bars: &[[f64; 4]]) -> [Vec<f64>; 4] {
+    let all: Vec<[f64; 4]> = std::iter::repeat_n([10.0, 11.5, 9.5, 11.0], 12)
+      .chain(bars.iter().copied())
+      .collect();
+    std::array::from_fn(|k| all.iter().map(|b| b[k]).collect())
+  }
+
+  fn last(f: Pattern, bars: &[[f64; 4]]) -> f64 {
+    let [o, h, l, c] = fixture(bars);
+    let mut r = vec![0.0; o.len()];
+    f(&Context::new(0, 1, 0), &mut r, &o, &h, &l, &c).unwrap();
+    r[r.len() - 1]
+  }
+
+  #[test]
+  fn test_single_candle() {
+    assert_eq!(last(ta_cdldoji, &[[10.0, 11.0, 9.0, 10.05]]), 100.0);
+    assert_eq!(last(ta_cdldoji, &[[10.0, 11.0, 9.0, 10.5]]), 0.0);
+    assert_eq!(
+      last(ta_cdldragonflydoji, &[[11.0, 11.02, 9.5, 11.01]]),
+      100.0
+    );
+    assert_eq!(
+      last(ta_cdlgravestonedoji, &[[9.5, 11.0, 9.49, 9.51]]),
+      100.0
+    );
+    assert_eq!(last(ta_cdlmarubozu, &[[10.0, 12.0, 10.0, 12.0]]), 100.0);
+    assert_eq!(last(ta_cdlmarubozu, &[[12.0, 12.0, 10.0, 10.0]]), -100.0);
+
+    assert_eq!(last(ta_cdlhammer, &[[9.6, 9.85, 9.0, 9.8]]), 100.0);
+    assert_eq!(last(ta_cdlhangingman, &[[11.6, 11.85, 11.0, 11.8]]), -100.0);
+    assert_eq!(
+      last(ta_cdlshootingstar, &[[11.2, 12.0, 11.15, 11.4]]),
+      -100.0
+    );
+    assert_eq!(last(ta_cdlinvertedhammer, &[[9.4, 10.2, 9.35, 9.6]]), 100.0);
+    // the hammer shape is not near the previous low
+    assert_eq!(last(ta_cdlhammer, &[[11.6, 11.85, 11.0, 11.8]]), 0.0);
+  }
+
+  #[test]
+  fn test_multi_candle() {
+    assert_eq!(last(ta_cdlengulfing, &[[11.5, 11.6, 9.4, 9.5]]), -100.0);
+    let bars = [[11.0, 11.2, 9.9, 10.0], [9.8, 11.5, 9.7, 11.3]];
+    assert_eq!(last(ta_cdlengulfing, &bars), 100.0);
+
+    // the open touches the previous close
+    let bars = [[11.0, 11.2, 9.9, 10.0], [10.0, 11.5, 9.7, 11.3]];
+    assert_eq!(last(ta_cdlengulfing, &bars), 80.0);
+
+    let bars = [[12.0, 12.1, 9.9, 10.0], [10.8, 11.2, 10.7, 11.1]];
+    assert_eq!(last(ta_cdlharami, &bars), 100.0);
+    // the bottom touches the previous bottom
+    let bars = [[12.0, 12.1, 9.9, 10.0], [10.0, 10.4, 9.95, 10.3]];
+    assert_eq!(last(ta_cdlharami, &bars), 80.0);
+
+    let ctx = Context::new(0, 1, 0);
+    let [o, h, l, c] = fixture(&[
+      [12.0, 12.1, 9.9, 10.0],
+      [9.6, 9.8, 9.3, 9.5],
+      [9.8, 11.4, 9.7, 11.3],
+    ]);
+    let mut r = vec![0.0; o.len()];
+    ta_cdlmorningstar(&ctx, &mut r, &o, &h, &l, &c, 0.3).unwrap();
+    assert_eq!(r[14], 100.0);
+    assert_eq!(r[13], 0.0);
+
+    let [o, h, l, c] = fixture(&[
+      [10.0, 12.1, 9.9, 12.0],
+      [12.3, 12.6, 12.2, 12.4],
+      [12.2, 12.3, 10.6, 10.7],
+    ]);
+    ta_cdleveningstar(&ctx, &mut r, &o, &h, &l, &c, 0.3).unwrap();
+    assert_eq!(r[14], -100.0);
+
+    let bars = [
+      [10.0, 11.05, 9.9, 11.0],
+      [10.5, 12.05, 10.4, 12.0],
+      [11.5, 13.05, 11.4, 13.0],
+    ];
+    assert_eq!(last(ta_cdl3whitesoldiers, &bars), 100.0);
+    let bars = [
+      [11.2, 11.25, 10.45, 10.5],
+      [10.8, 10.85, 9.75, 9.8],
+      [10.0, 10.05, 8.95, 9.0],
+    ];
+    assert_eq!(last(ta_cdl3blackcrows, &bars), -100.0);
+  }
+
+  #[test]
+  fn test_candle_nan() {
+    let [mut o, h, l, c] = fixture(&[[10.0, 11.0, 9.0, 10.05]]);
+    let mut r = vec![0.0; o.len()];
+
+    // lookback of 10 bars
+    let ctx = Context::new(0, 1, 0);
+    ta_cdldoji(&ctx, &mut r, &o, &h, &l, &c).unwrap();
+    assert!(r[9].is_nan());
+    assert_vec_eq_nan(&r[10..], &[0.0, 0.0, 100.0]);
+
+    // a missing bar inside the lookback
+    o[5] = f64::NAN;
+    ta_cdldoji(&ctx, &mut r, &o, &h, &l, &c).unwrap();
+    assert!(r.iter().all(|v| v.is_nan()));
+    let ctx = Context::new(0, 1, FLAG_SKIP_NAN);
+    ta_cdldoji(&ctx, &mut r, &o, &h, &l, &c).unwrap();
+    assert_vec_eq_nan(&r[10..], &[f64::NAN, 0.0, 100.0]);
+  }
+}
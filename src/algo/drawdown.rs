@@ -50,67 +50,19 @@ pub fn ta_max_drawdown<NumT: Float + Send + Sync>(
             continue;
           }
 
-          // Find first valid value as initial peak
-          let mut j = win_start;
-          while j <= i && !is_normal(&x[j]) {
-            j += 1;
-          }
-          if j > i {
-            continue;
-          }
-
-          let mut peak = x[j];
-          let mut mdd = NumT::zero();
-
-          for k in j..=i {
-            let val = x[k];
-            if !is_normal(&val) {
-              continue;
-            }
-            if val > peak {
-              peak = val;
-            }
-            let dd = (val - peak) / peak;
-            if dd < mdd {
-              mdd = dd;
-            }
-          }
-
-          r[i] = mdd;
+          r[i] = max_drawdown_of(x[win_start..=i].iter().copied().filter(is_normal));
         } else {
           if !is_normal(&x[i]) {
             continue;
           }
 
-          // Scan window; any NaN invalidates the whole window
-          let mut has_nan = false;
-          let mut peak = NumT::zero();
-          let mut peak_set = false;
-          let mut mdd = NumT::zero();
-
-          for k in win_start..=i {
-            let val = x[k];
-            if !is_normal(&val) {
-              has_nan = true;
-              break;
-            }
-            if !peak_set {
-              peak = val;
-              peak_set = true;
-            } else if val > peak {
-              peak = val;
-            }
-            let dd = (val - peak) / peak;
-            if dd < mdd {
-              mdd = dd;
-            }
-          }
-
-          if has_nan {
+          // Any NaN invalidates the whole window
+          let window = &x[win_start..=i];
+          if !window.iter().all(is_normal) {
             continue;
           }
 
-          r[i] = mdd;
+          r[i] = max_drawdown_of(window.iter().copied());
         }
       }
     });
@@ -118,6 +70,25 @@ pub fn ta_max_drawdown<NumT: Float + Send + Sync>(
   Ok(())
 }
 
+/// Maximum drawdown of an equity `curve`, the first value is the initial peak
+pub(crate) fn max_drawdown_of<NumT: Float>(curve: impl IntoIterator<Item = NumT>) -> NumT {
+  let mut curve = curve.into_iter();
+  let Some(mut peak) = curve.next() else {
+    return NumT::zero();
+  };
+  let mut mdd = NumT::zero();
+  for val in curve {
+    if val > peak {
+      peak = val;
+    }
+    let dd = (val - peak) / peak;
+    if dd < mdd {
+      mdd = dd;
+    }
+  }
+  mdd
+}
+
 #[cfg(test)]
 mod tests {
   use crate::algo::{
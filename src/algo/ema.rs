@@ -3,9 +3,79 @@
 
 use num_traits::Float;
 
-use super::{Context, Error, is_normal, skip_nan_window::SkipNanWindow};
+use super::{
+  Context, Error,
+  ewm::{EWM_ADJUST, Ewm},
+  is_normal,
+  skip_nan_window::SkipNanWindow,
+};
 use rayon::prelude::*;
 
+/// Running EMA state, value = alpha * x + (1 - alpha) * value
+///
+/// Seeded either with the first value (`EMA`, `DMA`) or with the simple mean
+/// of the first `periods` values as TA-Lib.
+pub(crate) struct Ema<NumT> {
+  alpha: NumT,
+  /// number of values averaged for the seed, 0 seeds with the first value
+  seed: usize,
+  count: usize,
+  value: NumT,
+}
+
+impl<NumT: Float> Ema<NumT> {
+  /// EMA with weight `alpha` seeded with the first value
+  pub(crate) fn new(alpha: NumT) -> Self {
+    Self {
+      alpha,
+      seed: 0,
+      count: 0,
+      value: NumT::zero(),
+    }
+  }
+
+  /// EMA with alpha = 2 / (periods + 1) seeded with the first value
+  pub(crate) fn with_periods(periods: usize) -> Self {
+    Self::new(NumT::from(2.0).unwrap() / NumT::from(periods + 1).unwrap())
+  }
+
+  /// EMA with alpha = 2 / (periods + 1) seeded with the mean of the first
+  /// `periods` values, None until then
+  pub(crate) fn sma_seeded(periods: usize) -> Self {
+    Self {
+      seed: periods,
+      ..Self::with_periods(periods)
+    }
+  }
+
+  /// Number of values seen
+  pub(crate) fn count(&self) -> usize {
+    self.count
+  }
+
+  /// Restart from `x` without counting it as an EMA value
+  pub(crate) fn reseed(&mut self, x: NumT) {
+    self.value = x;
+    self.count = self.count.max(1);
+  }
+
+  pub(crate) fn update(&mut self, x: NumT) -> Option<NumT> {
+    self.count += 1;
+    if self.count <= self.seed {
+      self.value = self.value + x;
+      if self.count < self.seed {
+        return None;
+      }
+      self.value = self.value / NumT::from(self.seed).unwrap();
+    } else if self.count == 1 {
+      self.value = x;
+    } else {
+      self.value = self.alpha * x + (NumT::one() - self.alpha) * self.value;
+    }
+    Some(self.value)
+  }
+}
+
 /// Exponential Moving Average (variant of well-known EMA) weight = 2 / (n + 1)
 ///
 /// Ref: https://en.wikipedia.org/wiki/Moving_average#Exponential_moving_average
@@ -50,6 +120,94 @@ pub fn ta_dma<NumT: Float + Send + Sync>(
   ema_impl(ctx, r, input, weight, 0)
 }
 
+/// Exponential Moving Average, pandas compatible, weight = 2 / (n + 1)
+///
+/// Same as `ewm(span=n, adjust).mean()` of pandas, `flags` takes EWM_ADJUST,
+/// FLAG_SKIP_NAN of context means ignore_na=True. Seeded from the first valid
+/// value, NaN input carries the previous value forward. With
+/// FLAG_STRICTLY_CYCLE the first n - 1 observations are NaN.
+///
+pub fn ta_ema_ex<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+  periods: usize,
+  flags: usize,
+) -> Result<(), Error> {
+  let alpha = NumT::from(2.0).unwrap() / NumT::from(periods + 1).unwrap();
+  ewm_mean_impl(ctx, r, input, alpha, flags, periods)
+}
+
+/// Exponential Moving Average, pandas compatible, weight = m / n
+///
+/// Same options as `EMA_EX`.
+///
+pub fn ta_sma_ex<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+  n: usize,
+  m: usize,
+  flags: usize,
+) -> Result<(), Error> {
+  let alpha = NumT::from(m).unwrap() / NumT::from(n).unwrap();
+  ta_dma_ex(ctx, r, input, alpha, flags)
+}
+
+/// Exponential Moving Average, pandas compatible, given weight
+///
+/// Same as `ewm(alpha=weight, adjust).mean()` of pandas, same options as `EMA_EX`.
+///
+pub fn ta_dma_ex<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+  weight: NumT,
+  flags: usize,
+) -> Result<(), Error> {
+  ewm_mean_impl(ctx, r, input, weight, flags, 0)
+}
+
+fn ewm_mean_impl<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+  alpha: NumT,
+  flags: usize,
+  periods: usize,
+) -> Result<(), Error> {
+  if r.len() != input.len() {
+    return Err(Error::LengthMismatch(r.len(), input.len()));
+  }
+
+  if !(alpha > NumT::zero() && alpha <= NumT::one()) {
+    return Err(Error::InvalidParameter(
+      "alpha must be between 0 and 1".to_string(),
+    ));
+  }
+
+  let adjust = flags & EWM_ADJUST != 0;
+  let min_periods = if ctx.is_strictly_cycle() { periods } else { 1 };
+
+  r.par_chunks_mut(ctx.chunk_size(r.len()))
+    .zip(input.par_chunks(ctx.chunk_size(input.len())))
+    .for_each(|(r, x)| {
+      let start = ctx.start(r.len());
+      let end = ctx.end(r.len());
+      r.fill(NumT::nan());
+
+      let mut ewm = Ewm::new(alpha, adjust, ctx.is_skip_nan());
+      for i in start..end {
+        ewm.update(x[i], x[i]);
+        if ewm.count() >= min_periods {
+          r[i] = ewm.mean();
+        }
+      }
+    });
+
+  Ok(())
+}
+
 /// Linear Weighted Moving Average
 ///
 /// LWMA = SUM(Price * Weight) / SUM(Weight)
@@ -226,29 +384,24 @@ pub fn ema_impl<NumT: Float + Send + Sync>(
     ));
   }
 
-  let k = NumT::one() - weight;
-
   r.par_chunks_mut(ctx.chunk_size(r.len()))
     .zip(input.par_chunks(ctx.chunk_size(input.len())))
     .for_each(|(r, i)| {
-      let mut prev = i[0];
-      let total = r.len();
-      for (n, (r, c)) in r
-        .iter_mut()
-        .zip(i.iter())
-        .enumerate()
-        .skip(ctx.start(total))
-      {
-        if ctx.is_skip_nan() && !is_normal(c) {
-          *r = NumT::nan();
-          continue;
-        }
-        if ctx.is_strictly_cycle() && n < periods - 1 {
-          *r = NumT::nan();
-          prev = *c;
+      // seed from the first valid value, leading NaN never poisons the state,
+      // the state runs from the first bar, outputs are written from `start`
+      let mut ema = Ema::new(weight);
+      let start = ctx.start(r.len());
+      for (n, (r, c)) in r.iter_mut().zip(i.iter()).enumerate() {
+        let value = if !is_normal(c) && (ctx.is_skip_nan() || ema.count() == 0) {
+          NumT::nan()
+        } else if ctx.is_strictly_cycle() && n + 1 < periods {
+          ema.reseed(*c);
+          NumT::nan()
         } else {
-          *r = weight * *c + k * prev;
-          prev = *r;
+          ema.update(*c).unwrap()
+        };
+        if n >= start {
+          *r = value;
         }
       }
     });
@@ -264,6 +417,23 @@ mod tests {
 
   use super::*;
 
+  #[test]
+  fn test_ta_ema_start() {
+    let input = [1.0, 2.0, 3.0, 4.0];
+    let mut full = vec![0.0; input.len()];
+    ta_ema(&Context::new(0, 0, 0), &mut full, &input, 3).unwrap();
+    assert_vec_eq_nan(&full, &[1.0, 1.5, 2.25, 3.125]);
+
+    // bars before start still feed the state
+    let mut r = vec![0.0; input.len()];
+    ta_ema(&Context::new(2, 0, 0), &mut r, &input, 3).unwrap();
+    assert_vec_eq_nan(&r, &[0.0, 0.0, 2.25, 3.125]);
+
+    let mut r = vec![0.0; input.len()];
+    ta_dma(&Context::new(2, 0, 0), &mut r, &input, 0.5).unwrap();
+    assert_vec_eq_nan(&r, &[0.0, 0.0, 2.25, 3.125]);
+  }
+
   #[test]
   fn test_ta_ema() {
     let input = vec![1.0, 2.0, 3.0, 4.0, 5.0];
@@ -298,6 +468,59 @@ mod tests {
     assert_vec_eq_nan(&r, &vec![f64::NAN, f64::NAN, 2.5, 3.25, 4.125]);
   }
 
+  #[test]
+  fn test_ta_ema_leading_nan() {
+    let input = vec![f64::NAN, 1.0, 2.0, 3.0];
+    let mut r = vec![0.0; input.len()];
+    let ctx = Context::new(0, 0, 0);
+    ta_ema(&ctx, &mut r, &input, 3).unwrap();
+    assert_vec_eq_nan(&r, &[f64::NAN, 1.0, 1.5, 2.25]);
+
+    let mut r = vec![0.0; input.len()];
+    ta_dma(&ctx, &mut r, &input, 0.5).unwrap();
+    assert_vec_eq_nan(&r, &[f64::NAN, 1.0, 1.5, 2.25]);
+  }
+
+  #[test]
+  fn test_ta_ema_ex() {
+    // pandas: Series([1, 2, 3]).ewm(span=3, adjust=True).mean()
+    let input = vec![1.0, 2.0, 3.0];
+    let mut r = vec![0.0; input.len()];
+    let ctx = Context::new(0, 0, 0);
+    ta_ema_ex(&ctx, &mut r, &input, 3, EWM_ADJUST).unwrap();
+    assert_vec_eq_nan(&r, &[1.0, 2.5 / 1.5, 4.25 / 1.75]);
+
+    // adjust=False matches the recursive form, leading NaN skipped
+    let input = vec![f64::NAN, 1.0, 2.0, 3.0];
+    let mut r = vec![0.0; input.len()];
+    ta_sma_ex(&ctx, &mut r, &input, 2, 1, 0).unwrap();
+    assert_vec_eq_nan(&r, &[f64::NAN, 1.0, 1.5, 2.25]);
+
+    let ctx = Context::new(0, 0, FLAG_STRICTLY_CYCLE);
+    let mut r = vec![0.0; input.len()];
+    ta_ema_ex(&ctx, &mut r, &input, 2, 0).unwrap();
+    assert!(r[1].is_nan());
+    assert!((r[2] - (2.0 / 3.0 * 2.0 + 1.0 / 3.0)).abs() < 1e-12);
+  }
+
+  #[test]
+  fn test_ta_ema_ex_ignore_na() {
+    let input = vec![1.0, f64::NAN, 3.0];
+    let mut r = vec![0.0; input.len()];
+
+    // ignore_na=False: weight of 1.0 decays over the missing position
+    let ctx = Context::new(0, 0, 0);
+    ta_dma_ex(&ctx, &mut r, &input, 0.5, EWM_ADJUST).unwrap();
+    assert_vec_eq_nan(&r, &[1.0, 1.0, 3.25 / 1.25]);
+
+    // ignore_na=True: missing positions are dropped
+    let ctx = Context::new(0, 0, FLAG_SKIP_NAN);
+    ta_dma_ex(&ctx, &mut r, &input, 0.5, EWM_ADJUST).unwrap();
+    assert_vec_eq_nan(&r, &[1.0, 1.0, 3.5 / 1.5]);
+
+    assert!(ta_dma_ex(&ctx, &mut r, &input, 0.0, 0).is_err());
+  }
+
   #[test]
   fn test_ta_lwma() {
     let input = vec![1.0, 2.0, 3.0, 4.0, 5.0];
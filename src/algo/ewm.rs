@@ -0,0 +1,481 @@
+// Copyright 2026 MSD-RS Project LiJia
+// SPDX-License-Identifier: BSD-2-Clause
+
+use num_traits::Float;
+use rayon::prelude::*;
+
+use crate::algo::{Context, Error, is_normal};
+
+/// ewm flag: `decay` is the smoothing factor alpha, 0 < alpha <= 1
+pub const EWM_ALPHA: usize = 0;
+/// ewm flag: `decay` is the span, alpha = 2 / (span + 1), span >= 1
+pub const EWM_SPAN: usize = 1;
+/// ewm flag: `decay` is the halflife, alpha = 1 - exp(-ln(2) / halflife), halflife > 0
+pub const EWM_HALFLIFE: usize = 2;
+/// ewm flag: `decay` is the center of mass, alpha = 1 / (1 + com), com >= 0
+pub const EWM_COM: usize = 3;
+/// ewm flag: divide by the decaying sum of weights (pandas adjust=True)
+pub const EWM_ADJUST: usize = 4;
+/// ewm flag: biased statistics without bias correction (pandas bias=True)
+pub const EWM_BIAS: usize = 8;
+
+const EWM_DECAY_MASK: usize = 3;
+
+/// Smoothing factor alpha from `decay` interpreted by `flags`
+pub(crate) fn ewm_alpha<NumT: Float>(decay: NumT, flags: usize) -> Result<NumT, Error> {
+  let alpha = match flags & EWM_DECAY_MASK {
+    EWM_SPAN if decay >= NumT::one() => NumT::from(2).unwrap() / (decay + NumT::one()),
+    EWM_HALFLIFE if decay > NumT::zero() => {
+      NumT::one() - (-NumT::from(2).unwrap().ln() / decay).exp()
+    }
+    EWM_COM if decay >= NumT::zero() => NumT::one() / (NumT::one() + decay),
+    EWM_ALPHA if decay > NumT::zero() && decay <= NumT::one() => decay,
+    _ => {
+      return Err(Error::InvalidParameter(format!(
+        "invalid ewm decay: {} for flags {}",
+        decay.to_f64().unwrap_or(f64::NAN),
+        flags
+      )));
+    }
+  };
+  Ok(alpha)
+}
+
+/// Exponentially weighted mean and covariance of one or two series
+///
+/// Same recursion as pandas `ewmcov`, the mean of x is pandas `ewma`.
+pub(crate) struct Ewm<NumT> {
+  old_wt_factor: NumT,
+  new_wt: NumT,
+  adjust: bool,
+  ignore_na: bool,
+  mean_x: NumT,
+  mean_y: NumT,
+  cov: NumT,
+  sum_wt: NumT,
+  sum_wt2: NumT,
+  old_wt: NumT,
+  nobs: usize,
+}
+
+impl<NumT: Float> Ewm<NumT> {
+  pub fn new(alpha: NumT, adjust: bool, ignore_na: bool) -> Self {
+    Self {
+      old_wt_factor: NumT::one() - alpha,
+      new_wt: if adjust { NumT::one() } else { alpha },
+      adjust,
+      ignore_na,
+      mean_x: NumT::nan(),
+      mean_y: NumT::nan(),
+      cov: NumT::zero(),
+      sum_wt: NumT::one(),
+      sum_wt2: NumT::one(),
+      old_wt: NumT::one(),
+      nobs: 0,
+    }
+  }
+
+  /// Add the next pair, NaN in either value is a missing observation
+  pub fn update(&mut self, x: NumT, y: NumT) {
+    let is_observation = is_normal(&x) && is_normal(&y);
+    if self.nobs == 0 {
+      if is_observation {
+        self.mean_x = x;
+        self.mean_y = y;
+        self.nobs = 1;
+      }
+      return;
+    }
+
+    if !is_observation && self.ignore_na {
+      return;
+    }
+    let f = self.old_wt_factor;
+    self.sum_wt = self.sum_wt * f;
+    self.sum_wt2 = self.sum_wt2 * f * f;
+    self.old_wt = self.old_wt * f;
+    if !is_observation {
+      return;
+    }
+
+    self.nobs += 1;
+    let old_mean_x = self.mean_x;
+    let old_mean_y = self.mean_y;
+    let total = self.old_wt + self.new_wt;
+    // keep constant series exact
+    if self.mean_x != x {
+      self.mean_x = (self.old_wt * old_mean_x + self.new_wt * x) / total;
+    }
+    if self.mean_y != y {
+      self.mean_y = (self.old_wt * old_mean_y + self.new_wt * y) / total;
+    }
+    self.cov = (self.old_wt * (self.cov + (old_mean_x - self.mean_x) * (old_mean_y - self.mean_y))
+      + self.new_wt * (x - self.mean_x) * (y - self.mean_y))
+      / total;
+    self.sum_wt = self.sum_wt + self.new_wt;
+    self.sum_wt2 = self.sum_wt2 + self.new_wt * self.new_wt;
+    self.old_wt = self.old_wt + self.new_wt;
+    if !self.adjust {
+      self.sum_wt = self.sum_wt / self.old_wt;
+      self.sum_wt2 = self.sum_wt2 / (self.old_wt * self.old_wt);
+      self.old_wt = NumT::one();
+    }
+  }
+
+  /// Number of observations so far
+  pub fn count(&self) -> usize {
+    self.nobs
+  }
+
+  /// Mean of x, NaN before the first observation
+  pub fn mean(&self) -> NumT {
+    self.mean_x
+  }
+
+  /// Covariance, bias corrected unless `bias`, NaN before the first observation
+  pub fn cov(&self, bias: bool) -> NumT {
+    if self.nobs == 0 {
+      return NumT::nan();
+    }
+    if bias {
+      return self.cov;
+    }
+    let numerator = self.sum_wt * self.sum_wt;
+    let denominator = numerator - self.sum_wt2;
+    if denominator > NumT::zero() {
+      numerator / denominator * self.cov
+    } else {
+      NumT::nan()
+    }
+  }
+}
+
+/// Run `f` over the exponentially weighted state of each group
+///
+/// `ctx.is_skip_nan()` skips missing observations when decaying (pandas
+/// ignore_na=True), otherwise weights are based on absolute positions.
+fn ewm_run<NumT, F>(
+  ctx: &Context,
+  r: &mut [NumT],
+  x: &[NumT],
+  y: &[NumT],
+  decay: NumT,
+  flags: usize,
+  f: F,
+) -> Result<(), Error>
+where
+  NumT: Float + Send + Sync,
+  F: Fn(&Ewm<NumT>, NumT) -> NumT + Send + Sync,
+{
+  if r.len() != x.len() || r.len() != y.len() {
+    return Err(Error::LengthMismatch(r.len(), x.len()));
+  }
+  let alpha = ewm_alpha(decay, flags)?;
+
+  let chunk = ctx.chunk_size(r.len());
+  r.par_chunks_mut(chunk)
+    .zip(x.par_chunks(chunk))
+    .zip(y.par_chunks(chunk))
+    .for_each(|((r, x), y)| {
+      let start = ctx.start(r.len());
+      let end = ctx.end(r.len());
+      r.fill(NumT::nan());
+
+      let mut ewm = Ewm::new(alpha, flags & EWM_ADJUST != 0, ctx.is_skip_nan());
+      for i in start..end {
+        ewm.update(x[i], y[i]);
+        r[i] = f(&ewm, x[i]);
+      }
+    });
+
+  Ok(())
+}
+
+/// Exponentially Weighted Variance
+///
+/// `decay` is interpreted by `flags`: EWM_ALPHA (0), EWM_SPAN (1),
+/// EWM_HALFLIFE (2) or EWM_COM (3), combined with EWM_ADJUST (4) and
+/// EWM_BIAS (8), matches pandas `ewm(..., adjust, ignore_na).var(bias)`,
+/// FLAG_SKIP_NAN of context means ignore_na=True.
+pub fn ta_ewm_var<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+  decay: NumT,
+  flags: usize,
+) -> Result<(), Error> {
+  let bias = flags & EWM_BIAS != 0;
+  ewm_run(ctx, r, input, input, decay, flags, |ewm, _| ewm.cov(bias))
+}
+
+/// Exponentially Weighted Standard Deviation
+///
+/// Square root of `EWM_VAR`, same parameters.
+pub fn ta_ewm_std<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+  decay: NumT,
+  flags: usize,
+) -> Result<(), Error> {
+  let bias = flags & EWM_BIAS != 0;
+  ewm_run(ctx, r, input, input, decay, flags, |ewm, _| {
+    ewm.cov(bias).sqrt()
+  })
+}
+
+/// Exponentially Weighted Covariance
+///
+/// Only pairs with both values valid are observations, same parameters as `EWM_VAR`.
+pub fn ta_ewm_cov<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  x: &[NumT],
+  y: &[NumT],
+  decay: NumT,
+  flags: usize,
+) -> Result<(), Error> {
+  let bias = flags & EWM_BIAS != 0;
+  ewm_run(ctx, r, x, y, decay, flags, |ewm, _| ewm.cov(bias))
+}
+
+/// Exponentially Weighted Correlation
+///
+/// cov(x, y) / sqrt(var(x) * var(y)), the bias flag is ignored.
+pub fn ta_ewm_corr<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  x: &[NumT],
+  y: &[NumT],
+  decay: NumT,
+  flags: usize,
+) -> Result<(), Error> {
+  if r.len() != x.len() || r.len() != y.len() {
+    return Err(Error::LengthMismatch(r.len(), x.len()));
+  }
+  let alpha = ewm_alpha(decay, flags)?;
+  let adjust = flags & EWM_ADJUST != 0;
+
+  let chunk = ctx.chunk_size(r.len());
+  r.par_chunks_mut(chunk)
+    .zip(x.par_chunks(chunk))
+    .zip(y.par_chunks(chunk))
+    .for_each(|((r, x), y)| {
+      let start = ctx.start(r.len());
+      let end = ctx.end(r.len());
+      r.fill(NumT::nan());
+
+      let mut cov = Ewm::new(alpha, adjust, ctx.is_skip_nan());
+      let mut var_x = Ewm::new(alpha, adjust, ctx.is_skip_nan());
+      let mut var_y = Ewm::new(alpha, adjust, ctx.is_skip_nan());
+      for i in start..end {
+        // variances only see the pairs where both values are valid
+        let valid = is_normal(&x[i]) && is_normal(&y[i]);
+        let (a, b) = if valid {
+          (x[i], y[i])
+        } else {
+          (NumT::nan(), NumT::nan())
+        };
+        cov.update(a, b);
+        var_x.update(a, a);
+        var_y.update(b, b);
+        let denom = (var_x.cov(true) * var_y.cov(true)).sqrt();
+        if denom > NumT::zero() {
+          r[i] = cov.cov(true) / denom;
+        }
+      }
+    });
+
+  Ok(())
+}
+
+/// Exponentially Weighted Beta
+///
+/// cov(input, benchmark) / var(benchmark) over pairs with both values valid,
+/// the bias flag is ignored.
+pub fn ta_ewm_beta<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+  benchmark: &[NumT],
+  decay: NumT,
+  flags: usize,
+) -> Result<(), Error> {
+  if r.len() != input.len() || r.len() != benchmark.len() {
+    return Err(Error::LengthMismatch(r.len(), input.len()));
+  }
+  let alpha = ewm_alpha(decay, flags)?;
+  let adjust = flags & EWM_ADJUST != 0;
+
+  let chunk = ctx.chunk_size(r.len());
+  r.par_chunks_mut(chunk)
+    .zip(input.par_chunks(chunk))
+    .zip(benchmark.par_chunks(chunk))
+    .for_each(|((r, x), y)| {
+      let start = ctx.start(r.len());
+      let end = ctx.end(r.len());
+      r.fill(NumT::nan());
+
+      let mut cov = Ewm::new(alpha, adjust, ctx.is_skip_nan());
+      let mut var = Ewm::new(alpha, adjust, ctx.is_skip_nan());
+      for i in start..end {
+        let b = if is_normal(&x[i]) { y[i] } else { NumT::nan() };
+        cov.update(x[i], y[i]);
+        var.update(b, b);
+        let v = var.cov(true);
+        if v > NumT::zero() {
+          r[i] = cov.cov(true) / v;
+        }
+      }
+    });
+
+  Ok(())
+}
+
+/// Exponentially Weighted Z-Score
+///
+/// (x - ewm mean) / ewm std, same parameters as `EWM_VAR`, NaN input gives NaN.
+pub fn ta_ewm_zscore<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+  decay: NumT,
+  flags: usize,
+) -> Result<(), Error> {
+  let bias = flags & EWM_BIAS != 0;
+  ewm_run(ctx, r, input, input, decay, flags, |ewm, x| {
+    let std = ewm.cov(bias).sqrt();
+    if is_normal(&x) && std > NumT::zero() {
+      (x - ewm.mean()) / std
+    } else {
+      NumT::nan()
+    }
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::algo::{assert_vec_eq_nan, context::FLAG_SKIP_NAN};
+
+  /// adjusted weighted variance by definition, weights by position
+  fn adjusted_var(x: &[f64], alpha: f64, bias: bool) -> f64 {
+    let n = x.len();
+    let w: Vec<f64> = (0..n)
+      .map(|i| (1.0 - alpha).powi((n - 1 - i) as i32))
+      .collect();
+    let sw: f64 = w.iter().sum();
+    let sw2: f64 = w.iter().map(|w| w * w).sum();
+    let mean = x.iter().zip(&w).map(|(x, w)| x * w).sum::<f64>() / sw;
+    let var = x
+      .iter()
+      .zip(&w)
+      .map(|(x, w)| w * (x - mean).powi(2))
+      .sum::<f64>()
+      / sw;
+    if bias {
+      var
+    } else {
+      var * sw * sw / (sw * sw - sw2)
+    }
+  }
+
+  #[test]
+  fn test_ewm_alpha() {
+    assert_eq!(ewm_alpha(0.5, EWM_ALPHA).unwrap(), 0.5);
+    assert_eq!(ewm_alpha(3.0, EWM_SPAN).unwrap(), 0.5);
+    assert_eq!(ewm_alpha(1.0, EWM_COM).unwrap(), 0.5);
+    assert!((ewm_alpha(1.0, EWM_HALFLIFE).unwrap() - 0.5f64).abs() < 1e-12);
+    assert!(ewm_alpha(0.0, EWM_ALPHA).is_err());
+    assert!(ewm_alpha(0.5, EWM_SPAN).is_err());
+  }
+
+  #[test]
+  fn test_ewm_var_adjust() {
+    let ctx = Context::new(0, 1, 0);
+    let input = [1.0, 2.0, 4.0, 3.0];
+    let mut r = [0.0; 4];
+    ta_ewm_var(&ctx, &mut r, &input, 0.5, EWM_ADJUST).unwrap();
+    let expected: Vec<f64> = (1..=4)
+      .map(|n| adjusted_var(&input[..n], 0.5, false))
+      .collect();
+    assert!(r[0].is_nan());
+    assert_vec_eq_nan(&r[1..], &expected[1..]);
+
+    ta_ewm_var(&ctx, &mut r, &input, 3.0, EWM_SPAN | EWM_ADJUST | EWM_BIAS).unwrap();
+    let expected: Vec<f64> = (1..=4)
+      .map(|n| adjusted_var(&input[..n], 0.5, true))
+      .collect();
+    assert_vec_eq_nan(&r, &expected);
+
+    ta_ewm_std(&ctx, &mut r, &input, 0.5, EWM_ADJUST | EWM_BIAS).unwrap();
+    let expected: Vec<f64> = expected.iter().map(|v| v.sqrt()).collect();
+    assert_vec_eq_nan(&r, &expected);
+  }
+
+  #[test]
+  fn test_ewm_var_not_adjust() {
+    // adjust=False: mean_t = 0.5 * x_t + 0.5 * mean_t-1
+    let ctx = Context::new(0, 1, 0);
+    let input = [1.0, 3.0];
+    let mut r = [0.0; 2];
+    ta_ewm_var(&ctx, &mut r, &input, 0.5, EWM_BIAS).unwrap();
+    // weights 0.5, 0.5 -> mean 2, biased var 1
+    assert_vec_eq_nan(&r, &[0.0, 1.0]);
+    ta_ewm_var(&ctx, &mut r, &input, 0.5, 0).unwrap();
+    // sum_wt = 1, sum_wt2 = 0.5 -> var * 1 / (1 - 0.5)
+    assert_vec_eq_nan(&r, &[f64::NAN, 2.0]);
+  }
+
+  #[test]
+  fn test_ewm_ignore_na() {
+    let input = [1.0, f64::NAN, 3.0];
+    let mut r = [0.0; 3];
+
+    // positional weights: x0 decays twice, (0.25 * 1 + 3) / 1.25
+    ta_ewm_zscore(
+      &Context::new(0, 1, 0),
+      &mut r,
+      &input,
+      0.5,
+      EWM_ADJUST | EWM_BIAS,
+    )
+    .unwrap();
+    let mean = (0.25 + 3.0) / 1.25;
+    let var = (0.25 * (1.0 - mean).powi(2) + (3.0 - mean).powi(2)) / 1.25;
+    assert_vec_eq_nan(&r, &[f64::NAN, f64::NAN, (3.0 - mean) / var.sqrt()]);
+
+    // ignore_na: x0 decays once, (0.5 * 1 + 3) / 1.5
+    ta_ewm_zscore(
+      &Context::new(0, 1, FLAG_SKIP_NAN),
+      &mut r,
+      &input,
+      0.5,
+      EWM_ADJUST | EWM_BIAS,
+    )
+    .unwrap();
+    let mean = (0.5 + 3.0) / 1.5;
+    let var = (0.5 * (1.0 - mean).powi(2) + (3.0 - mean).powi(2)) / 1.5;
+    assert_vec_eq_nan(&r, &[f64::NAN, f64::NAN, (3.0 - mean) / var.sqrt()]);
+  }
+
+  #[test]
+  fn test_ewm_cov_corr_beta() {
+    let ctx = Context::new(0, 1, 0);
+    let x = [1.0, 2.0, 4.0, 3.0];
+    let y = [2.0, 4.0, 8.0, 6.0];
+    let mut r = [0.0; 4];
+
+    ta_ewm_cov(&ctx, &mut r, &x, &y, 0.5, EWM_ADJUST).unwrap();
+    let expected: Vec<f64> = (1..=4)
+      .map(|n| 2.0 * adjusted_var(&x[..n], 0.5, false))
+      .collect();
+    assert_vec_eq_nan(&r[1..], &expected[1..]);
+
+    ta_ewm_corr(&ctx, &mut r, &x, &y, 0.5, EWM_ADJUST).unwrap();
+    assert_vec_eq_nan(&r, &[f64::NAN, 1.0, 1.0, 1.0]);
+
+    ta_ewm_beta(&ctx, &mut r, &y, &x, 0.5, EWM_ADJUST).unwrap();
+    assert_vec_eq_nan(&r, &[f64::NAN, 2.0, 2.0, 2.0]);
+  }
+}
@@ -0,0 +1,362 @@
+// Copyright 2026 MSD-RS Project LiJia
+// SPDX-License-Identifier: BSD-2-Clause
+
+use num_traits::Float;
+use rayon::prelude::*;
+
+use crate::algo::{Context, Error, is_normal};
+
+/// Minimum number of observations for a maximum likelihood fit
+const GARCH_MIN_OBS: usize = 10;
+/// Upper bound of alpha + beta in a fit, keeps the process stationary
+const GARCH_MAX_PERSISTENCE: f64 = 0.9999;
+
+/// GARCH(1,1) parameters, variance = omega + alpha * e^2 + beta * variance
+#[derive(Clone, Copy, Debug)]
+struct Garch<NumT> {
+  omega: NumT,
+  alpha: NumT,
+  beta: NumT,
+}
+
+impl<NumT: Float> Garch<NumT> {
+  fn is_valid(&self) -> bool {
+    self.omega > NumT::zero()
+      && self.alpha >= NumT::zero()
+      && self.beta >= NumT::zero()
+      && self.alpha + self.beta < NumT::one()
+  }
+
+  /// Variance of the next bar after observing `e`, None for a missing value
+  fn next(&self, variance: NumT, e: Option<NumT>) -> NumT {
+    // a missing value adds its expectation, which is the variance itself
+    let e2 = e.map_or(variance, |e| e * e);
+    self.omega + self.alpha * e2 + self.beta * variance
+  }
+
+  /// Gaussian negative log likelihood (without constants) of `e`, started
+  /// from `variance`, and the variance forecast after the last value
+  fn nll(&self, e: &[NumT], mut variance: NumT) -> (NumT, NumT) {
+    let mut sum = NumT::zero();
+    for &x in e {
+      sum = sum + variance.ln() + x * x / variance;
+      variance = self.next(variance, Some(x));
+    }
+    (sum, variance)
+  }
+
+  /// Unconstrained coordinates: ln(omega), logit of persistence, logit of
+  /// the alpha share of persistence
+  fn to_coords(self) -> [NumT; 3] {
+    let max = NumT::from(GARCH_MAX_PERSISTENCE).unwrap();
+    let tiny = NumT::from(1e-4).unwrap();
+    let persistence = (self.alpha + self.beta).max(tiny).min(max - tiny);
+    let share = (self.alpha / (self.alpha + self.beta).max(tiny))
+      .max(tiny)
+      .min(NumT::one() - tiny);
+    [self.omega.ln(), logit(persistence / max), logit(share)]
+  }
+
+  fn from_coords(c: &[NumT; 3]) -> Self {
+    let persistence = NumT::from(GARCH_MAX_PERSISTENCE).unwrap() * sigmoid(c[1]);
+    let alpha = persistence * sigmoid(c[2]);
+    Self {
+      omega: c[0].exp(),
+      alpha,
+      beta: persistence - alpha,
+    }
+  }
+}
+
+fn sigmoid<NumT: Float>(x: NumT) -> NumT {
+  NumT::one() / (NumT::one() + (-x).exp())
+}
+
+fn logit<NumT: Float>(p: NumT) -> NumT {
+  (p / (NumT::one() - p)).ln()
+}
+
+fn sample_var<NumT: Float>(e: &[NumT]) -> NumT {
+  let n = NumT::from(e.len()).unwrap();
+  let mean = e.iter().fold(NumT::zero(), |a, &b| a + b) / n;
+  e.iter()
+    .fold(NumT::zero(), |a, &b| a + (b - mean) * (b - mean))
+    / n
+}
+
+/// Nelder-Mead minimization of `f` starting at `x0`
+fn nelder_mead<NumT: Float, F: Fn(&[NumT; 3]) -> NumT>(f: F, x0: [NumT; 3]) -> [NumT; 3] {
+  let half = NumT::from(0.5).unwrap();
+  let two = NumT::from(2).unwrap();
+  let eval = |x: &[NumT; 3]| {
+    let v = f(x);
+    if v.is_nan() { NumT::infinity() } else { v }
+  };
+
+  let mut simplex: Vec<([NumT; 3], NumT)> = (0..4)
+    .map(|k| {
+      let mut x = x0;
+      if k > 0 {
+        x[k - 1] = x[k - 1] + half;
+      }
+      (x, eval(&x))
+    })
+    .collect();
+
+  for _ in 0..500 {
+    simplex.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+    if (simplex[3].1 - simplex[0].1).abs()
+      <= NumT::from(1e-10).unwrap() * (NumT::one() + simplex[0].1.abs())
+    {
+      break;
+    }
+
+    let mut centroid = [NumT::zero(); 3];
+    for (x, _) in &simplex[..3] {
+      for d in 0..3 {
+        centroid[d] = centroid[d] + x[d] / NumT::from(3).unwrap();
+      }
+    }
+    let towards = |t: NumT| {
+      let mut x = centroid;
+      for d in 0..3 {
+        x[d] = centroid[d] + t * (simplex[3].0[d] - centroid[d]);
+      }
+      x
+    };
+
+    let reflected = towards(-NumT::one());
+    let fr = eval(&reflected);
+    if fr < simplex[0].1 {
+      let expanded = towards(-two);
+      let fe = eval(&expanded);
+      simplex[3] = if fe < fr {
+        (expanded, fe)
+      } else {
+        (reflected, fr)
+      };
+    } else if fr < simplex[2].1 {
+      simplex[3] = (reflected, fr);
+    } else {
+      let contracted = if fr < simplex[3].1 {
+        towards(-half)
+      } else {
+        towards(half)
+      };
+      let fc = eval(&contracted);
+      if fc < fr.min(simplex[3].1) {
+        simplex[3] = (contracted, fc);
+      } else {
+        // shrink towards the best point
+        let best = simplex[0].0;
+        for (x, v) in simplex.iter_mut().skip(1) {
+          for d in 0..3 {
+            x[d] = best[d] + half * (x[d] - best[d]);
+          }
+          *v = eval(x);
+        }
+      }
+    }
+  }
+
+  simplex.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+  simplex[0].0
+}
+
+/// Fit GARCH(1,1) by maximum likelihood starting from `init`, the filter
+/// starts from the sample variance of `e`
+fn garch_fit<NumT: Float>(e: &[NumT], init: Garch<NumT>) -> Garch<NumT> {
+  let var0 = sample_var(e);
+  let x = nelder_mead(|c| Garch::from_coords(c).nll(e, var0).0, init.to_coords());
+  Garch::from_coords(&x)
+}
+
+/// GARCH(1,1) Conditional Variance
+///
+/// variance[t + 1] = omega + alpha * input[t]^2 + beta * variance[t], output at
+/// t is the forecast of the next bar given data up to t. `input` is a return
+/// series with zero mean assumed.
+///
+/// With `refit` == 0 the given (omega, alpha, beta) are used, the filter starts
+/// from the unconditional variance omega / (1 - alpha - beta).
+///
+/// With `refit` > 0 the parameters are fitted by maximum likelihood every
+/// `refit` observations on the last `periods` valid values (0 means expanding
+/// window, at least 10 values), starting from the given parameters, and the
+/// filter is rerun over the window. Output is NaN before the first fit.
+///
+/// FLAG_SKIP_NAN skips missing values, otherwise the variance still decays
+/// over them as if the squared return equals its expectation.
+#[allow(clippy::too_many_arguments)]
+pub fn ta_garch<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+  omega: NumT,
+  alpha: NumT,
+  beta: NumT,
+  periods: usize,
+  refit: usize,
+) -> Result<(), Error> {
+  if r.len() != input.len() {
+    return Err(Error::LengthMismatch(r.len(), input.len()));
+  }
+
+  let init = Garch { omega, alpha, beta };
+  if !init.is_valid() {
+    return Err(Error::InvalidParameter(format!(
+      "invalid garch parameters: omega {:?}, alpha {:?}, beta {:?}",
+      omega.to_f64(),
+      alpha.to_f64(),
+      beta.to_f64()
+    )));
+  }
+  if refit > 0 && periods > 0 && periods < GARCH_MIN_OBS {
+    return Err(Error::InvalidPeriod(format!(
+      "garch fit needs at least {} periods",
+      GARCH_MIN_OBS
+    )));
+  }
+
+  let min_obs = periods.max(GARCH_MIN_OBS);
+  r.par_chunks_mut(ctx.chunk_size(r.len()))
+    .zip(input.par_chunks(ctx.chunk_size(input.len())))
+    .for_each(|(r, x)| {
+      let start = ctx.start(r.len());
+      let end = ctx.end(r.len());
+      r.fill(NumT::nan());
+
+      let mut params = init;
+      let mut variance = if refit == 0 {
+        init.omega / (NumT::one() - init.alpha - init.beta)
+      } else {
+        NumT::nan()
+      };
+      let mut history: Vec<NumT> = Vec::new();
+      let mut since_fit = 0;
+      for i in 0..end {
+        let e = x[i];
+        if !is_normal(&e) {
+          if !ctx.is_skip_nan() {
+            variance = params.next(variance, None);
+          }
+          continue;
+        }
+
+        variance = params.next(variance, Some(e));
+        if refit > 0 {
+          history.push(e);
+          since_fit += 1;
+          let fitted = !variance.is_nan();
+          if history.len() >= min_obs && (!fitted || since_fit >= refit) {
+            let window =
+              &history[history.len() - if periods > 0 { periods } else { history.len() }..];
+            // warm start from the previous fit
+            params = garch_fit(window, params);
+            variance = params.nll(window, sample_var(window)).1;
+            since_fit = 0;
+          }
+        }
+
+        if i >= start {
+          r[i] = variance;
+        }
+      }
+    });
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::algo::{assert_vec_eq_nan, context::FLAG_SKIP_NAN};
+
+  /// GARCH(1,1) path driven by a deterministic normal sequence
+  fn simulate(n: usize, truth: Garch<f64>) -> Vec<f64> {
+    let mut seed: u64 = 42;
+    let mut uniform = || {
+      seed = seed
+        .wrapping_mul(6364136223846793005)
+        .wrapping_add(1442695040888963407);
+      ((seed >> 11) as f64 + 0.5) / (1u64 << 53) as f64
+    };
+    let mut variance = truth.omega / (1.0 - truth.alpha - truth.beta);
+    (0..n)
+      .map(|_| {
+        let z = (-2.0 * uniform().ln()).sqrt() * (2.0 * std::f64::consts::PI * uniform()).cos();
+        let e = variance.sqrt() * z;
+        variance = truth.next(variance, Some(e));
+        e
+      })
+      .collect()
+  }
+
+  #[test]
+  fn test_garch_fixed() {
+    let input = vec![0.01, -0.02, f64::NAN, 0.03];
+    let (omega, alpha, beta) = (1e-5, 0.1, 0.8);
+    let mut r = vec![0.0; input.len()];
+    let v0 = omega / (1.0 - alpha - beta);
+    let v1 = omega + alpha * 0.01 * 0.01 + beta * v0;
+    let v2 = omega + alpha * 0.02 * 0.02 + beta * v1;
+
+    let ctx = Context::new(0, 1, 0);
+    ta_garch(&ctx, &mut r, &input, omega, alpha, beta, 0, 0).unwrap();
+    // the missing bar decays the variance towards its unconditional level
+    let v3 = omega + (alpha + beta) * v2;
+    let v4 = omega + alpha * 0.03 * 0.03 + beta * v3;
+    assert_vec_eq_nan(&r, &[v1, v2, f64::NAN, v4]);
+
+    let ctx = Context::new(0, 1, FLAG_SKIP_NAN);
+    ta_garch(&ctx, &mut r, &input, omega, alpha, beta, 0, 0).unwrap();
+    let v4 = omega + alpha * 0.03 * 0.03 + beta * v2;
+    assert_vec_eq_nan(&r, &[v1, v2, f64::NAN, v4]);
+
+    assert!(ta_garch(&ctx, &mut r, &input, omega, 0.5, 0.5, 0, 0).is_err());
+    assert!(ta_garch(&ctx, &mut r, &input, omega, alpha, beta, 5, 1).is_err());
+  }
+
+  #[test]
+  fn test_garch_fit() {
+    let truth = Garch {
+      omega: 1e-5,
+      alpha: 0.1,
+      beta: 0.85,
+    };
+    let e = simulate(3000, truth);
+    let init = Garch {
+      omega: 1e-4,
+      alpha: 0.05,
+      beta: 0.5,
+    };
+    let fit = garch_fit(&e, init);
+    assert!((fit.alpha - truth.alpha).abs() < 0.05, "{:?}", fit);
+    assert!((fit.beta - truth.beta).abs() < 0.08, "{:?}", fit);
+
+    // the fit is at least as likely as the truth
+    let var0 = sample_var(&e);
+    assert!(fit.nll(&e, var0).0 <= truth.nll(&e, var0).0 + 1e-9);
+  }
+
+  #[test]
+  fn test_garch_refit() {
+    let e = simulate(
+      300,
+      Garch {
+        omega: 1e-5,
+        alpha: 0.1,
+        beta: 0.85,
+      },
+    );
+    let ctx = Context::new(0, 2, 0);
+    let input = [e.clone(), e].concat();
+    let mut r = vec![0.0; input.len()];
+    ta_garch(&ctx, &mut r, &input, 1e-5, 0.05, 0.9, 100, 50).unwrap();
+
+    // NaN before the first fit, then positive variances
+    assert!(r[..99].iter().all(|v| v.is_nan()));
+    assert!(r[99..300].iter().all(|v| *v > 0.0));
+    assert_vec_eq_nan(&r[..300], &r[300..]);
+  }
+}
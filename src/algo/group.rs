@@ -6,7 +6,10 @@ use std::{cmp::Ordering, fmt::Debug};
 use num_traits::Float;
 use rayon::prelude::*;
 
-use crate::algo::{Context, Error, is_normal};
+use crate::algo::{
+  Context, Error, is_normal,
+  rank::{RANK_AVERAGE, RANK_PCT, bin_of, check_rank_method, rank_items},
+};
 
 #[derive(Copy, Clone, Default, PartialEq, PartialOrd)]
 struct OrderedFloat<NumT: Float> {
@@ -67,7 +70,9 @@ unsafe impl<NumT: Float> Sync for UnsafePtr<NumT> {}
 ///
 /// For each time position, groups items by `category` value, then computes
 /// rank percentage within each group. Same value gets averaged rank.
+/// A category with a single valid value gets 0.5.
 /// NaN in category or input produces NaN output.
+/// See `ta_group_rank_ex` for other tie methods.
 pub fn ta_group_rank<NumT: Float + Send + Sync + Debug>(
   ctx: &Context,
   r: &mut [NumT],
@@ -78,82 +83,57 @@ pub fn ta_group_rank<NumT: Float + Send + Sync + Debug>(
     return Err(Error::LengthMismatch(r.len(), input.len()));
   }
 
+  group_rank(ctx, r, category, input, RANK_AVERAGE | RANK_PCT, true)
+}
 
-  let group_size = ctx.chunk_size(r.len()) as usize;
-  let groups = ctx.groups() as usize;
-
-  if r.len() != group_size * groups {
-    return Err(Error::LengthMismatch(r.len(), group_size * groups));
+/// Calculate rank within each category group at each time step using a tie `method`
+///
+/// `method` is one of `RANK_AVERAGE` (0), `RANK_MIN` (1), `RANK_MAX` (2), `RANK_DENSE` (3),
+/// `RANK_ORDINAL` (4), optionally combined with `RANK_PCT` (8) and `RANK_DESCENDING` (16).
+/// Ties of `RANK_ORDINAL` are ranked by group order.
+/// NaN in category or input produces NaN output.
+pub fn ta_group_rank_ex<NumT: Float + Send + Sync + Debug>(
+  ctx: &Context,
+  r: &mut [NumT],
+  category: &[NumT],
+  input: &[NumT],
+  method: usize,
+) -> Result<(), Error> {
+  if r.len() != input.len() || r.len() != category.len() {
+    return Err(Error::LengthMismatch(r.len(), input.len()));
   }
 
+  check_rank_method(method)?;
 
-  let r_ptr = UnsafePtr::new(r.as_mut_ptr(), r.len());
-  (0..group_size).into_par_iter().for_each(|j| {
-    let r = r_ptr.get();
-
-    // Initialize all to NaN
-    for i in 0..groups {
-      r[i * group_size + j] = NumT::nan();
-    }
+  group_rank(ctx, r, category, input, method, false)
+}
 
-    // Collect (category, value, index) for valid items
-    let mut items: Vec<(OrderedFloat<NumT>, OrderedFloat<NumT>, usize)> =
-      Vec::with_capacity(groups);
-    for i in 0..groups {
-      let idx = i * group_size + j;
-      let c = category[idx];
-      let x = input[idx];
-      if is_normal(&c) && is_normal(&x) {
-        items.push((c.into(), x.into(), idx));
+/// Group rank kernel of `ta_group_rank` and `ta_group_rank_ex`
+///
+/// If `single_half` is set, the only valid item of a category gets 0.5.
+fn group_rank<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  category: &[NumT],
+  input: &[NumT],
+  method: usize,
+  single_half: bool,
+) -> Result<(), Error> {
+  for_each_category(ctx, r, category, |_, members, r| {
+    let mut items: Vec<(NumT, usize)> = Vec::with_capacity(members.len());
+    for &idx in members {
+      if is_normal(&input[idx]) {
+        items.push((input[idx], idx));
+      } else {
+        r[idx] = NumT::nan();
       }
     }
-
-    if items.is_empty() {
-      return;
+    if single_half && items.len() == 1 {
+      r[items[0].1] = NumT::from(0.5).unwrap();
+    } else {
+      rank_items(&mut items, method, r);
     }
-
-    // Sort by category first, then by value
-    items.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)));
-
-    // Process each category group
-    let mut cat_start = 0;
-    while cat_start < items.len() {
-      // Find end of this category
-      let mut cat_end = cat_start + 1;
-      while cat_end < items.len() && items[cat_end].0.value == items[cat_start].0.value {
-        cat_end += 1;
-      }
-
-      let cat_count = cat_end - cat_start;
-      if cat_count == 1 {
-        r[items[cat_start].2] = NumT::from(0.5).unwrap();
-        cat_start = cat_end;
-        continue;
-      }
-
-      // Items in this category are already sorted by value
-      // Compute averaged rank percentage
-      let total = NumT::from(cat_count).unwrap();
-      let mut s = cat_start;
-      while s < cat_end {
-        let mut e = s + 1;
-        while e < cat_end && items[e].1.value == items[s].1.value {
-          e += 1;
-        }
-        // Ranks (1-based) for this tie group: (s-cat_start+1) to (e-cat_start)
-        let rank_avg =
-          NumT::from((s - cat_start + 1) + (e - cat_start)).unwrap() / NumT::from(2usize).unwrap();
-        for k in s..e {
-          r[items[k].2] = rank_avg / total;
-        }
-        s = e;
-      }
-
-      cat_start = cat_end;
-    }
-  });
-
-  Ok(())
+  })
 }
 
 /// Calculate Z-Score within each category group at each time step
@@ -255,10 +235,479 @@ pub fn ta_group_zscore<NumT: Float + Send + Sync + Debug>(
   Ok(())
 }
 
+/// Visit every category group at every time step
+///
+/// For each time position `j`, items with a valid category are sorted by category
+/// and `f` is called once per category with `j` and the flat indices of its members.
+/// Output of items whose category is NaN is set to NaN.
+fn for_each_category<NumT, F>(
+  ctx: &Context,
+  r: &mut [NumT],
+  category: &[NumT],
+  f: F,
+) -> Result<(), Error>
+where
+  NumT: Float + Send + Sync,
+  F: Fn(usize, &[usize], &mut [NumT]) + Send + Sync,
+{
+  let group_size = ctx.chunk_size(r.len());
+  let groups = ctx.groups();
+
+  if r.len() != group_size * groups {
+    return Err(Error::LengthMismatch(r.len(), group_size * groups));
+  }
+
+  let r_ptr = UnsafePtr::new(r.as_mut_ptr(), r.len());
+  (0..group_size).into_par_iter().for_each(|j| {
+    let r = r_ptr.get();
+
+    let mut items: Vec<(OrderedFloat<NumT>, usize)> = Vec::with_capacity(groups);
+    for i in 0..groups {
+      let idx = i * group_size + j;
+      let c = category[idx];
+      if is_normal(&c) {
+        items.push((c.into(), idx));
+      } else {
+        r[idx] = NumT::nan();
+      }
+    }
+
+    // Sort by category, keep the security order inside each category
+    items.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)));
+
+    let mut members: Vec<usize> = Vec::with_capacity(items.len());
+    let mut cat_start = 0;
+    while cat_start < items.len() {
+      let mut cat_end = cat_start + 1;
+      while cat_end < items.len() && items[cat_end].0.value == items[cat_start].0.value {
+        cat_end += 1;
+      }
+
+      members.clear();
+      members.extend(items[cat_start..cat_end].iter().map(|v| v.1));
+      f(j, &members, r);
+
+      cat_start = cat_end;
+    }
+  });
+
+  Ok(())
+}
+
+/// Aggregate valid values of each category group and broadcast the result
+/// to every member of the group (including members whose input is NaN)
+fn group_aggregate<NumT, F>(
+  ctx: &Context,
+  r: &mut [NumT],
+  category: &[NumT],
+  input: &[NumT],
+  f: F,
+) -> Result<(), Error>
+where
+  NumT: Float + Send + Sync,
+  F: Fn(&mut [NumT]) -> NumT + Send + Sync,
+{
+  if r.len() != input.len() || r.len() != category.len() {
+    return Err(Error::LengthMismatch(r.len(), input.len()));
+  }
+
+  for_each_category(ctx, r, category, |_, members, r| {
+    let mut values: Vec<NumT> = members
+      .iter()
+      .map(|&idx| input[idx])
+      .filter(is_normal)
+      .collect();
+    let v = if values.is_empty() {
+      NumT::nan()
+    } else {
+      f(&mut values)
+    };
+    for &idx in members {
+      r[idx] = v;
+    }
+  })
+}
+
+/// Calculate mean of each category group at each time step
+///
+/// For each time position, groups items by `category` value and assigns the
+/// mean of valid values to every member of the group.
+/// If `weights` is not empty, the weighted mean sum(w * x) / sum(w) is used,
+/// items with NaN weight are excluded.
+/// NaN in category produces NaN output.
+pub fn ta_group_mean<NumT: Float + Send + Sync + Debug>(
+  ctx: &Context,
+  r: &mut [NumT],
+  category: &[NumT],
+  input: &[NumT],
+  weights: &[NumT],
+) -> Result<(), Error> {
+  if weights.is_empty() {
+    return group_aggregate(ctx, r, category, input, |v| {
+      v.iter().fold(NumT::zero(), |a, b| a + *b) / NumT::from(v.len()).unwrap()
+    });
+  }
+
+  if r.len() != input.len() || r.len() != category.len() || r.len() != weights.len() {
+    return Err(Error::LengthMismatch(r.len(), input.len()));
+  }
+
+  for_each_category(ctx, r, category, |_, members, r| {
+    let mut sum = NumT::zero();
+    let mut sum_w = NumT::zero();
+    for &idx in members {
+      let x = input[idx];
+      let w = weights[idx];
+      if is_normal(&x) && is_normal(&w) {
+        sum = sum + w * x;
+        sum_w = sum_w + w;
+      }
+    }
+    let v = if sum_w == NumT::zero() {
+      NumT::nan()
+    } else {
+      sum / sum_w
+    };
+    for &idx in members {
+      r[idx] = v;
+    }
+  })
+}
+
+/// Calculate sum of each category group at each time step
+///
+/// The sum of valid values is assigned to every member of the group.
+/// Groups without valid values produce NaN.
+pub fn ta_group_sum<NumT: Float + Send + Sync + Debug>(
+  ctx: &Context,
+  r: &mut [NumT],
+  category: &[NumT],
+  input: &[NumT],
+) -> Result<(), Error> {
+  group_aggregate(ctx, r, category, input, |v| {
+    v.iter().fold(NumT::zero(), |a, b| a + *b)
+  })
+}
+
+/// Calculate maximum of each category group at each time step
+///
+/// The maximum of valid values is assigned to every member of the group.
+pub fn ta_group_max<NumT: Float + Send + Sync + Debug>(
+  ctx: &Context,
+  r: &mut [NumT],
+  category: &[NumT],
+  input: &[NumT],
+) -> Result<(), Error> {
+  group_aggregate(ctx, r, category, input, |v| {
+    v.iter().fold(NumT::neg_infinity(), |a, b| a.max(*b))
+  })
+}
+
+/// Calculate minimum of each category group at each time step
+///
+/// The minimum of valid values is assigned to every member of the group.
+pub fn ta_group_min<NumT: Float + Send + Sync + Debug>(
+  ctx: &Context,
+  r: &mut [NumT],
+  category: &[NumT],
+  input: &[NumT],
+) -> Result<(), Error> {
+  group_aggregate(ctx, r, category, input, |v| {
+    v.iter().fold(NumT::infinity(), |a, b| a.min(*b))
+  })
+}
+
+/// Calculate median of each category group at each time step
+///
+/// The median of valid values is assigned to every member of the group,
+/// even-sized groups use the average of the two middle values.
+pub fn ta_group_median<NumT: Float + Send + Sync + Debug>(
+  ctx: &Context,
+  r: &mut [NumT],
+  category: &[NumT],
+  input: &[NumT],
+) -> Result<(), Error> {
+  group_aggregate(ctx, r, category, input, |v| {
+    v.sort_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+    let n = v.len();
+    if n % 2 == 1 {
+      v[n / 2]
+    } else {
+      (v[n / 2 - 1] + v[n / 2]) / NumT::from(2.0).unwrap()
+    }
+  })
+}
+
+/// Count valid values of each category group at each time step
+///
+/// The number of members with non-NaN input is assigned to every member of the group.
+/// NaN in category produces NaN output.
+pub fn ta_group_count<NumT: Float + Send + Sync + Debug>(
+  ctx: &Context,
+  r: &mut [NumT],
+  category: &[NumT],
+  input: &[NumT],
+) -> Result<(), Error> {
+  if r.len() != input.len() || r.len() != category.len() {
+    return Err(Error::LengthMismatch(r.len(), input.len()));
+  }
+
+  for_each_category(ctx, r, category, |_, members, r| {
+    let count = members
+      .iter()
+      .filter(|&&idx| is_normal(&input[idx]))
+      .count();
+    let v = NumT::from(count).unwrap();
+    for &idx in members {
+      r[idx] = v;
+    }
+  })
+}
+
+/// Demean the input within each category group at each time step
+///
+/// r = x - group_mean, NaN in category or input produces NaN output.
+///
+/// Ref: WQ BRAIN group_neutralize
+pub fn ta_group_neutralize<NumT: Float + Send + Sync + Debug>(
+  ctx: &Context,
+  r: &mut [NumT],
+  category: &[NumT],
+  input: &[NumT],
+) -> Result<(), Error> {
+  if r.len() != input.len() || r.len() != category.len() {
+    return Err(Error::LengthMismatch(r.len(), input.len()));
+  }
+
+  for_each_category(ctx, r, category, |_, members, r| {
+    let mut sum = NumT::zero();
+    let mut n = 0usize;
+    for &idx in members {
+      if is_normal(&input[idx]) {
+        sum = sum + input[idx];
+        n += 1;
+      }
+    }
+    for &idx in members {
+      r[idx] = if n == 0 {
+        NumT::nan()
+      } else {
+        input[idx] - sum / NumT::from(n).unwrap()
+      };
+    }
+  })
+}
+
+/// Scale the input into [0, 1] within each category group at each time step
+///
+/// r = (x - group_min) / (group_max - group_min).
+/// Groups with zero range produce 0.5, NaN in category or input produces NaN output.
+///
+/// Ref: WQ BRAIN group_scale
+pub fn ta_group_scale<NumT: Float + Send + Sync + Debug>(
+  ctx: &Context,
+  r: &mut [NumT],
+  category: &[NumT],
+  input: &[NumT],
+) -> Result<(), Error> {
+  if r.len() != input.len() || r.len() != category.len() {
+    return Err(Error::LengthMismatch(r.len(), input.len()));
+  }
+
+  for_each_category(ctx, r, category, |_, members, r| {
+    let mut min = NumT::infinity();
+    let mut max = NumT::neg_infinity();
+    for &idx in members {
+      let x = input[idx];
+      if is_normal(&x) {
+        min = min.min(x);
+        max = max.max(x);
+      }
+    }
+    let range = max - min;
+    for &idx in members {
+      let x = input[idx];
+      r[idx] = if !is_normal(&x) {
+        NumT::nan()
+      } else if range.abs() < NumT::epsilon() {
+        NumT::from(0.5).unwrap()
+      } else {
+        (x - min) / range
+      };
+    }
+  })
+}
+
+/// bins flag: bucket by equal-width value ranges instead of equal counts
+pub const BINS_EQUAL_WIDTH: usize = 1;
+/// bins flag: bin index starts from 1 instead of 0
+pub const BINS_ONE_BASED: usize = 2;
+
+/// Discretize the input into n bins within each category group at each time step
+///
+/// By default bins have equal counts and use the same rule as `ta_bins`:
+/// same value are assigned to the same bin, bin = floor((avg_rank - 1) * bins / count).
+/// `flags` is a combination of `BINS_EQUAL_WIDTH` (1) to split [min, max] of the group
+/// into equal-width ranges, and `BINS_ONE_BASED` (2) to number bins from 1.
+/// NaN in category or input produces NaN output.
+pub fn ta_group_bins<NumT: Float + Send + Sync + Debug>(
+  ctx: &Context,
+  r: &mut [NumT],
+  category: &[NumT],
+  input: &[NumT],
+  bins: usize,
+  flags: usize,
+) -> Result<(), Error> {
+  if r.len() != input.len() || r.len() != category.len() {
+    return Err(Error::LengthMismatch(r.len(), input.len()));
+  }
+
+  if bins == 0 {
+    return Err(Error::InvalidParameter("bins must be >= 1".to_string()));
+  }
+
+  let bins_t = NumT::from(bins).unwrap();
+  let last_bin = bins_t - NumT::one();
+  let base = if flags & BINS_ONE_BASED != 0 {
+    NumT::one()
+  } else {
+    NumT::zero()
+  };
+  let equal_width = flags & BINS_EQUAL_WIDTH != 0;
+
+  for_each_category(ctx, r, category, |_, members, r| {
+    let mut items: Vec<(NumT, usize)> = Vec::with_capacity(members.len());
+    for &idx in members {
+      let x = input[idx];
+      if is_normal(&x) {
+        items.push((x, idx));
+      } else {
+        r[idx] = NumT::nan();
+      }
+    }
+
+    if items.is_empty() {
+      return;
+    }
+
+    items.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+
+    if equal_width {
+      let min = items[0].0;
+      let range = items[items.len() - 1].0 - min;
+      for &(x, idx) in &items {
+        let b = if range > NumT::zero() {
+          ((x - min) / range * bins_t).floor().min(last_bin)
+        } else {
+          NumT::zero()
+        };
+        r[idx] = b + base;
+      }
+      return;
+    }
+
+    let total = NumT::from(items.len()).unwrap();
+    let mut s = 0;
+    while s < items.len() {
+      let mut e = s + 1;
+      while e < items.len() && items[e].0 == items[s].0 {
+        e += 1;
+      }
+      // 1-based average rank of this tie chunk
+      let rank_avg = NumT::from(s + e + 1).unwrap() / NumT::from(2usize).unwrap();
+      let b = bin_of(rank_avg, total, bins_t);
+      for item in &items[s..e] {
+        r[item.1] = b + base;
+      }
+      s = e;
+    }
+  })
+}
+
+/// Fill NaN input with the winsorized mean of the same category group
+///
+/// For a NaN value at a time step, collects all valid values of the members
+/// in the same category over the preceding `periods` time steps (including the
+/// current one), clips them to mean ± std * stddev, and fills with the mean of
+/// the clipped values. Valid input is kept as is.
+///
+/// Ref: WQ BRAIN group_backfill
+pub fn ta_group_backfill<NumT: Float + Send + Sync + Debug>(
+  ctx: &Context,
+  r: &mut [NumT],
+  category: &[NumT],
+  input: &[NumT],
+  periods: usize,
+  std: NumT,
+) -> Result<(), Error> {
+  if r.len() != input.len() || r.len() != category.len() {
+    return Err(Error::LengthMismatch(r.len(), input.len()));
+  }
+
+  if periods == 0 {
+    return Err(Error::InvalidPeriod(format!(
+      "group_backfill requires periods >= 1, got {}",
+      periods
+    )));
+  }
+
+  for_each_category(ctx, r, category, |j, members, r| {
+    let win_start = (j + 1).saturating_sub(periods);
+    let mut fill: Option<NumT> = None;
+
+    for &idx in members {
+      let x = input[idx];
+      if is_normal(&x) {
+        r[idx] = x;
+        continue;
+      }
+
+      let v = *fill.get_or_insert_with(|| {
+        let mut values = Vec::with_capacity(members.len() * (j + 1 - win_start));
+        for &m in members {
+          // `m` is the flat index at time `j`, walk back along the same security
+          for k in win_start..=j {
+            let v = input[m - j + k];
+            if is_normal(&v) {
+              values.push(v);
+            }
+          }
+        }
+        winsorized_mean(&values, std)
+      });
+      r[idx] = v;
+    }
+  })
+}
+
+fn winsorized_mean<NumT: Float>(values: &[NumT], std: NumT) -> NumT {
+  if values.is_empty() {
+    return NumT::nan();
+  }
+
+  let n = NumT::from(values.len()).unwrap();
+  let mean = values.iter().fold(NumT::zero(), |a, b| a + *b) / n;
+  if values.len() < 2 {
+    return mean;
+  }
+
+  let var = values
+    .iter()
+    .fold(NumT::zero(), |a, b| a + (*b - mean) * (*b - mean))
+    / (n - NumT::one());
+  let bound = std * var.sqrt();
+  let lo = mean - bound;
+  let hi = mean + bound;
+  values
+    .iter()
+    .fold(NumT::zero(), |a, b| a + b.max(lo).min(hi))
+    / n
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
-  use crate::algo::assert_vec_eq_nan;
+  use crate::algo::{RANK_DESCENDING, RANK_MIN, RANK_ORDINAL, RANK_PCT, assert_vec_eq_nan};
 
   #[test]
   fn test_group_rank_simple() {
@@ -354,4 +803,151 @@ mod tests {
     ta_group_rank(&ctx, &mut r, &category, &input).unwrap();
     assert_vec_eq_nan(&r, &vec![0.5, 0.5, 1.0, 1.0, 0.5, 0.5]);
   }
+
+  #[test]
+  fn test_group_aggregates() {
+    // groups=5, group_size=1
+    // cat1: [10, 30, NaN], cat2: [5, 15]
+    let ctx = Context::new(0, 5, 0);
+    let category = [1.0, 1.0, 1.0, 2.0, 2.0];
+    let input = [10.0, 30.0, f64::NAN, 5.0, 15.0];
+    let mut r = vec![0.0; 5];
+
+    ta_group_mean(&ctx, &mut r, &category, &input, &[]).unwrap();
+    assert_vec_eq_nan(&r, &[20.0, 20.0, 20.0, 10.0, 10.0]);
+
+    ta_group_sum(&ctx, &mut r, &category, &input).unwrap();
+    assert_vec_eq_nan(&r, &[40.0, 40.0, 40.0, 20.0, 20.0]);
+
+    ta_group_max(&ctx, &mut r, &category, &input).unwrap();
+    assert_vec_eq_nan(&r, &[30.0, 30.0, 30.0, 15.0, 15.0]);
+
+    ta_group_min(&ctx, &mut r, &category, &input).unwrap();
+    assert_vec_eq_nan(&r, &[10.0, 10.0, 10.0, 5.0, 5.0]);
+
+    ta_group_count(&ctx, &mut r, &category, &input).unwrap();
+    assert_vec_eq_nan(&r, &[2.0, 2.0, 2.0, 2.0, 2.0]);
+  }
+
+  #[test]
+  fn test_group_mean_weighted() {
+    // cat1: x=[10, 30] w=[3, 1] -> (30 + 30) / 4 = 15
+    // cat2: x=[5, 15] w=[NaN, 2] -> 15
+    let ctx = Context::new(0, 4, 0);
+    let category = [1.0, 1.0, 2.0, 2.0];
+    let input = [10.0, 30.0, 5.0, 15.0];
+    let weights = [3.0, 1.0, f64::NAN, 2.0];
+    let mut r = vec![0.0; 4];
+    ta_group_mean(&ctx, &mut r, &category, &input, &weights).unwrap();
+    assert_vec_eq_nan(&r, &[15.0, 15.0, 15.0, 15.0]);
+  }
+
+  #[test]
+  fn test_group_median() {
+    let ctx = Context::new(0, 6, 0);
+    let category = [1.0, 1.0, 1.0, 2.0, 2.0, f64::NAN];
+    let input = [3.0, 1.0, 2.0, 4.0, 8.0, 1.0];
+    let mut r = vec![0.0; 6];
+    ta_group_median(&ctx, &mut r, &category, &input).unwrap();
+    assert_vec_eq_nan(&r, &[2.0, 2.0, 2.0, 6.0, 6.0, f64::NAN]);
+  }
+
+  #[test]
+  fn test_group_neutralize_scale() {
+    let ctx = Context::new(0, 5, 0);
+    let category = [1.0, 1.0, 1.0, 2.0, 2.0];
+    let input = [10.0, 30.0, f64::NAN, 5.0, 5.0];
+    let mut r = vec![0.0; 5];
+
+    ta_group_neutralize(&ctx, &mut r, &category, &input).unwrap();
+    assert_vec_eq_nan(&r, &[-10.0, 10.0, f64::NAN, 0.0, 0.0]);
+
+    ta_group_scale(&ctx, &mut r, &category, &input).unwrap();
+    assert_vec_eq_nan(&r, &[0.0, 1.0, f64::NAN, 0.5, 0.5]);
+  }
+
+  #[test]
+  fn test_group_backfill() {
+    // groups=3, group_size=3
+    // s1: [1, 2, 3], s2: [5, NaN, NaN], s3 in another category: [NaN, NaN, NaN]
+    let ctx = Context::new(0, 3, 0);
+    let category = [1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 2.0, 2.0, 2.0];
+    let input = [
+      1.0,
+      2.0,
+      3.0,
+      5.0,
+      f64::NAN,
+      f64::NAN,
+      f64::NAN,
+      f64::NAN,
+      f64::NAN,
+    ];
+    let mut r = vec![0.0; 9];
+    // periods=2, a large std keeps the plain mean
+    // t1: window t0..t1 -> [1, 2, 5] mean 8/3
+    // t2: window t1..t2 -> [2, 3] mean 2.5
+    ta_group_backfill(&ctx, &mut r, &category, &input, 2, 100.0).unwrap();
+    assert_vec_eq_nan(
+      &r,
+      &[
+        1.0,
+        2.0,
+        3.0,
+        5.0,
+        8.0 / 3.0,
+        2.5,
+        f64::NAN,
+        f64::NAN,
+        f64::NAN,
+      ],
+    );
+  }
+
+  #[test]
+  fn test_group_bins_equal_count() {
+    // cat1: [1, 2, 3, 4] -> bins=2 -> [0, 0, 1, 1]
+    // cat2: [5, 5, 9] -> ties share avg rank 1.5 -> [0, 0, 1]
+    let ctx = Context::new(0, 8, 0);
+    let category = [1.0, 1.0, 1.0, 1.0, 2.0, 2.0, 2.0, 2.0];
+    let input = [4.0, 1.0, 3.0, 2.0, 5.0, 9.0, 5.0, f64::NAN];
+    let mut r = vec![0.0; 8];
+    ta_group_bins(&ctx, &mut r, &category, &input, 2, 0).unwrap();
+    assert_vec_eq_nan(&r, &[1.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, f64::NAN]);
+
+    ta_group_bins(&ctx, &mut r, &category, &input, 2, BINS_ONE_BASED).unwrap();
+    assert_vec_eq_nan(&r, &[2.0, 1.0, 2.0, 1.0, 1.0, 2.0, 1.0, f64::NAN]);
+  }
+
+  #[test]
+  fn test_group_bins_equal_width() {
+    // cat1: [0, 1, 2, 10] -> width 10 / 4 -> [0, 0, 0, 3]
+    // cat2: [7, 7] -> zero range -> [0, 0]
+    let ctx = Context::new(0, 6, 0);
+    let category = [1.0, 1.0, 1.0, 1.0, 2.0, 2.0];
+    let input = [0.0, 1.0, 2.0, 10.0, 7.0, 7.0];
+    let mut r = vec![0.0; 6];
+    ta_group_bins(&ctx, &mut r, &category, &input, 4, BINS_EQUAL_WIDTH).unwrap();
+    assert_vec_eq_nan(&r, &[0.0, 0.0, 0.0, 3.0, 0.0, 0.0]);
+  }
+
+  #[test]
+  fn test_group_rank_ex() {
+    let ctx = Context::new(0, 5, 0);
+    let category = [1.0, 1.0, 1.0, 2.0, 2.0];
+    let input = [5.0, 5.0, 7.0, 1.0, f64::NAN];
+    let mut r = vec![0.0; 5];
+    ta_group_rank_ex(&ctx, &mut r, &category, &input, RANK_MIN | RANK_PCT).unwrap();
+    assert_vec_eq_nan(&r, &[1.0 / 3.0, 1.0 / 3.0, 1.0, 1.0, f64::NAN]);
+
+    ta_group_rank_ex(
+      &ctx,
+      &mut r,
+      &category,
+      &input,
+      RANK_ORDINAL | RANK_DESCENDING,
+    )
+    .unwrap();
+    assert_vec_eq_nan(&r, &[2.0, 3.0, 1.0, 1.0, f64::NAN]);
+  }
 }
@@ -0,0 +1,583 @@
+// Copyright 2026 MSD-RS Project LiJia
+// SPDX-License-Identifier: BSD-2-Clause
+
+use std::collections::VecDeque;
+
+use num_traits::Float;
+
+use super::bars::run_bars;
+use crate::algo::{Context, Error};
+
+/// Bars before the first value of HT_DCPERIOD, HT_PHASOR and MAMA
+const LOOKBACK: usize = 32;
+/// Bars before the first value of the phase based indicators
+const PHASE_LOOKBACK: usize = 63;
+/// Longest dominant cycle, also the size of the price buffers
+const MAX_PERIOD: usize = 50;
+
+fn c<NumT: Float>(v: f64) -> NumT {
+  NumT::from(v).unwrap()
+}
+
+/// One Hilbert transform filter, with separate delay lines for odd and even
+/// bars as TA-Lib
+struct Hilbert<NumT> {
+  taps: [[NumT; 3]; 2],
+  prev: [NumT; 2],
+  prev_input: [NumT; 2],
+}
+
+impl<NumT: Float> Hilbert<NumT> {
+  fn new() -> Self {
+    Self {
+      taps: [[NumT::zero(); 3]; 2],
+      prev: [NumT::zero(); 2],
+      prev_input: [NumT::zero(); 2],
+    }
+  }
+
+  fn transform(&mut self, input: NumT, parity: usize, idx: usize, adjust: NumT) -> NumT {
+    let a = c::<NumT>(0.0962) * input;
+    let mut v = a - self.taps[parity][idx];
+    self.taps[parity][idx] = a;
+    v = v - self.prev[parity];
+    self.prev[parity] = c::<NumT>(0.5769) * self.prev_input[parity];
+    v = v + self.prev[parity];
+    self.prev_input[parity] = input;
+    v * adjust
+  }
+}
+
+/// Values of the core after a bar
+struct CoreStep<NumT> {
+  smoothed: NumT,
+  in_phase: NumT,
+  quadrature: NumT,
+  smooth_period: NumT,
+}
+
+/// Price smoother, Hilbert transform and homodyne period discriminator shared
+/// by the HT_* indicators and MAMA
+struct HilbertCore<NumT> {
+  today: usize,
+  prices: VecDeque<NumT>,
+  hilbert_idx: usize,
+  detrender: Hilbert<NumT>,
+  q1: Hilbert<NumT>,
+  ji: Hilbert<NumT>,
+  jq: Hilbert<NumT>,
+  /// [odd, even] in-phase component delayed by 2 and 3 bars
+  i1_prev2: [NumT; 2],
+  i1_prev3: [NumT; 2],
+  prev_i2: NumT,
+  prev_q2: NumT,
+  re: NumT,
+  im: NumT,
+  period: NumT,
+  smooth_period: NumT,
+}
+
+impl<NumT: Float> HilbertCore<NumT> {
+  fn new() -> Self {
+    let zero = NumT::zero();
+    Self {
+      today: 0,
+      prices: VecDeque::with_capacity(MAX_PERIOD + 1),
+      hilbert_idx: 0,
+      detrender: Hilbert::new(),
+      q1: Hilbert::new(),
+      ji: Hilbert::new(),
+      jq: Hilbert::new(),
+      i1_prev2: [zero; 2],
+      i1_prev3: [zero; 2],
+      prev_i2: zero,
+      prev_q2: zero,
+      re: zero,
+      im: zero,
+      period: zero,
+      smooth_period: zero,
+    }
+  }
+
+  /// Index of the current bar since the start or the last reset
+  fn today(&self) -> usize {
+    self.today - 1
+  }
+
+  /// Raw price `back` bars before the current one
+  fn price(&self, back: usize) -> NumT {
+    self.prices[self.prices.len() - 1 - back]
+  }
+
+  fn update(&mut self, x: NumT) -> Option<CoreStep<NumT>> {
+    if self.prices.len() == MAX_PERIOD {
+      self.prices.pop_front();
+    }
+    self.prices.push_back(x);
+    let today = self.today;
+    self.today += 1;
+    // the 4 bar WMA needs 3 prices, then 9 bars settle it
+    if today < 12 {
+      return None;
+    }
+
+    let smoothed = (c::<NumT>(4.0) * self.price(0)
+      + c::<NumT>(3.0) * self.price(1)
+      + c::<NumT>(2.0) * self.price(2)
+      + self.price(3))
+      * c(0.1);
+    let adjust = c::<NumT>(0.075) * self.period + c(0.54);
+
+    // odd and even bars run on their own delay lines, each feeding the
+    // in-phase history read by the other
+    let (parity, other) = if today.is_multiple_of(2) {
+      (1, 0)
+    } else {
+      (0, 1)
+    };
+    let idx = self.hilbert_idx;
+    let detrender = self.detrender.transform(smoothed, parity, idx, adjust);
+    let q1 = self.q1.transform(detrender, parity, idx, adjust);
+    let i1 = self.i1_prev3[parity];
+    let ji = self.ji.transform(i1, parity, idx, adjust);
+    let jq = self.jq.transform(q1, parity, idx, adjust);
+    if parity == 1 {
+      self.hilbert_idx = (self.hilbert_idx + 1) % 3;
+    }
+    let q2 = c::<NumT>(0.2) * (q1 + ji) + c::<NumT>(0.8) * self.prev_q2;
+    let i2 = c::<NumT>(0.2) * (i1 - jq) + c::<NumT>(0.8) * self.prev_i2;
+    self.i1_prev3[other] = self.i1_prev2[other];
+    self.i1_prev2[other] = detrender;
+
+    self.re = c::<NumT>(0.2) * (i2 * self.prev_i2 + q2 * self.prev_q2) + c::<NumT>(0.8) * self.re;
+    self.im = c::<NumT>(0.2) * (i2 * self.prev_q2 - q2 * self.prev_i2) + c::<NumT>(0.8) * self.im;
+    self.prev_q2 = q2;
+    self.prev_i2 = i2;
+
+    let prev = self.period;
+    let mut period = prev;
+    if self.im != NumT::zero() && self.re != NumT::zero() {
+      period = c::<NumT>(360.0) / (self.im / self.re).atan().to_degrees();
+    }
+    period = period
+      .min(c::<NumT>(1.5) * prev)
+      .max(c::<NumT>(0.67) * prev);
+    period = period.max(c(6.0)).min(c(MAX_PERIOD as f64));
+    self.period = c::<NumT>(0.2) * period + c::<NumT>(0.8) * prev;
+    self.smooth_period = c::<NumT>(0.33) * self.period + c::<NumT>(0.67) * self.smooth_period;
+
+    Some(CoreStep {
+      smoothed,
+      in_phase: i1,
+      quadrature: q1,
+      smooth_period: self.smooth_period,
+    })
+  }
+}
+
+/// Dominant cycle phase from the smoothed prices of the last dominant cycle
+struct Phase<NumT> {
+  smoothed: VecDeque<NumT>,
+  phase: NumT,
+}
+
+impl<NumT: Float> Phase<NumT> {
+  fn new() -> Self {
+    Self {
+      smoothed: std::iter::repeat_n(NumT::zero(), MAX_PERIOD).collect(),
+      phase: NumT::zero(),
+    }
+  }
+
+  fn update(&mut self, smoothed: NumT, smooth_period: NumT) -> NumT {
+    self.smoothed.pop_front();
+    self.smoothed.push_back(smoothed);
+
+    let n = (smooth_period + c(0.5)).to_usize().unwrap_or(0);
+    let (mut real, mut imag) = (NumT::zero(), NumT::zero());
+    for (i, v) in self.smoothed.iter().rev().take(n).enumerate() {
+      let angle = c::<NumT>(std::f64::consts::TAU) * c(i as f64) / c(n as f64);
+      real = real + angle.sin() * *v;
+      imag = imag + angle.cos() * *v;
+    }
+
+    if imag.abs() > NumT::zero() {
+      self.phase = (real / imag).atan().to_degrees();
+    } else if real < NumT::zero() {
+      self.phase = self.phase - c(90.0);
+    } else if real > NumT::zero() {
+      self.phase = self.phase + c(90.0);
+    }
+    self.phase = self.phase + c(90.0);
+    // compensate the one bar lag of the WMA
+    self.phase = self.phase + c::<NumT>(360.0) / smooth_period;
+    if imag < NumT::zero() {
+      self.phase = self.phase + c(180.0);
+    }
+    if self.phase > c(315.0) {
+      self.phase = self.phase - c(360.0);
+    }
+    self.phase
+  }
+
+  /// Smoothed price of the current bar
+  fn smoothed(&self) -> NumT {
+    self.smoothed[MAX_PERIOD - 1]
+  }
+}
+
+/// Instantaneous trendline, a 4 bar WMA of the mean price over the dominant
+/// cycle
+struct Trendline<NumT> {
+  itrend: [NumT; 3],
+}
+
+impl<NumT: Float> Trendline<NumT> {
+  fn new() -> Self {
+    Self {
+      itrend: [NumT::zero(); 3],
+    }
+  }
+
+  fn update(&mut self, core: &HilbertCore<NumT>, smooth_period: NumT) -> NumT {
+    let n = (smooth_period + c(0.5))
+      .to_usize()
+      .unwrap_or(0)
+      .min(core.prices.len());
+    let mut mean = (0..n).fold(NumT::zero(), |s, i| s + core.price(i));
+    if n > 0 {
+      mean = mean / c(n as f64);
+    }
+    let [t1, t2, t3] = self.itrend;
+    let v = (c::<NumT>(4.0) * mean + c::<NumT>(3.0) * t1 + c::<NumT>(2.0) * t2 + t3) * c(0.1);
+    self.itrend = [mean, t1, t2];
+    v
+  }
+}
+
+/// Hilbert Transform - Dominant Cycle Period
+///
+/// Ehlers' homodyne discriminator on a 4 bar WMA of the input, the period is
+/// limited to [6, 50] bars and smoothed. First 32 bars are NaN as TA-Lib.
+///
+/// Ref: John F. Ehlers, Rocket Science for Traders, chapter 7
+pub fn ta_ht_dcperiod<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+) -> Result<(), Error> {
+  run_bars(ctx, [r], [input], HilbertCore::new, |core, [x]| {
+    let step = core.update(x)?;
+    (core.today() >= LOOKBACK).then_some([step.smooth_period])
+  })
+}
+
+/// Hilbert Transform - Dominant Cycle Phase
+///
+/// Phase in degrees of the smoothed input over the last dominant cycle,
+/// within (-45, 315]. First 63 bars are NaN as TA-Lib.
+///
+/// Ref: John F. Ehlers, Rocket Science for Traders, chapter 9
+pub fn ta_ht_dcphase<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+) -> Result<(), Error> {
+  run_bars(
+    ctx,
+    [r],
+    [input],
+    || (HilbertCore::new(), Phase::new()),
+    |(core, phase), [x]| {
+      let step = core.update(x)?;
+      let v = phase.update(step.smoothed, step.smooth_period);
+      (core.today() >= PHASE_LOOKBACK).then_some([v])
+    },
+  )
+}
+
+/// Hilbert Transform - Phasor Components
+///
+/// In-phase and quadrature components of the detrended smoothed input. First
+/// 32 bars are NaN as TA-Lib.
+///
+/// Ref: John F. Ehlers, Rocket Science for Traders, chapter 7
+pub fn ta_ht_phasor<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  in_phase: &mut [NumT],
+  quadrature: &mut [NumT],
+  input: &[NumT],
+) -> Result<(), Error> {
+  run_bars(
+    ctx,
+    [in_phase, quadrature],
+    [input],
+    HilbertCore::new,
+    |core, [x]| {
+      let step = core.update(x)?;
+      (core.today() >= LOOKBACK).then_some([step.in_phase, step.quadrature])
+    },
+  )
+}
+
+/// Hilbert Transform - SineWave
+///
+/// sine = sin(DCPHASE), lead sine = sin(DCPHASE + 45), a crossing of the two
+/// lines marks a cycle turning point. First 63 bars are NaN as TA-Lib.
+///
+/// Ref: John F. Ehlers, Rocket Science for Traders, chapter 9
+pub fn ta_ht_sine<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  sine: &mut [NumT],
+  lead_sine: &mut [NumT],
+  input: &[NumT],
+) -> Result<(), Error> {
+  run_bars(
+    ctx,
+    [sine, lead_sine],
+    [input],
+    || (HilbertCore::new(), Phase::new()),
+    |(core, phase), [x]| {
+      let step = core.update(x)?;
+      let v = phase.update(step.smoothed, step.smooth_period);
+      (core.today() >= PHASE_LOOKBACK)
+        .then_some([v.to_radians().sin(), (v + c(45.0)).to_radians().sin()])
+    },
+  )
+}
+
+/// Hilbert Transform - Instantaneous Trendline
+///
+/// 4 bar WMA of the mean input over the dominant cycle. First 63 bars are NaN
+/// as TA-Lib.
+///
+/// Ref: John F. Ehlers, Rocket Science for Traders, chapter 10
+pub fn ta_ht_trendline<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+) -> Result<(), Error> {
+  run_bars(
+    ctx,
+    [r],
+    [input],
+    || (HilbertCore::new(), Trendline::new()),
+    |(core, trendline), [x]| {
+      let step = core.update(x)?;
+      let v = trendline.update(core, step.smooth_period);
+      (core.today() >= PHASE_LOOKBACK).then_some([v])
+    },
+  )
+}
+
+/// State of HT_TRENDMODE
+struct TrendMode<NumT> {
+  core: HilbertCore<NumT>,
+  phase: Phase<NumT>,
+  trendline: Trendline<NumT>,
+  sine: (NumT, NumT),
+  days_in_trend: usize,
+}
+
+impl<NumT: Float> TrendMode<NumT> {
+  fn update(&mut self, x: NumT) -> Option<NumT> {
+    let step = self.core.update(x)?;
+    let period = step.smooth_period;
+    let prev_phase = self.phase.phase;
+    let phase = self.phase.update(step.smoothed, period);
+    let trendline = self.trendline.update(&self.core, period);
+
+    let (prev_sine, prev_lead) = self.sine;
+    let sine = phase.to_radians().sin();
+    let lead = (phase + c(45.0)).to_radians().sin();
+    self.sine = (sine, lead);
+
+    let mut trend = true;
+    // days in trend since the last crossing of the sine wave lines
+    if (sine > lead && prev_sine <= prev_lead) || (sine < lead && prev_sine >= prev_lead) {
+      self.days_in_trend = 0;
+      trend = false;
+    }
+    self.days_in_trend += 1;
+    if c::<NumT>(self.days_in_trend as f64) < c::<NumT>(0.5) * period {
+      trend = false;
+    }
+    // cycle mode while the phase advances at the dominant cycle rate
+    let delta = phase - prev_phase;
+    if period != NumT::zero()
+      && delta > c::<NumT>(0.67 * 360.0) / period
+      && delta < c::<NumT>(1.5 * 360.0) / period
+    {
+      trend = false;
+    }
+    // trend mode when prices are far from the trendline
+    let smoothed = self.phase.smoothed();
+    if trendline != NumT::zero() && ((smoothed - trendline) / trendline).abs() >= c(0.015) {
+      trend = true;
+    }
+
+    (self.core.today() >= PHASE_LOOKBACK).then(|| if trend { NumT::one() } else { NumT::zero() })
+  }
+}
+
+/// Hilbert Transform - Trend vs Cycle Mode
+///
+/// 1 in trend mode and 0 in cycle mode, from the sine wave crossings, the
+/// phase rate of change and the distance to the instantaneous trendline.
+/// First 63 bars are NaN as TA-Lib.
+///
+/// Ref: John F. Ehlers, Rocket Science for Traders, chapter 10
+pub fn ta_ht_trendmode<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+) -> Result<(), Error> {
+  run_bars(
+    ctx,
+    [r],
+    [input],
+    || TrendMode {
+      core: HilbertCore::new(),
+      phase: Phase::new(),
+      trendline: Trendline::new(),
+      sine: (NumT::zero(), NumT::zero()),
+      days_in_trend: 0,
+    },
+    |s, [x]| s.update(x).map(|v| [v]),
+  )
+}
+
+/// MESA Adaptive Moving Average
+///
+/// alpha = fast_limit / delta phase clamped to [slow_limit, fast_limit],
+/// MAMA = alpha * input + (1 - alpha) * MAMA[-1] and
+/// FAMA = alpha / 2 * MAMA + (1 - alpha / 2) * FAMA[-1]. Usually
+/// fast_limit = 0.5 and slow_limit = 0.05, first 32 bars are NaN as TA-Lib.
+///
+/// Ref: https://www.mesasoftware.com/papers/MAMA.pdf
+pub fn ta_mama<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  mama: &mut [NumT],
+  fama: &mut [NumT],
+  input: &[NumT],
+  fast_limit: NumT,
+  slow_limit: NumT,
+) -> Result<(), Error> {
+  if !(slow_limit > NumT::zero() && slow_limit <= fast_limit && fast_limit <= NumT::one()) {
+    return Err(Error::InvalidParameter(
+      "mama requires 0 < slow_limit <= fast_limit <= 1".to_string(),
+    ));
+  }
+
+  let zero = NumT::zero();
+  run_bars(
+    ctx,
+    [mama, fama],
+    [input],
+    || (HilbertCore::new(), zero, zero, zero),
+    |(core, prev_phase, mama, fama), [x]| {
+      let step = core.update(x)?;
+      let phase = if step.in_phase != zero {
+        (step.quadrature / step.in_phase).atan().to_degrees()
+      } else {
+        zero
+      };
+      let delta = (*prev_phase - phase).max(NumT::one());
+      *prev_phase = phase;
+      let alpha = if delta > NumT::one() {
+        (fast_limit / delta).max(slow_limit)
+      } else {
+        fast_limit
+      };
+      *mama = alpha * x + (NumT::one() - alpha) * *mama;
+      let half = alpha * c(0.5);
+      *fama = half * *mama + (NumT::one() - half) * *fama;
+      (core.today() >= LOOKBACK).then_some([*mama, *fama])
+    },
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::algo::context::FLAG_SKIP_NAN;
+
+  fn sine_wave(n: usize, period: f64) -> Vec<f64> {
+    (0..n)
+      .map(|i| 100.0 + 5.0 * (std::f64::consts::TAU * i as f64 / period).sin())
+      .collect()
+  }
+
+  #[test]
+  fn test_dcperiod_phasor() {
+    let input = sine_wave(400, 20.0);
+    let mut r = vec![0.0; 400];
+    let ctx = Context::new(0, 1, 0);
+    ta_ht_dcperiod(&ctx, &mut r, &input).unwrap();
+    assert!(r[..LOOKBACK].iter().all(|v| v.is_nan()));
+    assert!(r[LOOKBACK].is_finite());
+    // locks on the cycle
+    assert!(
+      r[200..].iter().all(|v| (v - 20.0).abs() < 1.5),
+      "{:?}",
+      &r[200..]
+    );
+
+    let mut q = vec![0.0; 400];
+    ta_ht_phasor(&ctx, &mut r, &mut q, &input).unwrap();
+    assert!(r[LOOKBACK - 1].is_nan() && q[LOOKBACK].is_finite());
+  }
+
+  #[test]
+  fn test_phase_sine_trendmode() {
+    let input = sine_wave(400, 20.0);
+    let mut r = vec![0.0; 400];
+    let mut lead = vec![0.0; 400];
+    let ctx = Context::new(0, 1, 0);
+
+    ta_ht_dcphase(&ctx, &mut r, &input).unwrap();
+    assert!(r[PHASE_LOOKBACK - 1].is_nan());
+    assert!(
+      r[PHASE_LOOKBACK..]
+        .iter()
+        .all(|v| *v > -45.0 && *v <= 315.0)
+    );
+
+    ta_ht_sine(&ctx, &mut r, &mut lead, &input).unwrap();
+    assert!(r[PHASE_LOOKBACK..].iter().all(|v| v.abs() <= 1.0));
+
+    // a pure cycle close to its trendline is mostly in cycle mode, a steady
+    // rise in trend mode
+    let cycle: Vec<f64> = input.iter().map(|v| 100.0 + (v - 100.0) / 5.0).collect();
+    ta_ht_trendmode(&ctx, &mut r, &cycle).unwrap();
+    let trend: f64 = r[200..].iter().sum();
+    assert!(trend < 0.5 * 200.0, "{}", trend);
+    let rise: Vec<f64> = (0..400).map(|i| 100.0 * 1.01f64.powi(i)).collect();
+    ta_ht_trendmode(&ctx, &mut r, &rise).unwrap();
+    assert!(r[200..].iter().all(|v| *v == 1.0));
+
+    // the trendline follows the level of the cycle
+    ta_ht_trendline(&ctx, &mut r, &input).unwrap();
+    assert!(r[200..].iter().all(|v| (v - 100.0).abs() < 1.0));
+  }
+
+  #[test]
+  fn test_mama() {
+    let input = sine_wave(400, 20.0);
+    let mut mama = vec![0.0; 400];
+    let mut fama = vec![0.0; 400];
+    let ctx = Context::new(0, 1, 0);
+    ta_mama(&ctx, &mut mama, &mut fama, &input, 0.5, 0.05).unwrap();
+    assert!(mama[LOOKBACK - 1].is_nan() && fama[LOOKBACK].is_finite());
+    assert!(mama[200..].iter().all(|v| (v - 100.0).abs() < 5.0));
+    assert!(fama[200..].iter().all(|v| (v - 100.0).abs() < 2.0));
+    assert!(ta_mama(&ctx, &mut mama, &mut fama, &input, 0.05, 0.5).is_err());
+
+    // a missing bar is skipped, the warm-up counts valid bars only
+    let mut input = input;
+    input[10] = f64::NAN;
+    let ctx = Context::new(0, 1, FLAG_SKIP_NAN);
+    ta_mama(&ctx, &mut mama, &mut fama, &input, 0.5, 0.05).unwrap();
+    assert!(mama[LOOKBACK].is_nan() && mama[LOOKBACK + 1].is_finite());
+  }
+}
@@ -0,0 +1,436 @@
+// Copyright 2026 MSD-RS Project LiJia
+// SPDX-License-Identifier: BSD-2-Clause
+
+use num_traits::Float;
+use rayon::prelude::*;
+
+use crate::algo::{Context, Error, RANK_AVERAGE, is_normal, rank::rank_items};
+
+/// Pearson correlation of the pairs, NaN if fewer than 2 pairs or zero variance
+fn pearson<NumT: Float>(pairs: &[(NumT, NumT)]) -> NumT {
+  if pairs.len() < 2 {
+    return NumT::nan();
+  }
+  let n = NumT::from(pairs.len()).unwrap();
+  let (sx, sy) = pairs
+    .iter()
+    .fold((NumT::zero(), NumT::zero()), |(sx, sy), &(x, y)| {
+      (sx + x, sy + y)
+    });
+  let (mx, my) = (sx / n, sy / n);
+  let mut cov = NumT::zero();
+  let mut vx = NumT::zero();
+  let mut vy = NumT::zero();
+  for &(x, y) in pairs {
+    let dx = x - mx;
+    let dy = y - my;
+    cov = cov + dx * dy;
+    vx = vx + dx * dx;
+    vy = vy + dy * dy;
+  }
+  if vx <= NumT::zero() || vy <= NumT::zero() {
+    return NumT::nan();
+  }
+  cov / (vx * vy).sqrt()
+}
+
+/// Spearman correlation of the pairs, ties get the average rank
+fn spearman<NumT: Float>(pairs: &[(NumT, NumT)]) -> NumT {
+  let n = pairs.len();
+  let mut rx = vec![NumT::zero(); n];
+  let mut ry = vec![NumT::zero(); n];
+  let mut items: Vec<(NumT, usize)> = pairs.iter().enumerate().map(|(k, p)| (p.0, k)).collect();
+  rank_items(&mut items, RANK_AVERAGE, &mut rx);
+  let mut items: Vec<(NumT, usize)> = pairs.iter().enumerate().map(|(k, p)| (p.1, k)).collect();
+  rank_items(&mut items, RANK_AVERAGE, &mut ry);
+  let ranks: Vec<(NumT, NumT)> = rx.into_iter().zip(ry).collect();
+  pearson(&ranks)
+}
+
+/// Correlation across securities at date `j` between `a` and `b(idx)`,
+/// securities with NaN in either value are excluded
+fn cross_corr<NumT: Float>(
+  a: &[NumT],
+  b: impl Fn(usize) -> NumT,
+  groups: usize,
+  group_size: usize,
+  j: usize,
+  rank: bool,
+) -> NumT {
+  let mut pairs = Vec::with_capacity(groups);
+  for i in 0..groups {
+    let idx = i * group_size + j;
+    let x = a[idx];
+    let y = b(idx);
+    if is_normal(&x) && is_normal(&y) {
+      pairs.push((x, y));
+    }
+  }
+  if rank {
+    spearman(&pairs)
+  } else {
+    pearson(&pairs)
+  }
+}
+
+fn ic_impl<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  factor: &[NumT],
+  returns: &[NumT],
+  rank: bool,
+) -> Result<(), Error> {
+  if r.len() != factor.len() || r.len() != returns.len() {
+    return Err(Error::LengthMismatch(r.len(), factor.len()));
+  }
+
+  let group_size = ctx.chunk_size(r.len());
+  let groups = ctx.groups();
+  if r.len() != group_size * groups {
+    return Err(Error::LengthMismatch(r.len(), group_size * groups));
+  }
+
+  let ic: Vec<NumT> = (0..group_size)
+    .into_par_iter()
+    .map(|j| cross_corr(factor, |idx| returns[idx], groups, group_size, j, rank))
+    .collect();
+
+  r.par_chunks_mut(group_size)
+    .for_each(|r| r.copy_from_slice(&ic));
+
+  Ok(())
+}
+
+/// Information Coefficient (IC)
+///
+/// Pearson correlation between factor and returns across securities at each
+/// date, securities with NaN in either panel are excluded. The IC of a date is
+/// written to every security of that date, NaN if fewer than 2 valid pairs.
+/// `returns` is usually the forward return, e.g. the output of `FRET`.
+pub fn ta_ic<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  factor: &[NumT],
+  returns: &[NumT],
+) -> Result<(), Error> {
+  ic_impl(ctx, r, factor, returns, false)
+}
+
+/// Rank Information Coefficient (RankIC)
+///
+/// Spearman correlation between factor and returns across securities at each
+/// date, ties get the average rank. Same layout and NaN handling as `IC`.
+pub fn ta_rank_ic<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  factor: &[NumT],
+  returns: &[NumT],
+) -> Result<(), Error> {
+  ic_impl(ctx, r, factor, returns, true)
+}
+
+/// Factor rank autocorrelation
+///
+/// Spearman correlation between the cross-section of `input` at each date and
+/// the cross-section `lag` dates before, securities with NaN at either date
+/// are excluded. Low values mean the factor ranking changes quickly (high
+/// turnover). The value of a date is written to every security of that date.
+pub fn ta_rank_autocorr<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+  lag: usize,
+) -> Result<(), Error> {
+  if r.len() != input.len() {
+    return Err(Error::LengthMismatch(r.len(), input.len()));
+  }
+  if lag == 0 {
+    return Err(Error::InvalidPeriod("lag must be >= 1".to_string()));
+  }
+
+  let group_size = ctx.chunk_size(r.len());
+  let groups = ctx.groups();
+  if r.len() != group_size * groups {
+    return Err(Error::LengthMismatch(r.len(), group_size * groups));
+  }
+
+  let ac: Vec<NumT> = (0..group_size)
+    .into_par_iter()
+    .map(|j| {
+      if j < lag {
+        return NumT::nan();
+      }
+      cross_corr(input, |idx| input[idx - lag], groups, group_size, j, true)
+    })
+    .collect();
+
+  r.par_chunks_mut(group_size)
+    .for_each(|r| r.copy_from_slice(&ac));
+
+  Ok(())
+}
+
+/// IC summary statistics
+///
+/// Rolling statistics of an IC series (e.g. output of `IC` or `RANK_IC`) over
+/// the last `periods` values, `periods == 0` means expanding window, NaN
+/// values are skipped. Outputs:
+/// mean: mean IC
+/// icir: mean IC / std of IC (sample std)
+/// tstat: icir * sqrt(count)
+/// hit_rate: ratio of IC > 0
+pub fn ta_ic_summary<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  mean: &mut [NumT],
+  icir: &mut [NumT],
+  tstat: &mut [NumT],
+  hit_rate: &mut [NumT],
+  ic: &[NumT],
+  periods: usize,
+) -> Result<(), Error> {
+  for len in [icir.len(), tstat.len(), hit_rate.len(), ic.len()] {
+    if mean.len() != len {
+      return Err(Error::LengthMismatch(mean.len(), len));
+    }
+  }
+
+  let chunk = ctx.chunk_size(ic.len());
+  mean
+    .par_chunks_mut(chunk)
+    .zip(icir.par_chunks_mut(chunk))
+    .zip(tstat.par_chunks_mut(chunk))
+    .zip(hit_rate.par_chunks_mut(chunk))
+    .zip(ic.par_chunks(chunk))
+    .for_each(|((((mean, icir), tstat), hit_rate), ic)| {
+      let start = ctx.start(ic.len());
+      let end = ctx.end(ic.len());
+      mean.fill(NumT::nan());
+      icir.fill(NumT::nan());
+      tstat.fill(NumT::nan());
+      hit_rate.fill(NumT::nan());
+
+      let mut sum = NumT::zero();
+      let mut sum_sq = NumT::zero();
+      let mut hits = 0usize;
+      let mut count = 0usize;
+      let first = if periods == 0 {
+        0
+      } else {
+        start.saturating_sub(periods - 1)
+      };
+      for i in first..end {
+        let x = ic[i];
+        if is_normal(&x) {
+          sum = sum + x;
+          sum_sq = sum_sq + x * x;
+          count += 1;
+          if x > NumT::zero() {
+            hits += 1;
+          }
+        }
+        if periods != 0 && i >= first + periods {
+          let x = ic[i - periods];
+          if is_normal(&x) {
+            sum = sum - x;
+            sum_sq = sum_sq - x * x;
+            count -= 1;
+            if x > NumT::zero() {
+              hits -= 1;
+            }
+          }
+        }
+        if i < start || count == 0 {
+          continue;
+        }
+        if ctx.is_strictly_cycle() && periods != 0 && i + 1 < periods {
+          continue;
+        }
+
+        let n = NumT::from(count).unwrap();
+        let m = sum / n;
+        mean[i] = m;
+        hit_rate[i] = NumT::from(hits).unwrap() / n;
+        if count > 1 {
+          let var = (sum_sq - sum * m) / (n - NumT::one());
+          if var > NumT::zero() {
+            let ir = m / var.sqrt();
+            icir[i] = ir;
+            tstat[i] = ir * n.sqrt();
+          }
+        }
+      }
+    });
+
+  Ok(())
+}
+
+fn ic_decay_impl<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  factor: &[NumT],
+  price: &[NumT],
+  horizons: usize,
+  rank: bool,
+) -> Result<(), Error> {
+  if factor.len() != price.len() {
+    return Err(Error::LengthMismatch(factor.len(), price.len()));
+  }
+
+  let group_size = ctx.chunk_size(factor.len());
+  let groups = ctx.groups();
+  if factor.len() != group_size * groups {
+    return Err(Error::LengthMismatch(factor.len(), group_size * groups));
+  }
+  if horizons == 0 {
+    return Err(Error::InvalidParameter("horizons must be >= 1".to_string()));
+  }
+  if r.len() != horizons * group_size {
+    return Err(Error::LengthMismatch(r.len(), horizons * group_size));
+  }
+
+  r.par_chunks_mut(group_size).enumerate().for_each(|(h, r)| {
+    r.fill(NumT::nan());
+    let h = h + 1;
+    let fret = |idx: usize| price[idx + h] / price[idx] - NumT::one();
+    for (j, r) in r.iter_mut().enumerate().take(group_size.saturating_sub(h)) {
+      *r = cross_corr(factor, fret, groups, group_size, j, rank);
+    }
+  });
+
+  Ok(())
+}
+
+/// IC decay
+///
+/// IC between factor and forward returns `price[t+h] / price[t] - 1` for
+/// horizons `h = 1..=horizons`. `r` is the row-major (horizons, T) matrix,
+/// `horizons * T` values, row `h-1` holds the IC series of horizon `h`.
+pub fn ta_ic_decay<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  factor: &[NumT],
+  price: &[NumT],
+  horizons: usize,
+) -> Result<(), Error> {
+  ic_decay_impl(ctx, r, factor, price, horizons, false)
+}
+
+/// RankIC decay
+///
+/// Same as `IC_DECAY` but uses Spearman correlation.
+pub fn ta_rank_ic_decay<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  factor: &[NumT],
+  price: &[NumT],
+  horizons: usize,
+) -> Result<(), Error> {
+  ic_decay_impl(ctx, r, factor, price, horizons, true)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::algo::assert_vec_eq_nan;
+
+  #[test]
+  fn test_ic() {
+    // 3 securities, 2 dates
+    let ctx = Context::new(0, 3, 0);
+    let factor = [1.0, 1.0, 2.0, 2.0, 3.0, f64::NAN];
+    let returns = [0.1, 0.3, 0.2, 0.1, 0.3, 0.2];
+    let mut r = [0.0; 6];
+    ta_ic(&ctx, &mut r, &factor, &returns).unwrap();
+    // date 0: perfect correlation, date 1: 2 pairs (1,0.3),(2,0.1)
+    assert_vec_eq_nan(&r, &[1.0, -1.0, 1.0, -1.0, 1.0, -1.0]);
+
+    let factor = [1.0, 1.0, 2.0, 2.0, 30.0, 3.0];
+    let returns = [0.1, 0.1, 0.2, 0.3, 0.3, 0.2];
+    ta_rank_ic(&ctx, &mut r, &factor, &returns).unwrap();
+    assert_vec_eq_nan(&r, &[1.0, 0.5, 1.0, 0.5, 1.0, 0.5]);
+  }
+
+  #[test]
+  fn test_rank_autocorr() {
+    // 3 securities, 3 dates
+    let ctx = Context::new(0, 3, 0);
+    let input = [1.0, 1.0, 3.0, 2.0, 2.0, 2.0, 3.0, 3.0, 1.0];
+    let mut r = [0.0; 9];
+    ta_rank_autocorr(&ctx, &mut r, &input, 1).unwrap();
+    assert_vec_eq_nan(
+      &r,
+      &[
+        f64::NAN,
+        1.0,
+        -1.0,
+        f64::NAN,
+        1.0,
+        -1.0,
+        f64::NAN,
+        1.0,
+        -1.0,
+      ],
+    );
+
+    ta_rank_autocorr(&ctx, &mut r, &input, 2).unwrap();
+    assert_vec_eq_nan(
+      &r,
+      &[
+        f64::NAN,
+        f64::NAN,
+        -1.0,
+        f64::NAN,
+        f64::NAN,
+        -1.0,
+        f64::NAN,
+        f64::NAN,
+        -1.0,
+      ],
+    );
+  }
+
+  #[test]
+  fn test_ic_summary() {
+    let ctx = Context::new(0, 1, 0);
+    let ic = [0.1, -0.1, f64::NAN, 0.3];
+    let mut mean = [0.0; 4];
+    let mut icir = [0.0; 4];
+    let mut tstat = [0.0; 4];
+    let mut hit = [0.0; 4];
+    ta_ic_summary(&ctx, &mut mean, &mut icir, &mut tstat, &mut hit, &ic, 0).unwrap();
+    let std =
+      (((0.1f64 - 0.1).powi(2) + (-0.1f64 - 0.1).powi(2) + (0.3f64 - 0.1).powi(2)) / 2.0).sqrt();
+    assert_vec_eq_nan(&mean, &[0.1, 0.0, 0.0, 0.1]);
+    assert_vec_eq_nan(&hit, &[1.0, 0.5, 0.5, 2.0 / 3.0]);
+    assert_vec_eq_nan(&icir, &[f64::NAN, 0.0, 0.0, 0.1 / std]);
+    assert_vec_eq_nan(&tstat, &[f64::NAN, 0.0, 0.0, 0.1 / std * 3f64.sqrt()]);
+
+    ta_ic_summary(&ctx, &mut mean, &mut icir, &mut tstat, &mut hit, &ic, 2).unwrap();
+    assert_vec_eq_nan(&mean, &[0.1, 0.0, -0.1, 0.3]);
+    assert_vec_eq_nan(&hit, &[1.0, 0.5, 0.0, 1.0]);
+  }
+
+  #[test]
+  fn test_ic_decay() {
+    // 2 securities, 3 dates
+    let ctx = Context::new(0, 2, 0);
+    let factor = [1.0, 1.0, 1.0, 2.0, 2.0, 2.0];
+    let price = [10.0, 11.0, 10.0, 10.0, 10.0, 12.0];
+    let nan = f64::NAN;
+    let mut r = [0.0; 6];
+    ta_ic_decay(&ctx, &mut r, &factor, &price, 2).unwrap();
+    assert_vec_eq_nan(&r, &[-1.0, 1.0, nan, 1.0, nan, nan]);
+
+    let mut r = [0.0; 3];
+    ta_rank_ic_decay(&ctx, &mut r, &factor, &price, 1).unwrap();
+    assert_vec_eq_nan(&r, &[-1.0, 1.0, nan]);
+
+    // more horizons than securities
+    let mut r = [0.0; 9];
+    ta_ic_decay(&ctx, &mut r, &factor, &price, 3).unwrap();
+    assert_vec_eq_nan(&r, &[-1.0, 1.0, nan, 1.0, nan, nan, nan, nan, nan]);
+
+    assert!(ta_ic_decay(&ctx, &mut r, &factor, &price, 2).is_err());
+    assert!(ta_ic_decay(&ctx, &mut r, &factor, &price, 0).is_err());
+  }
+}
@@ -0,0 +1,375 @@
+// Copyright 2026 MSD-RS Project LiJia
+// SPDX-License-Identifier: BSD-2-Clause
+
+use num_traits::Float;
+use rayon::prelude::*;
+
+use crate::algo::{Context, Error, is_normal};
+
+/// Prior variance of the regression state before the first observation
+const KALMAN_DIFFUSE: f64 = 1e6;
+
+/// Kalman filter with a 2 dimensional state and scalar observations
+struct Kalman2<NumT> {
+  x: [NumT; 2],
+  p: [[NumT; 2]; 2],
+}
+
+impl<NumT: Float> Kalman2<NumT> {
+  /// x = F x, P = F P F' + Q with F = [[1, f], [0, 1]] and Q diagonal
+  fn predict(&mut self, f: NumT, q: [NumT; 2]) {
+    let p = self.p;
+    self.x[0] = self.x[0] + f * self.x[1];
+    self.p[0][0] = p[0][0] + f * (p[0][1] + p[1][0]) + f * f * p[1][1] + q[0];
+    self.p[0][1] = p[0][1] + f * p[1][1];
+    self.p[1][0] = self.p[0][1];
+    self.p[1][1] = p[1][1] + q[1];
+  }
+
+  /// Observe y = h x + e, var(e) = r
+  fn update(&mut self, h: [NumT; 2], y: NumT, r: NumT) {
+    let ph = [
+      self.p[0][0] * h[0] + self.p[0][1] * h[1],
+      self.p[1][0] * h[0] + self.p[1][1] * h[1],
+    ];
+    let s = h[0] * ph[0] + h[1] * ph[1] + r;
+    let k = [ph[0] / s, ph[1] / s];
+    let e = y - (h[0] * self.x[0] + h[1] * self.x[1]);
+    self.x[0] = self.x[0] + k[0] * e;
+    self.x[1] = self.x[1] + k[1] * e;
+    // P = P - K (H P), symmetric
+    self.p[0][0] = self.p[0][0] - k[0] * ph[0];
+    self.p[0][1] = self.p[0][1] - k[0] * ph[1];
+    self.p[1][0] = self.p[0][1];
+    self.p[1][1] = self.p[1][1] - k[1] * ph[1];
+  }
+}
+
+fn check_noise<NumT: Float>(process: &[NumT], obs_var: NumT) -> Result<(), Error> {
+  let invalid = |v: NumT| v.is_nan() || v < NumT::zero();
+  if process.iter().any(|q| invalid(*q)) || invalid(obs_var) || obs_var == NumT::zero() {
+    return Err(Error::InvalidParameter(
+      "process noise must be >= 0 and observation noise > 0".to_string(),
+    ));
+  }
+  Ok(())
+}
+
+/// Kalman Filter Time-Varying Regression
+///
+/// input = intercept + beta * benchmark + e, var(e) = obs_var, intercept and
+/// beta follow random walks with variance `delta` per bar. Outputs the filtered
+/// beta, intercept and their variances given data up to t, starting from a
+/// diffuse prior.
+///
+/// A bar with either value missing only adds process noise, FLAG_SKIP_NAN
+/// skips it entirely, outputs are NaN on such bars.
+///
+/// Ref: https://en.wikipedia.org/wiki/Kalman_filter
+#[allow(clippy::too_many_arguments)]
+pub fn ta_kalman_beta<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  beta: &mut [NumT],
+  intercept: &mut [NumT],
+  beta_var: &mut [NumT],
+  intercept_var: &mut [NumT],
+  input: &[NumT],
+  benchmark: &[NumT],
+  delta: NumT,
+  obs_var: NumT,
+) -> Result<(), Error> {
+  let len = input.len();
+  for out in [
+    beta.len(),
+    intercept.len(),
+    beta_var.len(),
+    intercept_var.len(),
+    benchmark.len(),
+  ] {
+    if out != len {
+      return Err(Error::LengthMismatch(out, len));
+    }
+  }
+  check_noise(&[delta], obs_var)?;
+
+  let chunk = ctx.chunk_size(len);
+  beta
+    .par_chunks_mut(chunk)
+    .zip(intercept.par_chunks_mut(chunk))
+    .zip(beta_var.par_chunks_mut(chunk))
+    .zip(intercept_var.par_chunks_mut(chunk))
+    .zip(input.par_chunks(chunk))
+    .zip(benchmark.par_chunks(chunk))
+    .for_each(|(((((b, a), bv), av), y), x)| {
+      let start = ctx.start(b.len());
+      let end = ctx.end(b.len());
+      for r in [&mut *b, &mut *a, &mut *bv, &mut *av] {
+        r.fill(NumT::nan());
+      }
+
+      let diffuse = NumT::from(KALMAN_DIFFUSE).unwrap();
+      // state is [intercept, beta]
+      let mut kf = Kalman2 {
+        x: [NumT::zero(); 2],
+        p: [[diffuse, NumT::zero()], [NumT::zero(), diffuse]],
+      };
+      let mut started = false;
+      for i in 0..end {
+        let valid = is_normal(&y[i]) && is_normal(&x[i]);
+        if !valid && ctx.is_skip_nan() {
+          continue;
+        }
+        if started {
+          kf.predict(NumT::zero(), [delta, delta]);
+        }
+        if !valid {
+          continue;
+        }
+        started = true;
+        kf.update([NumT::one(), x[i]], y[i], obs_var);
+
+        if i >= start {
+          a[i] = kf.x[0];
+          b[i] = kf.x[1];
+          av[i] = kf.p[0][0];
+          bv[i] = kf.p[1][1];
+        }
+      }
+    });
+
+  Ok(())
+}
+
+/// Kalman Filter Local Level
+///
+/// input = level + e, var(e) = obs_var, level follows a random walk with
+/// variance `process_var` per bar. Outputs the filtered level and its variance,
+/// the first valid value initializes the level with variance obs_var.
+///
+/// A missing value only adds process noise, FLAG_SKIP_NAN skips it entirely,
+/// outputs are NaN on such bars.
+pub fn ta_kalman_level<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  level: &mut [NumT],
+  level_var: &mut [NumT],
+  input: &[NumT],
+  process_var: NumT,
+  obs_var: NumT,
+) -> Result<(), Error> {
+  let len = input.len();
+  for out in [level.len(), level_var.len()] {
+    if out != len {
+      return Err(Error::LengthMismatch(out, len));
+    }
+  }
+  check_noise(&[process_var], obs_var)?;
+
+  let chunk = ctx.chunk_size(len);
+  level
+    .par_chunks_mut(chunk)
+    .zip(level_var.par_chunks_mut(chunk))
+    .zip(input.par_chunks(chunk))
+    .for_each(|((l, lv), y)| {
+      let start = ctx.start(l.len());
+      let end = ctx.end(l.len());
+      l.fill(NumT::nan());
+      lv.fill(NumT::nan());
+
+      let mut x = NumT::nan();
+      let mut p = NumT::nan();
+      for i in 0..end {
+        let valid = is_normal(&y[i]);
+        if !valid && ctx.is_skip_nan() {
+          continue;
+        }
+        p = p + process_var;
+        if !valid {
+          continue;
+        }
+        if x.is_nan() {
+          x = y[i];
+          p = obs_var;
+        } else {
+          let k = p / (p + obs_var);
+          x = x + k * (y[i] - x);
+          p = (NumT::one() - k) * p;
+        }
+
+        if i >= start {
+          l[i] = x;
+          lv[i] = p;
+        }
+      }
+    });
+
+  Ok(())
+}
+
+/// Kalman Filter Local Linear Trend
+///
+/// input = level + e, var(e) = obs_var, level[t] = level[t - 1] + slope[t - 1]
+/// plus noise of variance `level_noise`, slope follows a random walk with
+/// variance `slope_noise`. Outputs the filtered level, slope and their
+/// variances, the state is initialized from the first two valid values.
+///
+/// A missing value only advances the state, FLAG_SKIP_NAN skips it entirely,
+/// outputs are NaN on such bars.
+#[allow(clippy::too_many_arguments)]
+pub fn ta_kalman_trend<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  level: &mut [NumT],
+  slope: &mut [NumT],
+  level_var: &mut [NumT],
+  slope_var: &mut [NumT],
+  input: &[NumT],
+  level_noise: NumT,
+  slope_noise: NumT,
+  obs_var: NumT,
+) -> Result<(), Error> {
+  let len = input.len();
+  for out in [level.len(), slope.len(), level_var.len(), slope_var.len()] {
+    if out != len {
+      return Err(Error::LengthMismatch(out, len));
+    }
+  }
+  check_noise(&[level_noise, slope_noise], obs_var)?;
+
+  let chunk = ctx.chunk_size(len);
+  level
+    .par_chunks_mut(chunk)
+    .zip(slope.par_chunks_mut(chunk))
+    .zip(level_var.par_chunks_mut(chunk))
+    .zip(slope_var.par_chunks_mut(chunk))
+    .zip(input.par_chunks(chunk))
+    .for_each(|((((l, s), lv), sv), y)| {
+      let start = ctx.start(l.len());
+      let end = ctx.end(l.len());
+      for r in [&mut *l, &mut *s, &mut *lv, &mut *sv] {
+        r.fill(NumT::nan());
+      }
+
+      let q = [level_noise, slope_noise];
+      let mut kf: Option<Kalman2<NumT>> = None;
+      // first valid value and the number of bars since it
+      let mut first: Option<(NumT, usize)> = None;
+      for i in 0..end {
+        let valid = is_normal(&y[i]);
+        if !valid && ctx.is_skip_nan() {
+          continue;
+        }
+        if let Some(kf) = kf.as_mut() {
+          kf.predict(NumT::one(), q);
+        } else if let Some((_, gap)) = first.as_mut() {
+          *gap += 1;
+        }
+        if !valid {
+          continue;
+        }
+
+        match (kf.as_mut(), first) {
+          (Some(kf), _) => kf.update([NumT::one(), NumT::zero()], y[i], obs_var),
+          (None, None) => {
+            first = Some((y[i], 0));
+            continue;
+          }
+          (None, Some((y0, gap))) => {
+            // diffuse limit after two observations
+            let k = NumT::from(gap).unwrap();
+            let two = NumT::from(2).unwrap();
+            kf = Some(Kalman2 {
+              x: [y[i], (y[i] - y0) / k],
+              p: [
+                [obs_var + level_noise, obs_var / k],
+                [obs_var / k, two * obs_var / (k * k) + slope_noise],
+              ],
+            });
+          }
+        }
+
+        if let (true, Some(kf)) = (i >= start, kf.as_ref()) {
+          l[i] = kf.x[0];
+          s[i] = kf.x[1];
+          lv[i] = kf.p[0][0];
+          sv[i] = kf.p[1][1];
+        }
+      }
+    });
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::algo::{assert_vec_eq_nan, context::FLAG_SKIP_NAN};
+
+  #[test]
+  fn test_kalman_level() {
+    let input = vec![1.0, 2.0, f64::NAN, 4.0];
+    let (q, r) = (0.5, 1.0);
+    let mut level = vec![0.0; 4];
+    let mut var = vec![0.0; 4];
+
+    let ctx = Context::new(0, 1, 0);
+    ta_kalman_level(&ctx, &mut level, &mut var, &input, q, r).unwrap();
+    // 0: level 1, var 1
+    // 1: p = 1.5, k = 0.6, level = 1.6, var = 0.6
+    // 3: p = 0.6 + 0.5 + 0.5 = 1.6, k = 1.6 / 2.6
+    let k = 1.6 / 2.6;
+    assert_vec_eq_nan(&level, &[1.0, 1.6, f64::NAN, 1.6 + k * 2.4]);
+    assert_vec_eq_nan(&var, &[1.0, 0.6, f64::NAN, (1.0 - k) * 1.6]);
+
+    let ctx = Context::new(0, 1, FLAG_SKIP_NAN);
+    ta_kalman_level(&ctx, &mut level, &mut var, &input, q, r).unwrap();
+    let k = 1.1 / 2.1;
+    assert!((level[3] - (1.6 + k * 2.4)).abs() < 1e-12);
+
+    assert!(ta_kalman_level(&ctx, &mut level, &mut var, &input, q, 0.0).is_err());
+  }
+
+  #[test]
+  fn test_kalman_trend() {
+    // a noiseless line is tracked exactly
+    let input: Vec<f64> = (0..20).map(|i| 3.0 + 0.5 * i as f64).collect();
+    let n = input.len();
+    let (mut l, mut s, mut lv, mut sv) = (vec![0.0; n], vec![0.0; n], vec![0.0; n], vec![0.0; n]);
+    let ctx = Context::new(0, 1, 0);
+    ta_kalman_trend(
+      &ctx, &mut l, &mut s, &mut lv, &mut sv, &input, 0.01, 0.001, 1.0,
+    )
+    .unwrap();
+
+    assert!(l[0].is_nan());
+    assert_eq!(l[1], 3.5);
+    assert_eq!(s[1], 0.5);
+    for i in 1..n {
+      assert!((l[i] - input[i]).abs() < 1e-9);
+      assert!((s[i] - 0.5).abs() < 1e-9);
+    }
+    // uncertainty shrinks with more data
+    assert!(sv[n - 1] < sv[1]);
+    assert!(lv[n - 1] < lv[1]);
+  }
+
+  #[test]
+  fn test_kalman_beta() {
+    let x: Vec<f64> = (0..200)
+      .map(|i| ((i * 37 % 17) as f64 - 8.0) / 100.0)
+      .collect();
+    // beta moves from 1 to 2 halfway
+    let y: Vec<f64> = x
+      .iter()
+      .enumerate()
+      .map(|(i, v)| 0.001 + if i < 100 { 1.0 } else { 2.0 } * v)
+      .collect();
+    let n = x.len();
+    let (mut b, mut a, mut bv, mut av) = (vec![0.0; n], vec![0.0; n], vec![0.0; n], vec![0.0; n]);
+    let ctx = Context::new(0, 1, 0);
+    ta_kalman_beta(&ctx, &mut b, &mut a, &mut bv, &mut av, &y, &x, 1e-4, 1e-6).unwrap();
+
+    assert!((b[99] - 1.0).abs() < 1e-3);
+    assert!((a[99] - 0.001).abs() < 1e-4);
+    assert!((b[n - 1] - 2.0).abs() < 0.05, "{}", b[n - 1]);
+    assert!(bv[n - 1] > 0.0 && av[n - 1] > 0.0);
+  }
+}
@@ -0,0 +1,476 @@
+// Copyright 2026 MSD-RS Project LiJia
+// SPDX-License-Identifier: BSD-2-Clause
+
+use std::collections::VecDeque;
+
+use num_traits::Float;
+
+use super::{bars::run_bars, ema::Ema};
+use crate::algo::{Context, Error};
+
+/// signal line smoothing: exponential moving average, alpha = 2 / (n + 1)
+pub const SIGNAL_EMA: usize = 0;
+/// signal line smoothing: simple moving average
+pub const SIGNAL_SMA: usize = 1;
+
+/// Signal line over the oscillator, EMA or SMA
+struct Signal<NumT> {
+  periods: usize,
+  ema: Option<Ema<NumT>>,
+  window: VecDeque<NumT>,
+  sum: NumT,
+  count: usize,
+}
+
+impl<NumT: Float> Signal<NumT> {
+  /// The EMA signal is seeded with the mean of the first `periods` values
+  /// when `sma_seeded` (TA-Lib), otherwise with the first value
+  fn new(periods: usize, signal_type: usize, sma_seeded: bool) -> Self {
+    let ema = || {
+      if sma_seeded {
+        Ema::sma_seeded(periods)
+      } else {
+        Ema::with_periods(periods)
+      }
+    };
+    Self {
+      periods,
+      ema: (signal_type == SIGNAL_EMA).then(ema),
+      window: VecDeque::with_capacity(periods),
+      sum: NumT::zero(),
+      count: 0,
+    }
+  }
+
+  fn update(&mut self, x: NumT) -> Option<NumT> {
+    self.count += 1;
+    if let Some(ema) = self.ema.as_mut() {
+      return ema.update(x);
+    }
+    if self.window.len() == self.periods
+      && let Some(old) = self.window.pop_front()
+    {
+      self.sum = self.sum - old;
+    }
+    self.window.push_back(x);
+    self.sum = self.sum + x;
+    Some(self.sum / NumT::from(self.window.len()).unwrap())
+  }
+}
+
+fn check_periods(name: &str, periods: &[usize]) -> Result<(), Error> {
+  if periods.contains(&0) {
+    return Err(Error::InvalidPeriod(format!(
+      "{} requires periods >= 1",
+      name
+    )));
+  }
+  Ok(())
+}
+
+/// Drive `line` and its signal line over each group
+///
+/// `line` returns the oscillator once warmed up, the signal line starts from
+/// its first value, see `Signal::new` for `sma_seeded`. Under
+/// FLAG_STRICTLY_CYCLE the signal and histogram are NaN until
+/// `signal_periods` oscillator values have been seen, the histogram is
+/// (line - signal) * `hist_scale`.
+#[allow(clippy::too_many_arguments)]
+fn run_signal<NumT, S, I, F>(
+  ctx: &Context,
+  line_out: &mut [NumT],
+  signal_out: &mut [NumT],
+  hist_out: &mut [NumT],
+  input: &[NumT],
+  signal_periods: usize,
+  signal_type: usize,
+  sma_seeded: bool,
+  hist_scale: NumT,
+  init: I,
+  line: F,
+) -> Result<(), Error>
+where
+  NumT: Float + Send + Sync,
+  I: Fn() -> S + Send + Sync,
+  F: Fn(&mut S, NumT) -> Option<NumT> + Send + Sync,
+{
+  if !matches!(signal_type, SIGNAL_EMA | SIGNAL_SMA) {
+    return Err(Error::InvalidParameter(format!(
+      "unknown signal type {}",
+      signal_type
+    )));
+  }
+
+  let strict = ctx.is_strictly_cycle();
+  run_bars(
+    ctx,
+    [line_out, signal_out, hist_out],
+    [input],
+    || (init(), Signal::new(signal_periods, signal_type, sma_seeded)),
+    |(state, signal), [x]| {
+      let v = line(state, x)?;
+      match signal.update(v) {
+        Some(s) if !strict || signal.count >= signal.periods => Some([v, s, (v - s) * hist_scale]),
+        _ => Some([v, NumT::nan(), NumT::nan()]),
+      }
+    },
+  )
+}
+
+/// Fast and slow EMA of the input
+struct FastSlow<NumT> {
+  fast: Ema<NumT>,
+  slow: Ema<NumT>,
+  /// bars before the fast and the slow EMA start
+  delay: (usize, usize),
+  /// bars before the first output
+  lookback: usize,
+  count: usize,
+}
+
+impl<NumT: Float> FastSlow<NumT> {
+  /// TA-Lib style, both EMAs are seeded with the simple mean of their
+  /// periods, the shorter one starts later so both seeds end on bar
+  /// max(fast, slow) - 1
+  fn sma_seeded(fast: usize, slow: usize) -> Self {
+    let lookback = fast.max(slow);
+    Self {
+      fast: Ema::sma_seeded(fast),
+      slow: Ema::sma_seeded(slow),
+      delay: (lookback - fast, lookback - slow),
+      lookback,
+      count: 0,
+    }
+  }
+
+  /// Both EMAs are seeded with the first value, the first max(fast, slow) - 1
+  /// outputs are skipped when `strict`
+  fn first_value(fast: usize, slow: usize, strict: bool) -> Self {
+    Self {
+      fast: Ema::with_periods(fast),
+      slow: Ema::with_periods(slow),
+      delay: (0, 0),
+      lookback: if strict { fast.max(slow) } else { 1 },
+      count: 0,
+    }
+  }
+
+  fn update(&mut self, x: NumT) -> Option<(NumT, NumT)> {
+    self.count += 1;
+    let f = (self.count > self.delay.0)
+      .then(|| self.fast.update(x))
+      .flatten();
+    let s = (self.count > self.delay.1)
+      .then(|| self.slow.update(x))
+      .flatten();
+    if self.count < self.lookback {
+      return None;
+    }
+    Some((f?, s?))
+  }
+}
+
+/// Moving Average Convergence / Divergence
+///
+/// MACD = EMA(input, fast) - EMA(input, slow), signal = EMA or SMA of MACD
+/// over `signal_periods` by `signal_type` (SIGNAL_EMA 0, SIGNAL_SMA 1) and
+/// histogram = MACD - signal. The EMAs are seeded with the simple mean of
+/// their first periods as TA-Lib, so the first max(fast, slow) - 1 MACD values
+/// and the first signal_periods - 1 EMA signal values after them are NaN,
+/// same as TA-Lib MACD after the lookback of max(fast, slow) + signal_periods - 2.
+///
+/// Ref: https://www.investopedia.com/terms/m/macd.asp
+#[allow(clippy::too_many_arguments)]
+pub fn ta_macd<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  macd: &mut [NumT],
+  signal: &mut [NumT],
+  hist: &mut [NumT],
+  input: &[NumT],
+  fast: usize,
+  slow: usize,
+  signal_periods: usize,
+  signal_type: usize,
+) -> Result<(), Error> {
+  check_periods("macd", &[fast, slow, signal_periods])?;
+
+  run_signal(
+    ctx,
+    macd,
+    signal,
+    hist,
+    input,
+    signal_periods,
+    signal_type,
+    true,
+    NumT::one(),
+    || FastSlow::sma_seeded(fast, slow),
+    |state, x| {
+      let (f, s) = state.update(x)?;
+      Some(f - s)
+    },
+  )
+}
+
+/// MACD of 通达信
+///
+/// DIF = EMA(input, short) - EMA(input, long), DEA = EMA(DIF, mid) and
+/// MACD = (DIF - DEA) * 2, as `MACD(SHORT, LONG, MID)` of 通达信, usually
+/// short = 12, long = 26 and mid = 9. The EMAs are seeded with the first
+/// value as 通达信, under FLAG_STRICTLY_CYCLE the first max(short, long) - 1
+/// DIF values and the first mid - 1 DEA values are NaN.
+#[allow(clippy::too_many_arguments)]
+pub fn ta_macd_tdx<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  dif: &mut [NumT],
+  dea: &mut [NumT],
+  macd: &mut [NumT],
+  input: &[NumT],
+  short: usize,
+  long: usize,
+  mid: usize,
+) -> Result<(), Error> {
+  check_periods("macd_tdx", &[short, long, mid])?;
+
+  let strict = ctx.is_strictly_cycle();
+  run_signal(
+    ctx,
+    dif,
+    dea,
+    macd,
+    input,
+    mid,
+    SIGNAL_EMA,
+    false,
+    NumT::from(2.0).unwrap(),
+    || FastSlow::first_value(short, long, strict),
+    |state, x| {
+      let (f, s) = state.update(x)?;
+      Some(f - s)
+    },
+  )
+}
+
+/// Percentage Price Oscillator
+///
+/// PPO = (EMA(input, fast) - EMA(input, slow)) / EMA(input, slow) * 100, with
+/// signal line and histogram as `MACD`, NaN when the slow EMA is 0. Same
+/// warm-up as `MACD`, same as TA-Lib PPO with matype = 1 (EMA).
+///
+/// Ref: https://www.investopedia.com/terms/p/ppo.asp
+#[allow(clippy::too_many_arguments)]
+pub fn ta_ppo<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  ppo: &mut [NumT],
+  signal: &mut [NumT],
+  hist: &mut [NumT],
+  input: &[NumT],
+  fast: usize,
+  slow: usize,
+  signal_periods: usize,
+  signal_type: usize,
+) -> Result<(), Error> {
+  check_periods("ppo", &[fast, slow, signal_periods])?;
+
+  let hundred = NumT::from(100.0).unwrap();
+  run_signal(
+    ctx,
+    ppo,
+    signal,
+    hist,
+    input,
+    signal_periods,
+    signal_type,
+    true,
+    NumT::one(),
+    || FastSlow::sma_seeded(fast, slow),
+    |state, x| {
+      let (f, s) = state.update(x)?;
+      if s == NumT::zero() {
+        // keep the signal line away from a NaN oscillator value
+        return None;
+      }
+      Some((f - s) / s * hundred)
+    },
+  )
+}
+
+/// Absolute Price Oscillator
+///
+/// APO = EMA(input, fast) - EMA(input, slow), the MACD line alone, the first
+/// max(fast, slow) - 1 values are NaN. Same as TA-Lib APO with matype = 1
+/// (EMA).
+///
+/// Ref: https://www.fidelity.com/learning-center/trading-investing/technical-analysis/technical-indicator-guide/apo
+pub fn ta_apo<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+  fast: usize,
+  slow: usize,
+) -> Result<(), Error> {
+  check_periods("apo", &[fast, slow])?;
+
+  run_bars(
+    ctx,
+    [r],
+    [input],
+    || FastSlow::sma_seeded(fast, slow),
+    |state, [x]| {
+      let (f, s) = state.update(x)?;
+      Some([f - s])
+    },
+  )
+}
+
+/// Triple Exponential Average
+///
+/// TRIX = (EMA3 - ref(EMA3, 1)) / ref(EMA3, 1) * 100 with EMA3 =
+/// EMA(EMA(EMA(input, periods), periods), periods), with signal line and
+/// histogram as `MACD`. Each EMA is seeded with the simple mean of its first
+/// `periods` values, so the first 3 * (periods - 1) + 1 values are NaN, same
+/// as TA-Lib TRIX.
+///
+/// Ref: https://www.investopedia.com/terms/t/trix.asp
+#[allow(clippy::too_many_arguments)]
+pub fn ta_trix<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  trix: &mut [NumT],
+  signal: &mut [NumT],
+  hist: &mut [NumT],
+  input: &[NumT],
+  periods: usize,
+  signal_periods: usize,
+  signal_type: usize,
+) -> Result<(), Error> {
+  check_periods("trix", &[periods, signal_periods])?;
+
+  let hundred = NumT::from(100.0).unwrap();
+  run_signal(
+    ctx,
+    trix,
+    signal,
+    hist,
+    input,
+    signal_periods,
+    signal_type,
+    true,
+    NumT::one(),
+    || {
+      (
+        [
+          Ema::sma_seeded(periods),
+          Ema::sma_seeded(periods),
+          Ema::sma_seeded(periods),
+        ],
+        None,
+      )
+    },
+    |(emas, prev): &mut ([Ema<NumT>; 3], Option<NumT>), x| {
+      let e3 = emas.iter_mut().try_fold(x, |v, ema| ema.update(v))?;
+      let p = prev.replace(e3)?;
+      if p == NumT::zero() {
+        return None;
+      }
+      Some((e3 - p) / p * hundred)
+    },
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::algo::{
+    assert_vec_eq_nan,
+    context::{FLAG_SKIP_NAN, FLAG_STRICTLY_CYCLE},
+  };
+
+  #[test]
+  fn test_macd() {
+    // EMA(3) seeded with mean(1, 2, 4) = 7 / 3, then 31 / 6 and 127 / 12,
+    // EMA(1) starts at bar 2 so both seeds end there
+    let input = vec![1.0, 2.0, 4.0, 8.0, 16.0];
+    let (mut m, mut s, mut h) = (vec![0.0; 5], vec![0.0; 5], vec![0.0; 5]);
+    let ctx = Context::new(0, 0, 0);
+    ta_macd(&ctx, &mut m, &mut s, &mut h, &input, 1, 3, 2, SIGNAL_EMA).unwrap();
+    let nan = f64::NAN;
+    let line = [nan, nan, 5.0 / 3.0, 17.0 / 6.0, 65.0 / 12.0];
+    assert_vec_eq_nan(&m, &line);
+    // EMA(2) signal seeded with mean(5 / 3, 17 / 6)
+    assert_vec_eq_nan(&s, &[nan, nan, nan, 9.0 / 4.0, 157.0 / 36.0]);
+    assert_vec_eq_nan(&h, &[nan, nan, nan, 7.0 / 12.0, 19.0 / 18.0]);
+
+    // SMA(2) signal, partial at the head
+    ta_macd(&ctx, &mut m, &mut s, &mut h, &input, 1, 3, 2, SIGNAL_SMA).unwrap();
+    assert_vec_eq_nan(&s, &[nan, nan, 5.0 / 3.0, 9.0 / 4.0, 33.0 / 8.0]);
+
+    // TDX seeds with the first value and doubles the histogram
+    // EMA(1) is the input, EMA(3) alpha 0.5: 1, 1.5, 2.25, 3.125
+    let input = vec![1.0, 2.0, 3.0, 4.0];
+    let (mut dif, mut dea, mut bar) = (vec![0.0; 4], vec![0.0; 4], vec![0.0; 4]);
+    ta_macd_tdx(&ctx, &mut dif, &mut dea, &mut bar, &input, 1, 3, 3).unwrap();
+    assert_vec_eq_nan(&dif, &[0.0, 0.5, 0.75, 0.875]);
+    assert_vec_eq_nan(&dea, &[0.0, 0.25, 0.5, 0.6875]);
+    assert_vec_eq_nan(&bar, &[0.0, 0.5, 0.5, 0.375]);
+  }
+
+  #[test]
+  fn test_macd_strictly_cycle() {
+    let input = vec![1.0, 2.0, 4.0, 8.0, 16.0];
+    let (mut m, mut s, mut h) = (vec![0.0; 5], vec![0.0; 5], vec![0.0; 5]);
+    let ctx = Context::new(0, 0, FLAG_STRICTLY_CYCLE);
+    ta_macd(&ctx, &mut m, &mut s, &mut h, &input, 1, 3, 2, SIGNAL_SMA).unwrap();
+    let nan = f64::NAN;
+    assert_vec_eq_nan(&m, &[nan, nan, 5.0 / 3.0, 17.0 / 6.0, 65.0 / 12.0]);
+    assert_vec_eq_nan(&s, &[nan, nan, nan, 9.0 / 4.0, 33.0 / 8.0]);
+
+    let mut apo = vec![0.0; 5];
+    ta_apo(&ctx, &mut apo, &input, 1, 3).unwrap();
+    assert_vec_eq_nan(&apo, &m);
+
+    let input = vec![1.0, 2.0, 3.0, 4.0];
+    let (mut dif, mut dea, mut bar) = (vec![0.0; 4], vec![0.0; 4], vec![0.0; 4]);
+    ta_macd_tdx(&ctx, &mut dif, &mut dea, &mut bar, &input, 1, 3, 2).unwrap();
+    assert_vec_eq_nan(&dif, &[nan, nan, 0.75, 0.875]);
+    assert_vec_eq_nan(&dea[..3], &[nan, nan, nan]);
+  }
+
+  #[test]
+  fn test_ppo_trix() {
+    let input = vec![1.0, 2.0, 4.0, 8.0, 16.0, 32.0];
+    let n = input.len();
+    let (mut p, mut s, mut h) = (vec![0.0; n], vec![0.0; n], vec![0.0; n]);
+    let ctx = Context::new(0, 0, 0);
+    let nan = f64::NAN;
+    ta_ppo(&ctx, &mut p, &mut s, &mut h, &input, 1, 3, 1, SIGNAL_EMA).unwrap();
+    assert_vec_eq_nan(&p[..4], &[nan, nan, 5.0 / 7.0 * 100.0, 17.0 / 31.0 * 100.0]);
+
+    // TRIX(1) is the one bar rate of change of the input
+    ta_trix(&ctx, &mut p, &mut s, &mut h, &input, 1, 1, SIGNAL_EMA).unwrap();
+    assert_vec_eq_nan(&p, &[nan, 100.0, 100.0, 100.0, 100.0, 100.0]);
+    assert_vec_eq_nan(&h, &[nan, 0.0, 0.0, 0.0, 0.0, 0.0]);
+
+    // TRIX(2) needs 3 * (2 - 1) + 1 bars
+    ta_trix(&ctx, &mut p, &mut s, &mut h, &input, 2, 1, SIGNAL_EMA).unwrap();
+    assert!(p[..4].iter().all(|x| x.is_nan()));
+    assert!(p[4..].iter().all(|x| x.is_finite()));
+  }
+
+  #[test]
+  fn test_macd_nan() {
+    let input = vec![1.0, 2.0, f64::NAN, 3.0, 4.0];
+    let (mut m, mut s, mut h) = (vec![0.0; 5], vec![0.0; 5], vec![0.0; 5]);
+    let nan = f64::NAN;
+
+    // restart after the gap, EMA(2) needs 2 bars again
+    let ctx = Context::new(0, 0, 0);
+    ta_macd(&ctx, &mut m, &mut s, &mut h, &input, 1, 2, 1, SIGNAL_EMA).unwrap();
+    assert_vec_eq_nan(&m, &[nan, 0.5, nan, nan, 0.5]);
+
+    let ctx = Context::new(0, 0, FLAG_SKIP_NAN);
+    ta_macd(&ctx, &mut m, &mut s, &mut h, &input, 1, 2, 1, SIGNAL_EMA).unwrap();
+    assert_vec_eq_nan(&m, &[nan, 0.5, nan, 0.5, 0.5]);
+
+    assert!(ta_macd(&ctx, &mut m, &mut s, &mut h, &input, 1, 3, 1, 2).is_err());
+  }
+}
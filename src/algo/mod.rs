@@ -1,24 +1,38 @@
 // Copyright 2026 MSD-RS Project LiJia
 // SPDX-License-Identifier: BSD-2-Clause
 
+mod adaptive;
 mod alpha;
 mod backfill;
+mod backtest;
+mod band;
+mod bars;
 mod beta;
+mod candle;
 mod context;
 mod cross;
 mod drawdown;
 mod ema;
 mod entropy;
 mod error;
+mod ewm;
 mod extremum;
+mod garch;
 mod group;
+mod hilbert;
+mod ic;
+mod kalman;
 mod ma;
+mod macd;
 mod misc;
 mod moments;
 mod neutralize;
+mod oscillator;
 mod quantile;
 mod rank;
+mod ratio;
 mod returns;
+mod risk;
 mod scan;
 mod series;
 mod sharpe;
@@ -27,26 +41,43 @@ mod slope;
 mod stats;
 mod stddev;
 mod sum;
+mod trend;
+mod volatility;
+mod volume;
+mod zigzag;
 mod zscore;
 
+pub use adaptive::*;
 pub use alpha::*;
 pub use backfill::*;
+pub use backtest::*;
+pub use band::*;
 pub use beta::*;
+pub use candle::*;
 pub use context::Context;
 pub use cross::*;
 pub use drawdown::*;
 pub use ema::*;
 pub use entropy::*;
 pub use error::Error;
+pub use ewm::*;
 pub use extremum::*;
+pub use garch::*;
 pub use group::*;
+pub use hilbert::*;
+pub use ic::*;
+pub use kalman::*;
 pub use ma::*;
+pub use macd::*;
 pub use misc::*;
 pub use moments::*;
 pub use neutralize::*;
+pub use oscillator::*;
 pub use quantile::*;
 pub use rank::*;
+pub use ratio::*;
 pub use returns::*;
+pub use risk::*;
 pub use scan::*;
 pub use series::*;
 pub use sharpe::*;
@@ -54,6 +85,10 @@ pub use slope::*;
 pub use stats::*;
 pub use stddev::*;
 pub use sum::*;
+pub use trend::*;
+pub use volatility::*;
+pub use volume::*;
+pub use zigzag::*;
 pub use zscore::*;
 
 pub use num_traits::Float;
@@ -0,0 +1,510 @@
+// Copyright 2026 MSD-RS Project LiJia
+// SPDX-License-Identifier: BSD-2-Clause
+
+use num_traits::Float;
+use rayon::prelude::*;
+
+use super::bars::run_window;
+use crate::algo::{Context, Error, is_normal, ta_hhv, ta_llv, ta_ma, ta_sma};
+
+/// Context for intermediate series, computed from the first bar so that the
+/// warm-up does not restart at `ctx.start`
+pub(crate) fn full_range(ctx: &Context) -> Context {
+  Context { _start: 0, ..*ctx }
+}
+
+/// Clear results before `ctx.start`
+pub(crate) fn clear_before_start<NumT: Float + Send + Sync>(ctx: &Context, r: &mut [NumT]) {
+  r.par_chunks_mut(ctx.chunk_size(r.len())).for_each(|r| {
+    let start = ctx.start(r.len());
+    r[..start].fill(NumT::nan());
+  });
+}
+
+/// HHV(high, periods) and LLV(low, periods) from the first bar
+fn high_low<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  high: &[NumT],
+  low: &[NumT],
+  periods: usize,
+) -> Result<(Vec<NumT>, Vec<NumT>), Error> {
+  let full = full_range(ctx);
+  let mut hh = vec![NumT::nan(); high.len()];
+  let mut ll = vec![NumT::nan(); low.len()];
+  ta_hhv(&full, &mut hh, high, periods)?;
+  ta_llv(&full, &mut ll, low, periods)?;
+  Ok((hh, ll))
+}
+
+/// 100 * (close - lowest low) / (highest high - lowest low), 0 for a flat window
+fn stoch_fast_k<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  high: &[NumT],
+  low: &[NumT],
+  close: &[NumT],
+  periods: usize,
+) -> Result<(), Error> {
+  let (hh, ll) = high_low(ctx, high, low, periods)?;
+  let hundred = NumT::from(100).unwrap();
+  r.par_iter_mut()
+    .zip(hh.par_iter().zip(ll.par_iter()))
+    .zip(close.par_iter())
+    .for_each(|((r, (&hh, &ll)), &c)| {
+      *r = if !is_normal(&c) || !is_normal(&hh) || !is_normal(&ll) {
+        NumT::nan()
+      } else if hh > ll {
+        hundred * (c - ll) / (hh - ll)
+      } else {
+        NumT::zero()
+      };
+    });
+  Ok(())
+}
+
+/// Relative Strength Index
+///
+/// RSI = 100 * avg gain / (avg gain + avg loss), averages start as the mean of
+/// the first `periods` changes and follow Wilder smoothing
+/// avg = (avg * (periods - 1) + x) / periods, same as TA-Lib RSI, NaN during
+/// the warm-up of `periods` changes.
+///
+/// With FLAG_SKIP_NAN missing values are skipped, otherwise a missing value
+/// restarts the warm-up.
+///
+/// Ref: https://en.wikipedia.org/wiki/Relative_strength_index
+pub fn ta_rsi<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+  periods: usize,
+) -> Result<(), Error> {
+  if r.len() != input.len() {
+    return Err(Error::LengthMismatch(r.len(), input.len()));
+  }
+  if periods == 0 {
+    return Err(Error::InvalidPeriod(
+      "rsi requires periods >= 1".to_string(),
+    ));
+  }
+
+  let n = NumT::from(periods).unwrap();
+  let hundred = NumT::from(100).unwrap();
+  r.par_chunks_mut(ctx.chunk_size(r.len()))
+    .zip(input.par_chunks(ctx.chunk_size(input.len())))
+    .for_each(|(r, x)| {
+      let start = ctx.start(r.len());
+      let end = ctx.end(r.len());
+      r.fill(NumT::nan());
+
+      let mut prev = NumT::nan();
+      let mut count = 0;
+      let mut gain = NumT::zero();
+      let mut loss = NumT::zero();
+      for i in 0..end {
+        let val = x[i];
+        if !is_normal(&val) {
+          if !ctx.is_skip_nan() {
+            prev = NumT::nan();
+          }
+          continue;
+        }
+        if prev.is_nan() {
+          prev = val;
+          count = 0;
+          gain = NumT::zero();
+          loss = NumT::zero();
+          continue;
+        }
+
+        let up = (val - prev).max(NumT::zero());
+        let down = (prev - val).max(NumT::zero());
+        prev = val;
+        count += 1;
+        if count <= periods {
+          gain = gain + up;
+          loss = loss + down;
+          if count < periods {
+            continue;
+          }
+          gain = gain / n;
+          loss = loss / n;
+        } else {
+          gain = (gain * (n - NumT::one()) + up) / n;
+          loss = (loss * (n - NumT::one()) + down) / n;
+        }
+
+        if i >= start {
+          r[i] = if gain + loss > NumT::zero() {
+            hundred * gain / (gain + loss)
+          } else {
+            NumT::zero()
+          };
+        }
+      }
+    });
+
+  Ok(())
+}
+
+/// Stochastic Oscillator
+///
+/// fast %K = 100 * (close - LLV(low, fastk)) / (HHV(high, fastk) - LLV(low, fastk)),
+/// %K = MA(fast %K, slowk), %D = MA(%K, slowd), same as TA-Lib STOCH with
+/// SMA smoothing after the warm-up.
+///
+/// Ref: https://en.wikipedia.org/wiki/Stochastic_oscillator
+#[allow(clippy::too_many_arguments)]
+pub fn ta_stoch<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  k: &mut [NumT],
+  d: &mut [NumT],
+  high: &[NumT],
+  low: &[NumT],
+  close: &[NumT],
+  fastk: usize,
+  slowk: usize,
+  slowd: usize,
+) -> Result<(), Error> {
+  let len = close.len();
+  for l in [k.len(), d.len(), high.len(), low.len()] {
+    if l != len {
+      return Err(Error::LengthMismatch(l, len));
+    }
+  }
+  if fastk == 0 || slowk == 0 || slowd == 0 {
+    return Err(Error::InvalidPeriod(
+      "stoch requires periods >= 1".to_string(),
+    ));
+  }
+
+  let full = full_range(ctx);
+  let mut fast = vec![NumT::nan(); len];
+  stoch_fast_k(ctx, &mut fast, high, low, close, fastk)?;
+  ta_ma(&full, k, &fast, slowk)?;
+  ta_ma(&full, d, k, slowd)?;
+  clear_before_start(ctx, k);
+  clear_before_start(ctx, d);
+  Ok(())
+}
+
+/// KDJ Indicator
+///
+/// RSV = fast %K of `STOCH` over `n` bars, K = SMA(RSV, m1, 1),
+/// D = SMA(K, m2, 1), J = 3 * K - 2 * D, the smoothing is seeded from the
+/// first valid value as `SMA`.
+///
+/// Ref: https://www.investopedia.com/terms/k/kdj.asp
+#[allow(clippy::too_many_arguments)]
+pub fn ta_kdj<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  k: &mut [NumT],
+  d: &mut [NumT],
+  j: &mut [NumT],
+  high: &[NumT],
+  low: &[NumT],
+  close: &[NumT],
+  n: usize,
+  m1: usize,
+  m2: usize,
+) -> Result<(), Error> {
+  let len = close.len();
+  for l in [k.len(), d.len(), j.len(), high.len(), low.len()] {
+    if l != len {
+      return Err(Error::LengthMismatch(l, len));
+    }
+  }
+  if n == 0 || m1 == 0 || m2 == 0 {
+    return Err(Error::InvalidPeriod(
+      "kdj requires periods >= 1".to_string(),
+    ));
+  }
+
+  let full = full_range(ctx);
+  let mut rsv = vec![NumT::nan(); len];
+  stoch_fast_k(ctx, &mut rsv, high, low, close, n)?;
+  ta_sma(&full, k, &rsv, m1, 1)?;
+  ta_sma(&full, d, k, m2, 1)?;
+  let three = NumT::from(3).unwrap();
+  let two = NumT::from(2).unwrap();
+  j.par_iter_mut()
+    .zip(k.par_iter())
+    .zip(d.par_iter())
+    .for_each(|((j, &k), &d)| *j = three * k - two * d);
+  clear_before_start(ctx, k);
+  clear_before_start(ctx, d);
+  clear_before_start(ctx, j);
+  Ok(())
+}
+
+/// Williams %R
+///
+/// %R = -100 * (HHV(high, n) - close) / (HHV(high, n) - LLV(low, n)),
+/// 0 for a flat window, same as TA-Lib WILLR after the warm-up.
+///
+/// Ref: https://en.wikipedia.org/wiki/Williams_%25R
+pub fn ta_willr<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  high: &[NumT],
+  low: &[NumT],
+  close: &[NumT],
+  periods: usize,
+) -> Result<(), Error> {
+  if r.len() != high.len() || r.len() != low.len() || r.len() != close.len() {
+    return Err(Error::LengthMismatch(r.len(), close.len()));
+  }
+
+  let (hh, ll) = high_low(ctx, high, low, periods)?;
+  let hundred = NumT::from(100).unwrap();
+  r.par_iter_mut()
+    .zip(hh.par_iter().zip(ll.par_iter()))
+    .zip(close.par_iter())
+    .for_each(|((r, (&hh, &ll)), &c)| {
+      *r = if !is_normal(&c) || !is_normal(&hh) || !is_normal(&ll) {
+        NumT::nan()
+      } else if hh > ll {
+        -hundred * (hh - c) / (hh - ll)
+      } else {
+        NumT::zero()
+      };
+    });
+  clear_before_start(ctx, r);
+  Ok(())
+}
+
+/// Commodity Channel Index
+///
+/// TP = (high + low + close) / 3, CCI = (TP - MA(TP, n)) / (0.015 * mean
+/// deviation of TP), 0 for a flat window, same as TA-Lib CCI. Requires
+/// `periods` valid bars.
+///
+/// Ref: https://en.wikipedia.org/wiki/Commodity_channel_index
+pub fn ta_cci<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  high: &[NumT],
+  low: &[NumT],
+  close: &[NumT],
+  periods: usize,
+) -> Result<(), Error> {
+  if r.len() != high.len() || r.len() != low.len() || r.len() != close.len() {
+    return Err(Error::LengthMismatch(r.len(), close.len()));
+  }
+  if periods == 0 {
+    return Err(Error::InvalidPeriod(
+      "cci requires periods >= 1".to_string(),
+    ));
+  }
+
+  let three = NumT::from(3).unwrap();
+  let factor = NumT::from(0.015).unwrap();
+  let n = NumT::from(periods).unwrap();
+  run_window(ctx, [r], [high, low, close], periods, |w| {
+    if w.len() < periods {
+      return [NumT::nan()];
+    }
+    let tp = |[h, l, c]: &[NumT; 3]| (*h + *l + *c) / three;
+    let mean = w.iter().fold(NumT::zero(), |a, v| a + tp(v)) / n;
+    let dev = w.iter().fold(NumT::zero(), |a, v| a + (tp(v) - mean).abs()) / n;
+    [if dev > NumT::zero() {
+      (tp(&w[w.len() - 1]) - mean) / (factor * dev)
+    } else {
+      NumT::zero()
+    }]
+  })
+}
+
+/// Money Flow Index
+///
+/// TP = (high + low + close) / 3, money flow TP * volume is positive when TP
+/// rises and negative when it falls, MFI = 100 * positive / (positive +
+/// negative) over the last `periods` flows, same as TA-Lib MFI. Requires
+/// `periods + 1` valid bars.
+///
+/// Ref: https://en.wikipedia.org/wiki/Money_flow_index
+pub fn ta_mfi<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  high: &[NumT],
+  low: &[NumT],
+  close: &[NumT],
+  volume: &[NumT],
+  periods: usize,
+) -> Result<(), Error> {
+  if r.len() != high.len()
+    || r.len() != low.len()
+    || r.len() != close.len()
+    || r.len() != volume.len()
+  {
+    return Err(Error::LengthMismatch(r.len(), close.len()));
+  }
+  if periods == 0 {
+    return Err(Error::InvalidPeriod(
+      "mfi requires periods >= 1".to_string(),
+    ));
+  }
+
+  let three = NumT::from(3).unwrap();
+  let hundred = NumT::from(100).unwrap();
+  run_window(ctx, [r], [high, low, close, volume], periods + 1, |w| {
+    if w.len() <= periods {
+      return [NumT::nan()];
+    }
+    let tp = |[h, l, c, _]: &[NumT; 4]| (*h + *l + *c) / three;
+    let (pos, neg) = w.iter().zip(w.iter().skip(1)).fold(
+      (NumT::zero(), NumT::zero()),
+      |(pos, neg), (prev, cur)| {
+        let (prev, now) = (tp(prev), tp(cur));
+        let flow = now * cur[3];
+        if now > prev {
+          (pos + flow, neg)
+        } else if now < prev {
+          (pos, neg + flow)
+        } else {
+          (pos, neg)
+        }
+      },
+    );
+    [if pos + neg > NumT::zero() {
+      hundred * pos / (pos + neg)
+    } else {
+      NumT::zero()
+    }]
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::algo::{assert_vec_eq_nan, context::FLAG_SKIP_NAN};
+
+  const HIGH: [f64; 8] = [10.0, 10.5, 10.8, 10.6, 11.0, 11.2, 10.9, 11.4];
+  const LOW: [f64; 8] = [9.5, 9.9, 10.1, 10.0, 10.3, 10.6, 10.4, 10.8];
+  const CLOSE: [f64; 8] = [9.8, 10.4, 10.2, 10.5, 10.9, 10.7, 10.6, 11.3];
+  const VOLUME: [f64; 8] = [100.0, 120.0, 90.0, 110.0, 130.0, 80.0, 95.0, 150.0];
+
+  #[test]
+  fn test_rsi() {
+    // Wilder's example, first RSI(14) is 70.46 (70.53 with rounded averages)
+    let close = vec![
+      44.34, 44.09, 44.15, 43.61, 44.33, 44.83, 45.10, 45.42, 45.84, 46.08, 45.89, 46.03, 45.61,
+      46.28, 46.28,
+    ];
+    let mut r = vec![0.0; close.len()];
+    let ctx = Context::new(0, 1, 0);
+    ta_rsi(&ctx, &mut r, &close, 14).unwrap();
+    assert!(r[..13].iter().all(|v| v.is_nan()));
+    // average gain 3.34 / 14, average loss 1.40 / 14
+    assert!((r[14] - 100.0 * 3.34 / 4.74).abs() < 1e-9);
+
+    // gains 1, 1, then a loss of 1 after the warm-up of 2 changes
+    let close = vec![1.0, 2.0, 3.0, 2.0];
+    let mut r = vec![0.0; 4];
+    ta_rsi(&ctx, &mut r, &close, 2).unwrap();
+    assert_vec_eq_nan(&r, &[f64::NAN, f64::NAN, 100.0, 50.0]);
+
+    // a missing value restarts the warm-up unless skipped
+    let close = vec![1.0, 2.0, f64::NAN, 3.0, 2.0];
+    let mut r = vec![0.0; 5];
+    ta_rsi(&ctx, &mut r, &close, 1).unwrap();
+    assert_vec_eq_nan(&r, &[f64::NAN, 100.0, f64::NAN, f64::NAN, 0.0]);
+    let ctx = Context::new(0, 1, FLAG_SKIP_NAN);
+    ta_rsi(&ctx, &mut r, &close, 1).unwrap();
+    assert_vec_eq_nan(&r, &[f64::NAN, 100.0, f64::NAN, 100.0, 0.0]);
+  }
+
+  fn naive_fast_k(i: usize, n: usize) -> f64 {
+    let hh = HIGH[i + 1 - n..=i].iter().cloned().fold(f64::MIN, f64::max);
+    let ll = LOW[i + 1 - n..=i].iter().cloned().fold(f64::MAX, f64::min);
+    100.0 * (CLOSE[i] - ll) / (hh - ll)
+  }
+
+  #[test]
+  fn test_stoch_willr() {
+    let ctx = Context::new(0, 1, 0);
+    let (mut k, mut d) = (vec![0.0; 8], vec![0.0; 8]);
+    ta_stoch(&ctx, &mut k, &mut d, &HIGH, &LOW, &CLOSE, 3, 2, 2).unwrap();
+
+    // warm-up of TA-Lib STOCH(3, 2, 2) is 2 + 1 + 1 bars
+    let slow_k = |i: usize| (naive_fast_k(i, 3) + naive_fast_k(i - 1, 3)) / 2.0;
+    for i in 4..8 {
+      assert!((k[i] - slow_k(i)).abs() < 1e-9);
+      assert!((d[i] - (slow_k(i) + slow_k(i - 1)) / 2.0).abs() < 1e-9);
+    }
+
+    let mut r = vec![0.0; 8];
+    ta_willr(&ctx, &mut r, &HIGH, &LOW, &CLOSE, 3).unwrap();
+    for (i, v) in r.iter().enumerate().skip(2) {
+      assert!((v - (naive_fast_k(i, 3) - 100.0)).abs() < 1e-9);
+    }
+
+    // flat window is 0
+    let flat = [1.0; 3];
+    let mut r = vec![0.0; 3];
+    ta_willr(&ctx, &mut r, &flat, &flat, &flat, 2).unwrap();
+    assert_vec_eq_nan(&r, &[0.0, 0.0, 0.0]);
+  }
+
+  #[test]
+  fn test_kdj() {
+    let ctx = Context::new(0, 1, 0);
+    let (mut k, mut d, mut j) = (vec![0.0; 8], vec![0.0; 8], vec![0.0; 8]);
+    ta_kdj(&ctx, &mut k, &mut d, &mut j, &HIGH, &LOW, &CLOSE, 3, 3, 3).unwrap();
+
+    let mut rsv = vec![0.0; 8];
+    stoch_fast_k(&ctx, &mut rsv, &HIGH, &LOW, &CLOSE, 3).unwrap();
+    let (mut ek, mut ed) = (rsv[0], rsv[0]);
+    for i in 0..8 {
+      ek = (rsv[i] + 2.0 * ek) / 3.0;
+      ed = (ek + 2.0 * ed) / 3.0;
+      assert!((k[i] - ek).abs() < 1e-9);
+      assert!((d[i] - ed).abs() < 1e-9);
+      assert!((j[i] - (3.0 * ek - 2.0 * ed)).abs() < 1e-9);
+    }
+  }
+
+  #[test]
+  fn test_cci_mfi() {
+    let ctx = Context::new(0, 1, 0);
+    let tp: Vec<f64> = (0..8)
+      .map(|i| (HIGH[i] + LOW[i] + CLOSE[i]) / 3.0)
+      .collect();
+
+    let mut r = vec![0.0; 8];
+    ta_cci(&ctx, &mut r, &HIGH, &LOW, &CLOSE, 4).unwrap();
+    assert!(r[..3].iter().all(|v| v.is_nan()));
+    for i in 3..8 {
+      let w = &tp[i - 3..=i];
+      let mean = w.iter().sum::<f64>() / 4.0;
+      let dev = w.iter().map(|v| (v - mean).abs()).sum::<f64>() / 4.0;
+      assert!((r[i] - (tp[i] - mean) / (0.015 * dev)).abs() < 1e-9);
+    }
+
+    ta_mfi(&ctx, &mut r, &HIGH, &LOW, &CLOSE, &VOLUME, 3).unwrap();
+    assert!(r[..3].iter().all(|v| v.is_nan()));
+    for (i, v) in r.iter().enumerate().skip(3) {
+      let (mut pos, mut neg) = (0.0, 0.0);
+      for t in i - 2..=i {
+        let flow = tp[t] * VOLUME[t];
+        if tp[t] > tp[t - 1] {
+          pos += flow;
+        } else if tp[t] < tp[t - 1] {
+          neg += flow;
+        }
+      }
+      assert!((v - 100.0 * pos / (pos + neg)).abs() < 1e-9);
+    }
+
+    // skip nan compacts the window
+    let mut close = CLOSE;
+    close[5] = f64::NAN;
+    let ctx = Context::new(0, 1, FLAG_SKIP_NAN);
+    ta_cci(&ctx, &mut r, &HIGH, &LOW, &close, 4).unwrap();
+    assert!(r[5].is_nan());
+    let w = [tp[3], tp[4], tp[6], tp[7]];
+    let mean = w.iter().sum::<f64>() / 4.0;
+    let dev = w.iter().map(|v| (v - mean).abs()).sum::<f64>() / 4.0;
+    assert!((r[7] - (tp[7] - mean) / (0.015 * dev)).abs() < 1e-9);
+  }
+}
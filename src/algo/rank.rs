@@ -1,7 +1,12 @@
 // Copyright 2026 MSD-RS Project LiJia
 // SPDX-License-Identifier: BSD-2-Clause
 
-use std::{cmp::Ordering, collections::BTreeMap, fmt::Debug};
+use std::{
+  cmp::Ordering,
+  collections::BTreeMap,
+  fmt::Debug,
+  ops::Bound::{Excluded, Unbounded},
+};
 
 use num_traits::Float;
 use rayon::prelude::*;
@@ -53,46 +58,216 @@ impl<NumT: Float + Debug> Debug for OrderedFloat<NumT> {
 ///
 /// Uses min-rank method for ties (same as pandas rankdata method='min').
 /// NaN values are treated as larger than all non-NaN values.
+/// See `ta_rank_ex` for other tie methods.
 pub fn ta_rank<NumT: Float + Send + Sync>(
   ctx: &Context,
   r: &mut [NumT],
   input: &[NumT],
   periods: usize,
+) -> Result<(), Error> {
+  rolling_rank(ctx, r, input, periods, RANK_MIN, true)
+}
+
+#[derive(Debug, Clone, Copy)]
+struct UnsafePtr<NumT: Float> {
+  ptr: *mut NumT,
+  len: usize,
+}
+
+impl<NumT: Float> UnsafePtr<NumT> {
+  pub fn new(ptr: *mut NumT, len: usize) -> Self {
+    UnsafePtr { ptr, len }
+  }
+
+  pub fn get(&self) -> &mut [NumT] {
+    unsafe { std::slice::from_raw_parts_mut(self.ptr, self.len) }
+  }
+}
+
+unsafe impl<NumT: Float> Send for UnsafePtr<NumT> {}
+unsafe impl<NumT: Float> Sync for UnsafePtr<NumT> {}
+
+/// Calculate rank percentage cross group dimension, the ctx.groups() is the number of groups
+/// Same value are averaged, see `ta_cc_rank_ex` for other tie methods.
+pub fn ta_cc_rank<NumT: Float + Send + Sync + Debug>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+) -> Result<(), Error> {
+  if ctx.groups() < 2 {
+    return ta_rank(ctx, r, input, 0);
+  }
+
+  ta_cc_rank_ex(ctx, r, input, RANK_AVERAGE | RANK_PCT)
+}
+
+/// rank method: average rank of the tied values
+pub const RANK_AVERAGE: usize = 0;
+/// rank method: lowest rank of the tied values
+pub const RANK_MIN: usize = 1;
+/// rank method: highest rank of the tied values
+pub const RANK_MAX: usize = 2;
+/// rank method: like min, but rank always increases by 1 between groups of tied values
+pub const RANK_DENSE: usize = 3;
+/// rank method: tied values are ranked in order of appearance
+pub const RANK_ORDINAL: usize = 4;
+/// rank flag: output rank percentage instead of rank
+pub const RANK_PCT: usize = 8;
+/// rank flag: rank in descending order
+pub const RANK_DESCENDING: usize = 16;
+
+const RANK_METHOD_MASK: usize = 7;
+
+pub(crate) fn check_rank_method(method: usize) -> Result<(), Error> {
+  match method & RANK_METHOD_MASK {
+    RANK_AVERAGE | RANK_MIN | RANK_MAX | RANK_DENSE | RANK_ORDINAL => Ok(()),
+    _ => Err(Error::InvalidParameter(format!(
+      "unknown rank method: {}",
+      method
+    ))),
+  }
+}
+
+/// 1-based rank of the `k`-th of `equal` tied values, which have `before` values ranked ahead
+/// and are the `dense`-th distinct value
+#[inline]
+fn tie_rank<NumT: Float>(
+  method: usize,
+  before: usize,
+  equal: usize,
+  dense: usize,
+  k: usize,
+) -> NumT {
+  match method & RANK_METHOD_MASK {
+    RANK_MIN => NumT::from(before + 1).unwrap(),
+    RANK_MAX => NumT::from(before + equal).unwrap(),
+    RANK_DENSE => NumT::from(dense).unwrap(),
+    RANK_ORDINAL => NumT::from(before + k + 1).unwrap(),
+    _ => NumT::from(2 * before + equal + 1).unwrap() / NumT::from(2usize).unwrap(),
+  }
+}
+
+/// Sort `items` of (value, index) by value and write rank of each item into `r[index]`
+///
+/// Items must not contain NaN, tied values keep the order of `items`,
+/// which is the order of appearance for `RANK_ORDINAL`.
+pub(crate) fn rank_items<NumT: Float>(items: &mut [(NumT, usize)], method: usize, r: &mut [NumT]) {
+  if items.is_empty() {
+    return;
+  }
+
+  if method & RANK_DESCENDING != 0 {
+    items.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
+  } else {
+    items.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal));
+  }
+
+  let mut dense = 0usize;
+  let mut s = 0;
+  while s < items.len() {
+    let mut e = s + 1;
+    while e < items.len() && items[e].0 == items[s].0 {
+      e += 1;
+    }
+    dense += 1;
+    for (k, item) in items[s..e].iter().enumerate() {
+      r[item.1] = tie_rank(method, s, e - s, dense, k);
+    }
+    s = e;
+  }
+
+  if method & RANK_PCT != 0 {
+    let total = if method & RANK_METHOD_MASK == RANK_DENSE {
+      NumT::from(dense).unwrap()
+    } else {
+      NumT::from(items.len()).unwrap()
+    };
+    for item in items.iter() {
+      r[item.1] = r[item.1] / total;
+    }
+  }
+}
+
+/// Bin of a 1-based average rank among `total` values: floor((rank_avg - 1) * bins / total)
+#[inline]
+pub(crate) fn bin_of<NumT: Float>(rank_avg: NumT, total: NumT, bins: NumT) -> NumT {
+  ((rank_avg - NumT::one()) * bins / total)
+    .floor()
+    .min(bins - NumT::one())
+}
+
+/// Calculate rank in a sliding window with size `periods` using a tie `method`
+///
+/// `method` is one of `RANK_AVERAGE` (0), `RANK_MIN` (1), `RANK_MAX` (2), `RANK_DENSE` (3),
+/// `RANK_ORDINAL` (4), optionally combined with `RANK_PCT` (8) and `RANK_DESCENDING` (16).
+/// The current value is the latest one of its ties for `RANK_ORDINAL`.
+/// NaN values are excluded from the window, NaN input produces NaN output.
+/// If periods is 0, the window expands from the first value.
+///
+/// Ref: https://pandas.pydata.org/docs/reference/api/pandas.core.window.rolling.Rolling.rank.html
+pub fn ta_rank_ex<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+  periods: usize,
+  method: usize,
 ) -> Result<(), Error> {
   if r.len() != input.len() {
     return Err(Error::LengthMismatch(r.len(), input.len()));
   }
 
+  check_rank_method(method)?;
 
-  if periods == 1 {
-    r.fill(NumT::from(1.0).unwrap());
-    return Ok(());
+  rolling_rank(ctx, r, input, periods, method, false)
+}
+
+/// Rolling rank kernel of `ta_rank` and `ta_rank_ex`
+///
+/// If `nan_in_window` is set, NaN values take slots of the window and rank ahead of
+/// the valid values, NaN input gets the window size as rank.
+/// Otherwise NaN values are excluded from the window and NaN input produces NaN output.
+fn rolling_rank<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+  periods: usize,
+  method: usize,
+  nan_in_window: bool,
+) -> Result<(), Error> {
+  if r.len() != input.len() {
+    return Err(Error::LengthMismatch(r.len(), input.len()));
   }
 
+  let descending = method & RANK_DESCENDING != 0;
+  let pct = method & RANK_PCT != 0;
+
   r.par_chunks_mut(ctx.chunk_size(r.len()))
     .zip(input.par_chunks(ctx.chunk_size(input.len())))
     .for_each(|(r, x)| {
       let start = ctx.start(r.len());
       let end = ctx.end(r.len());
       r.fill(NumT::nan());
-      // Track counts per unique value to handle duplicates correctly.
-      let mut rank_window: BTreeMap<OrderedFloat<NumT>, u32> = BTreeMap::new();
-      let mut nan_count: usize = 0;
-      let mut window_size: usize = 0;
 
-      for i in start..end {
-        let val = x[i];
+      // counts per unique valid value in the window
+      let mut rank_window: BTreeMap<OrderedFloat<NumT>, usize> = BTreeMap::new();
+      let mut valid_count = 0usize;
+      let mut nan_count = 0usize;
 
-        // Add current value to window
+      let win_begin = if periods == 0 {
+        0
+      } else {
+        start.saturating_sub(periods - 1)
+      };
+      for i in win_begin..end {
+        let val = x[i];
         if val.is_nan() {
           nan_count += 1;
         } else {
           *rank_window.entry(val.into()).or_insert(0) += 1;
+          valid_count += 1;
         }
-        window_size += 1;
 
-        // Remove oldest value if window exceeds periods
-        if window_size > periods {
+        if periods > 0 && i >= win_begin + periods {
           let old_val = x[i - periods];
           if old_val.is_nan() {
             nan_count -= 1;
@@ -104,134 +279,108 @@ pub fn ta_rank<NumT: Float + Send + Sync>(
                 rank_window.remove(&old_key);
               }
             }
+            valid_count -= 1;
           }
-          window_size -= 1;
         }
 
-        if ctx.is_strictly_cycle() && window_size < periods {
+        if i < start || (val.is_nan() && !nan_in_window) {
           continue;
         }
 
-        // Calculate rank
-        if val.is_nan() {
-          // NaN gets the highest rank in the window
-          r[i] = NumT::from(window_size).unwrap();
-        } else {
-          // Count all values strictly less than current (NaN treated as smallest)
-          let val_key: OrderedFloat<NumT> = val.into();
-          let mut less_count: usize = nan_count;
-          for (key, count) in rank_window.iter() {
-            if *key < val_key {
-              less_count += *count as usize;
-            } else {
-              break;
-            }
-          }
-          r[i] = NumT::from(less_count + 1).unwrap();
+        if ctx.is_strictly_cycle() && i + 1 < periods {
+          continue;
         }
+
+        // NaN values of the window form one tie group ahead of the valid values
+        let (nan_before, nan_dense, window_size, distinct) = if nan_in_window {
+          let nan_dense = (nan_count > 0) as usize;
+          (
+            nan_count,
+            nan_dense,
+            valid_count + nan_count,
+            rank_window.len() + nan_dense,
+          )
+        } else {
+          (0, 0, valid_count, rank_window.len())
+        };
+
+        let rank = if val.is_nan() {
+          NumT::from(window_size).unwrap()
+        } else {
+          let key: OrderedFloat<NumT> = val.into();
+          let equal = rank_window[&key];
+          let (before, dense_before) = if descending {
+            rank_window
+              .range((Excluded(key), Unbounded))
+              .fold((0, 0), |(n, d), (_, c)| (n + c, d + 1))
+          } else {
+            rank_window
+              .range(..key)
+              .fold((0, 0), |(n, d), (_, c)| (n + c, d + 1))
+          };
+          // the current value is the latest one of its ties
+          tie_rank(
+            method,
+            before + nan_before,
+            equal,
+            dense_before + nan_dense + 1,
+            equal - 1,
+          )
+        };
+
+        r[i] = if !pct {
+          rank
+        } else if method & RANK_METHOD_MASK == RANK_DENSE {
+          rank / NumT::from(distinct).unwrap()
+        } else {
+          rank / NumT::from(window_size).unwrap()
+        };
       }
     });
 
   Ok(())
 }
 
-#[derive(Debug, Clone, Copy)]
-struct UnsafePtr<NumT: Float> {
-  ptr: *mut NumT,
-  len: usize,
-}
-
-impl<NumT: Float> UnsafePtr<NumT> {
-  pub fn new(ptr: *mut NumT, len: usize) -> Self {
-    UnsafePtr { ptr, len }
-  }
-
-  pub fn get(&self) -> &mut [NumT] {
-    unsafe { std::slice::from_raw_parts_mut(self.ptr, self.len) }
-  }
-}
-
-unsafe impl<NumT: Float> Send for UnsafePtr<NumT> {}
-unsafe impl<NumT: Float> Sync for UnsafePtr<NumT> {}
-
-/// Calculate rank percentage cross group dimension, the ctx.groups() is the number of groups
-/// Same value are averaged
-pub fn ta_cc_rank<NumT: Float + Send + Sync + Debug>(
+/// Calculate rank cross group dimension using a tie `method`, the ctx.groups() is the number of groups
+///
+/// `method` is one of `RANK_AVERAGE` (0), `RANK_MIN` (1), `RANK_MAX` (2), `RANK_DENSE` (3),
+/// `RANK_ORDINAL` (4), optionally combined with `RANK_PCT` (8) and `RANK_DESCENDING` (16).
+/// Ties of `RANK_ORDINAL` are ranked by group order.
+/// NaN values are excluded, NaN input produces NaN output.
+///
+/// Ref: https://pandas.pydata.org/docs/reference/api/pandas.DataFrame.rank.html
+pub fn ta_cc_rank_ex<NumT: Float + Send + Sync + Debug>(
   ctx: &Context,
   r: &mut [NumT],
   input: &[NumT],
+  method: usize,
 ) -> Result<(), Error> {
   if r.len() != input.len() {
     return Err(Error::LengthMismatch(r.len(), input.len()));
   }
 
+  check_rank_method(method)?;
 
-  let group_size = ctx.chunk_size(r.len()) as usize;
-  let groups = ctx.groups() as usize;
-
-  if ctx.groups() < 2 {
-    return ta_rank(ctx, r, input, 0);
-  }
+  let group_size = ctx.chunk_size(r.len());
+  let groups = ctx.groups();
 
   if r.len() != group_size * groups {
-    // ensure data is complete
     return Err(Error::LengthMismatch(r.len(), group_size * groups));
   }
 
-
   let r = UnsafePtr::new(r.as_mut_ptr(), r.len());
   (0..group_size).into_par_iter().for_each(|j| {
-    let mut rank_window: Vec<(OrderedFloat<NumT>, usize)> = Vec::new();
+    let r = r.get();
+    let mut items: Vec<(NumT, usize)> = Vec::with_capacity(groups);
     for i in 0..groups {
       let idx = i * group_size + j;
-      rank_window.push((input[idx].into(), idx));
-    }
-    rank_window.sort_by(|a, b| a.0.cmp(&b.0));
-    let r = r.get();
-
-    // OrderedFloat sorts NaN to the beginning (NaN < everything).
-    // Find where valid (non-NaN) values start.
-    let nan_count = rank_window
-      .iter()
-      .take_while(|v| v.0.value.is_nan())
-      .count();
-    let valid_count = rank_window.len() - nan_count;
-
-    // Assign NaN to NaN positions
-    for i in 0..nan_count {
-      r[rank_window[i].1] = NumT::nan();
-    }
-
-    if valid_count == 0 {
-      return;
-    }
-
-    let total = NumT::from(valid_count).unwrap();
-
-    // Rank only valid values (starting from nan_count)
-    let mut prev_rank_value = rank_window[nan_count].0.value;
-    let mut s = nan_count;
-
-    // chunk by same value
-    for e in nan_count..rank_window.len() {
-      if prev_rank_value == rank_window[e].0.value {
-        continue;
-      }
-      let rank_avg =
-        NumT::from(e - nan_count + s - nan_count + 1).unwrap() / NumT::from(2usize).unwrap();
-      for i in s..e {
-        r[rank_window[i].1] = rank_avg / total;
+      if input[idx].is_nan() {
+        r[idx] = NumT::nan();
+      } else {
+        items.push((input[idx], idx));
       }
-      s = e;
-      prev_rank_value = rank_window[e].0.value;
-    }
-
-    // the last chunk of valid values
-    let rank_avg =
-      NumT::from(valid_count + s - nan_count + 1).unwrap() / NumT::from(2usize).unwrap();
-    for i in s..rank_window.len() {
-      r[rank_window[i].1] = rank_avg / total;
     }
+    rank_items(&mut items, method, r);
   });
 
   Ok(())
@@ -333,9 +482,7 @@ pub fn ta_bins<NumT: Float + Send + Sync + Debug>(
       // If we want bins, maybe NaN should be NaN.
       // Let's assume we preserve NaN if value is not normal.
       } else {
-        let b = ((rank_avg - NumT::one()) * bins_t / total).floor();
-        // clamp to bins-1 (although logic says it should be < bins)
-        if b >= bins_t { bins_t - NumT::one() } else { b }
+        bin_of(rank_avg, total, bins_t)
       };
 
       for i in s..e {
@@ -351,8 +498,7 @@ pub fn ta_bins<NumT: Float + Send + Sync + Debug>(
     let bin = if val.is_nan() {
       NumT::nan()
     } else {
-      let b = ((rank_avg - NumT::one()) * bins_t / total).floor();
-      if b >= bins_t { bins_t - NumT::one() } else { b }
+      bin_of(rank_avg, total, bins_t)
     };
 
     for i in s..rank_window.len() {
@@ -363,10 +509,82 @@ pub fn ta_bins<NumT: Float + Send + Sync + Debug>(
   Ok(())
 }
 
+/// Membership turnover of one bin produced by `ta_bins`
+///
+/// At each date the cross-section is split into `bins` bins like `ta_bins`,
+/// and the members of bin `bin` (0 is the bottom bin, bins - 1 the top bin)
+/// are compared with those of the previous date. Outputs, written to every
+/// security of the date:
+/// entering: fraction of the current members that were not in the bin
+/// leaving: fraction of the previous members that left the bin
+pub fn ta_bins_turnover<NumT: Float + Send + Sync + Debug>(
+  ctx: &Context,
+  entering: &mut [NumT],
+  leaving: &mut [NumT],
+  input: &[NumT],
+  bins: usize,
+  bin: usize,
+) -> Result<(), Error> {
+  if entering.len() != input.len() || leaving.len() != input.len() {
+    return Err(Error::LengthMismatch(entering.len(), input.len()));
+  }
+  if bin >= bins {
+    return Err(Error::InvalidParameter(format!(
+      "bin must be < bins ({}), got {}",
+      bins, bin
+    )));
+  }
+
+  let group_size = ctx.chunk_size(input.len());
+  let groups = ctx.groups();
+
+  let mut binned = vec![NumT::nan(); input.len()];
+  ta_bins(ctx, &mut binned, input, bins)?;
+
+  let bin_t = NumT::from(bin).unwrap();
+  let turnover: Vec<(NumT, NumT)> = (0..group_size)
+    .into_par_iter()
+    .map(|j| {
+      if j == 0 {
+        return (NumT::nan(), NumT::nan());
+      }
+      let (mut cur, mut prev, mut stay) = (0usize, 0usize, 0usize);
+      for i in 0..groups {
+        let idx = i * group_size + j;
+        let now_in = binned[idx] == bin_t;
+        let was_in = binned[idx - 1] == bin_t;
+        cur += now_in as usize;
+        prev += was_in as usize;
+        stay += (now_in && was_in) as usize;
+      }
+      let ratio = |a: usize, b: usize| {
+        if b == 0 {
+          NumT::nan()
+        } else {
+          NumT::from(a).unwrap() / NumT::from(b).unwrap()
+        }
+      };
+      (ratio(cur - stay, cur), ratio(prev - stay, prev))
+    })
+    .collect();
+
+  entering
+    .par_chunks_mut(group_size)
+    .zip(leaving.par_chunks_mut(group_size))
+    .for_each(|(entering, leaving)| {
+      for (j, t) in turnover.iter().enumerate() {
+        entering[j] = t.0;
+        leaving[j] = t.1;
+      }
+    });
+
+  Ok(())
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
-  use crate::algo::assert_vec_eq_nan;
+  use crate::algo::{assert_vec_eq_nan, context::FLAG_STRICTLY_CYCLE};
 
   #[test]
   fn test_ta_ts_rank_simple() {
@@ -506,4 +724,109 @@ mod tests {
       ],
     );
   }
+
+  #[test]
+  fn test_ta_rank_ex_methods() {
+    let input = [3.0, 1.0, 2.0, 1.0, 3.0];
+    let mut r = vec![0.0; input.len()];
+    let ctx = Context::new(0, 0, 0);
+
+    // Position 3: window [1, 2, 1], current 1 ties with the first 1
+    ta_rank_ex(&ctx, &mut r, &input, 3, RANK_AVERAGE).unwrap();
+    assert_vec_eq_nan(&r, &[1.0, 1.0, 2.0, 1.5, 3.0]);
+    ta_rank_ex(&ctx, &mut r, &input, 3, RANK_MIN).unwrap();
+    assert_vec_eq_nan(&r, &[1.0, 1.0, 2.0, 1.0, 3.0]);
+    ta_rank_ex(&ctx, &mut r, &input, 3, RANK_MAX).unwrap();
+    assert_vec_eq_nan(&r, &[1.0, 1.0, 2.0, 2.0, 3.0]);
+    ta_rank_ex(&ctx, &mut r, &input, 3, RANK_DENSE).unwrap();
+    assert_vec_eq_nan(&r, &[1.0, 1.0, 2.0, 1.0, 3.0]);
+    ta_rank_ex(&ctx, &mut r, &input, 3, RANK_ORDINAL).unwrap();
+    assert_vec_eq_nan(&r, &[1.0, 1.0, 2.0, 2.0, 3.0]);
+
+    ta_rank_ex(&ctx, &mut r, &input, 3, RANK_AVERAGE | RANK_PCT).unwrap();
+    assert_vec_eq_nan(&r, &[1.0, 0.5, 2.0 / 3.0, 0.5, 1.0]);
+    ta_rank_ex(&ctx, &mut r, &input, 3, RANK_MIN | RANK_DESCENDING).unwrap();
+    assert_vec_eq_nan(&r, &[1.0, 2.0, 2.0, 2.0, 1.0]);
+  }
+
+  #[test]
+  fn test_ta_rank_ex_nan_and_strict() {
+    let input = [1.0, f64::NAN, 3.0, 2.0];
+    let mut r = vec![0.0; input.len()];
+
+    let ctx = Context::new(0, 0, 0);
+    ta_rank_ex(&ctx, &mut r, &input, 3, RANK_AVERAGE).unwrap();
+    // NaN is excluded from the window: [1] [1, NaN] [1, NaN, 3] [NaN, 3, 2]
+    assert_vec_eq_nan(&r, &[1.0, f64::NAN, 2.0, 1.0]);
+
+    let ctx = Context::new(0, 0, FLAG_STRICTLY_CYCLE);
+    ta_rank_ex(&ctx, &mut r, &input, 3, RANK_AVERAGE).unwrap();
+    assert_vec_eq_nan(&r, &[f64::NAN, f64::NAN, 2.0, 1.0]);
+
+    assert!(ta_rank_ex(&ctx, &mut r, &input, 3, 5).is_err());
+  }
+
+  #[test]
+  fn test_ta_rank_ex_start() {
+    let nan = f64::NAN;
+    // window preloaded from start - periods + 1, values before it are never in it
+    let input = [1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0];
+    let mut r = vec![0.0; input.len()];
+    let ctx = Context::new(5, 0, 0);
+    ta_rank_ex(&ctx, &mut r, &input, 3, RANK_AVERAGE | RANK_PCT).unwrap();
+    assert_vec_eq_nan(&r, &[nan, nan, nan, nan, nan, 1.0, 1.0, 1.0]);
+
+    let input = [1.0; 8];
+    ta_rank_ex(&ctx, &mut r, &input, 3, RANK_AVERAGE).unwrap();
+    assert_vec_eq_nan(&r, &[nan, nan, nan, nan, nan, 2.0, 2.0, 2.0]);
+    ta_rank_ex(&ctx, &mut r, &input, 3, RANK_MAX | RANK_PCT).unwrap();
+    assert_vec_eq_nan(&r, &[nan, nan, nan, nan, nan, 1.0, 1.0, 1.0]);
+  }
+
+  #[test]
+  fn test_ta_cc_rank_ex_methods() {
+    // groups=4, group_size=1
+    let input = [2.0, 1.0, 2.0, f64::NAN];
+    let mut r = vec![0.0; input.len()];
+    let ctx = Context::new(0, 4, 0);
+
+    ta_cc_rank_ex(&ctx, &mut r, &input, RANK_AVERAGE).unwrap();
+    assert_vec_eq_nan(&r, &[2.5, 1.0, 2.5, f64::NAN]);
+    ta_cc_rank_ex(&ctx, &mut r, &input, RANK_MAX).unwrap();
+    assert_vec_eq_nan(&r, &[3.0, 1.0, 3.0, f64::NAN]);
+    ta_cc_rank_ex(&ctx, &mut r, &input, RANK_ORDINAL).unwrap();
+    assert_vec_eq_nan(&r, &[2.0, 1.0, 3.0, f64::NAN]);
+    ta_cc_rank_ex(&ctx, &mut r, &input, RANK_DENSE | RANK_PCT).unwrap();
+    assert_vec_eq_nan(&r, &[1.0, 0.5, 1.0, f64::NAN]);
+    ta_cc_rank_ex(&ctx, &mut r, &input, RANK_ORDINAL | RANK_DESCENDING).unwrap();
+    assert_vec_eq_nan(&r, &[1.0, 3.0, 2.0, f64::NAN]);
+
+    // default method of ta_cc_rank
+    ta_cc_rank_ex(&ctx, &mut r, &input, RANK_AVERAGE | RANK_PCT).unwrap();
+    assert_vec_eq_nan(&r, &[2.5 / 3.0, 1.0 / 3.0, 2.5 / 3.0, f64::NAN]);
+    ta_cc_rank(&ctx, &mut r, &input).unwrap();
+    assert_vec_eq_nan(&r, &[2.5 / 3.0, 1.0 / 3.0, 2.5 / 3.0, f64::NAN]);
+  }
+
+  #[test]
+  fn test_ta_bins_turnover() {
+    // 4 securities, 3 dates, top of 2 bins
+    let ctx = Context::new(0, 4, 0);
+    let input = [1.0, 1.0, 1.0, 2.0, 3.0, 2.0, 3.0, 2.0, 3.0, 4.0, 4.0, 4.0];
+    let mut entering = [0.0; 12];
+    let mut leaving = [0.0; 12];
+    ta_bins_turnover(&ctx, &mut entering, &mut leaving, &input, 2, 1).unwrap();
+    // top: {2, 3} -> {1, 3} -> {2, 3}
+    let nan = f64::NAN;
+    assert_vec_eq_nan(
+      &entering,
+      &[nan, 0.5, 0.5, nan, 0.5, 0.5, nan, 0.5, 0.5, nan, 0.5, 0.5],
+    );
+    assert_vec_eq_nan(
+      &leaving,
+      &[nan, 0.5, 0.5, nan, 0.5, 0.5, nan, 0.5, 0.5, nan, 0.5, 0.5],
+    );
+
+    assert!(ta_bins_turnover(&ctx, &mut entering, &mut leaving, &input, 2, 2).is_err());
+  }
 }
@@ -0,0 +1,317 @@
+// Copyright 2026 MSD-RS Project LiJia
+// SPDX-License-Identifier: BSD-2-Clause
+
+use std::iter;
+
+use num_traits::Float;
+use rayon::prelude::*;
+
+use crate::algo::{Context, Error, beta::beta_of, drawdown::max_drawdown_of, is_normal};
+
+/// Rolling window engine shared by the performance ratios
+///
+/// `f` gets the (input, benchmark) pairs of the window in time order and the
+/// ratio is written when finite. Windows follow `ta_sharpe`: without
+/// FLAG_SKIP_NAN a missing pair in the last `periods` bars gives NaN, with
+/// FLAG_SKIP_NAN the window is the last `periods` valid pairs, and
+/// FLAG_STRICTLY_CYCLE requires a full window.
+fn rolling_ratio<NumT, F>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+  benchmark: &[NumT],
+  periods: usize,
+  f: F,
+) -> Result<(), Error>
+where
+  NumT: Float + Send + Sync,
+  F: Fn(&[(NumT, NumT)]) -> NumT + Send + Sync,
+{
+  if r.len() != input.len() || r.len() != benchmark.len() {
+    return Err(Error::LengthMismatch(r.len(), input.len()));
+  }
+
+  if periods < 2 {
+    return Err(Error::InvalidPeriod(format!(
+      "ratio requires periods >= 2, got {}",
+      periods
+    )));
+  }
+
+  let chunk = ctx.chunk_size(r.len());
+  r.par_chunks_mut(chunk)
+    .zip(input.par_chunks(chunk))
+    .zip(benchmark.par_chunks(chunk))
+    .for_each(|((r, x), y)| {
+      let start = ctx.start(r.len());
+      let end = ctx.end(r.len());
+      r.fill(NumT::nan());
+
+      let valid = |k: usize| is_normal(&x[k]) && is_normal(&y[k]);
+      let mut pairs = Vec::with_capacity(periods);
+      for (i, r) in r.iter_mut().enumerate().take(end).skip(start) {
+        if !valid(i) {
+          continue;
+        }
+
+        pairs.clear();
+        let mut gap = false;
+        if ctx.is_skip_nan() {
+          let mut j = i + 1;
+          while j > 0 && pairs.len() < periods {
+            j -= 1;
+            if valid(j) {
+              pairs.push((x[j], y[j]));
+            } else {
+              gap = true;
+            }
+          }
+        } else {
+          let win_start = (i + 1).saturating_sub(periods);
+          for j in (win_start..=i).rev() {
+            if !valid(j) {
+              gap = true;
+              break;
+            }
+            pairs.push((x[j], y[j]));
+          }
+          if gap {
+            continue;
+          }
+        }
+        if ctx.is_strictly_cycle() && (pairs.len() < periods || gap) {
+          continue;
+        }
+        if pairs.len() < 2 {
+          continue;
+        }
+
+        pairs.reverse();
+        let v = f(&pairs);
+        if v.is_finite() {
+          *r = v;
+        }
+      }
+    });
+
+  Ok(())
+}
+
+fn mean<NumT: Float>(values: impl Iterator<Item = NumT>) -> NumT {
+  let (sum, n) = values.fold((NumT::zero(), 0), |(s, n), v| (s + v, n + 1));
+  sum / NumT::from(n).unwrap()
+}
+
+/// Rolling Sortino Ratio of returns.
+///
+/// Sortino = mean(input - target) / sqrt(mean(min(input - target, 0)^2)) * sqrt(annual),
+/// `target` is the per bar minimum acceptable return, `annual` the number of
+/// bars per year (1 for no annualisation).
+///
+/// Ref: https://en.wikipedia.org/wiki/Sortino_ratio
+///
+pub fn ta_sortino<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+  target: NumT,
+  periods: usize,
+  annual: NumT,
+) -> Result<(), Error> {
+  rolling_ratio(ctx, r, input, input, periods, |w| {
+    let excess = mean(w.iter().map(|p| p.0 - target));
+    let downside = mean(w.iter().map(|p| (p.0 - target).min(NumT::zero()).powi(2)));
+    excess / downside.sqrt() * annual.sqrt()
+  })
+}
+
+/// Rolling Calmar Ratio of returns.
+///
+/// Calmar = annualised compound return / |max drawdown|, the drawdown is
+/// `MAX_DRAWDOWN` of the equity curve compounded from the returns of the window
+/// and starting from 1 before its first bar, so a loss on the first bar is a
+/// drawdown. `annual` is the number of bars per year.
+///
+/// Ref: https://en.wikipedia.org/wiki/Calmar_ratio
+///
+pub fn ta_calmar<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+  periods: usize,
+  annual: NumT,
+) -> Result<(), Error> {
+  rolling_ratio(ctx, r, input, input, periods, |w| {
+    let mut equity = NumT::one();
+    let curve = w.iter().map(|p| {
+      equity = equity * (NumT::one() + p.0);
+      equity
+    });
+    // the curve starts from 1 before the first bar of the window
+    let mdd = max_drawdown_of(iter::once(NumT::one()).chain(curve));
+    let years = NumT::from(w.len()).unwrap() / annual;
+    let cagr = equity.powf(NumT::one() / years) - NumT::one();
+    cagr / mdd.abs()
+  })
+}
+
+/// Rolling Information Ratio of returns against a benchmark.
+///
+/// IR = mean(input - benchmark) / stddev(input - benchmark) * sqrt(annual),
+/// population stddev as `SHARPE`, `annual` is the number of bars per year.
+///
+/// Ref: https://en.wikipedia.org/wiki/Information_ratio
+///
+pub fn ta_information_ratio<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+  benchmark: &[NumT],
+  periods: usize,
+  annual: NumT,
+) -> Result<(), Error> {
+  rolling_ratio(ctx, r, input, benchmark, periods, |w| {
+    let m = mean(w.iter().map(|p| p.0 - p.1));
+    let var = mean(w.iter().map(|p| (p.0 - p.1 - m).powi(2)));
+    m / var.sqrt() * annual.sqrt()
+  })
+}
+
+/// Rolling Omega Ratio of returns.
+///
+/// Omega = sum(max(input - threshold, 0)) / sum(max(threshold - input, 0)),
+/// the probability weighted gains over losses relative to `threshold`.
+///
+/// Ref: https://en.wikipedia.org/wiki/Omega_ratio
+///
+pub fn ta_omega<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+  threshold: NumT,
+  periods: usize,
+) -> Result<(), Error> {
+  rolling_ratio(ctx, r, input, input, periods, |w| {
+    let (gain, loss) = w.iter().fold((NumT::zero(), NumT::zero()), |(g, l), p| {
+      let d = p.0 - threshold;
+      (g + d.max(NumT::zero()), l + (-d).max(NumT::zero()))
+    });
+    gain / loss
+  })
+}
+
+/// Rolling Treynor Ratio of returns against a benchmark.
+///
+/// Treynor = mean(input) * annual / beta, beta = cov(input, benchmark) /
+/// var(benchmark) as `BETA`, returns are expected in excess of the risk free
+/// rate, `annual` is the number of bars per year.
+///
+/// Ref: https://en.wikipedia.org/wiki/Treynor_ratio
+///
+pub fn ta_treynor<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+  benchmark: &[NumT],
+  periods: usize,
+  annual: NumT,
+) -> Result<(), Error> {
+  rolling_ratio(ctx, r, input, benchmark, periods, |w| {
+    let (sum_x, sum_y, sum_xy, sum_y2) = w.iter().fold(
+      (NumT::zero(), NumT::zero(), NumT::zero(), NumT::zero()),
+      |(sx, sy, sxy, sy2), p| (sx + p.0, sy + p.1, sxy + p.0 * p.1, sy2 + p.1 * p.1),
+    );
+    let beta = beta_of(w.len(), sum_x, sum_y, sum_xy, sum_y2);
+    sum_x / NumT::from(w.len()).unwrap() * annual / beta
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::algo::{
+    assert_vec_eq_nan, beta::ta_beta, context::FLAG_SKIP_NAN, drawdown::ta_max_drawdown,
+  };
+
+  #[test]
+  fn test_sortino_omega() {
+    let input = vec![0.02, -0.01, 0.03, -0.02];
+    let ctx = Context::new(0, 1, 0);
+    let mut r = vec![0.0; 4];
+    ta_sortino(&ctx, &mut r, &input, 0.0, 4, 1.0).unwrap();
+    // mean 0.005, downside sqrt((0.0001 + 0.0004) / 4)
+    let expected = 0.005 / (0.0005f64 / 4.0).sqrt();
+    assert!((r[3] - expected).abs() < 1e-12);
+    // partial window of 2 bars, -0.01 is the only downside
+    assert!((r[1] - 0.005 / (0.0001f64 / 2.0).sqrt()).abs() < 1e-12);
+    assert!(r[0].is_nan());
+
+    ta_omega(&ctx, &mut r, &input, 0.0, 4).unwrap();
+    assert!((r[3] - 0.05 / 0.03).abs() < 1e-12);
+    ta_omega(&ctx, &mut r, &input, 0.01, 4).unwrap();
+    assert!((r[3] - 0.03 / 0.05).abs() < 1e-12);
+  }
+
+  #[test]
+  fn test_calmar() {
+    let input = vec![0.1, -0.2, 0.1, 0.05];
+    let ctx = Context::new(0, 1, 0);
+    let mut r = vec![0.0; 4];
+    ta_calmar(&ctx, &mut r, &input, 4, 4.0).unwrap();
+    // equity 1.1, 0.88, 0.968, 1.0164, drawdown -0.2 from 1.1
+    let cagr = 1.1f64 * 0.8 * 1.1 * 1.05 - 1.0;
+    assert!((r[3] - cagr / 0.2).abs() < 1e-12);
+    // a single bar is not a window
+    assert!(r[0].is_nan());
+
+    // a loss on the first bar is a drawdown from the initial 1
+    let input = vec![-0.1, 0.2, -0.05, 0.1];
+    ta_calmar(&ctx, &mut r, &input, 4, 4.0).unwrap();
+    let curve = vec![1.0, 0.9, 1.08, 1.026, 1.1286];
+    let mut mdd = vec![0.0; 5];
+    ta_max_drawdown(&ctx, &mut mdd, &curve, 5).unwrap();
+    assert!((mdd[4] + 0.1).abs() < 1e-12);
+    assert!((r[3] - 0.1286 / 0.1).abs() < 1e-9);
+  }
+
+  #[test]
+  fn test_information_ratio_treynor() {
+    let input = vec![0.02, -0.01, 0.03, -0.02, 0.01];
+    let bench = vec![0.01, -0.02, 0.02, -0.01, 0.0];
+    let ctx = Context::new(0, 1, 0);
+    let mut r = vec![0.0; 5];
+
+    // active returns 0.01, 0.01, 0.01, -0.01, 0.01
+    ta_information_ratio(&ctx, &mut r, &input, &bench, 5, 252.0).unwrap();
+    let var = (4.0 * 0.004f64.powi(2) + 0.016f64.powi(2)) / 5.0;
+    assert!((r[4] - 0.006 / var.sqrt() * 252f64.sqrt()).abs() < 1e-9);
+
+    // zero mean benchmark against itself
+    ta_treynor(&ctx, &mut r, &bench, &bench, 5, 1.0).unwrap();
+    assert!(r[4].abs() < 1e-12);
+    ta_treynor(&ctx, &mut r, &input, &bench, 2, 1.0).unwrap();
+    assert!((r[1] - 0.005 / 1.0).abs() < 1e-12);
+
+    // same beta as BETA
+    let mut beta = vec![0.0; 5];
+    ta_beta(&ctx, &mut beta, &input, &bench, 5).unwrap();
+    ta_treynor(&ctx, &mut r, &input, &bench, 5, 252.0).unwrap();
+    assert!((r[4] - 0.006 * 252.0 / beta[4]).abs() < 1e-9);
+  }
+
+  #[test]
+  fn test_ratio_nan() {
+    let input = vec![0.02, f64::NAN, 0.03, -0.02];
+    let mut r = vec![0.0; 4];
+
+    let ctx = Context::new(0, 1, 0);
+    ta_omega(&ctx, &mut r, &input, 0.0, 2).unwrap();
+    assert_vec_eq_nan(&r, &[f64::NAN, f64::NAN, f64::NAN, 1.5]);
+
+    let ctx = Context::new(0, 1, FLAG_SKIP_NAN);
+    ta_omega(&ctx, &mut r, &input, 0.0, 3).unwrap();
+    assert_vec_eq_nan(&r, &[f64::NAN, f64::NAN, f64::NAN, 2.5]);
+
+    assert!(ta_omega(&ctx, &mut r, &input, 0.0, 1).is_err());
+  }
+}
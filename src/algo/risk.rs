@@ -0,0 +1,826 @@
+// Copyright 2026 MSD-RS Project LiJia
+// SPDX-License-Identifier: BSD-2-Clause
+
+use num_traits::Float;
+use rayon::prelude::*;
+
+use crate::algo::{Context, Error, is_normal};
+
+/// covariance target: sample covariance, no shrinkage
+pub const COV_SAMPLE: usize = 0;
+/// covariance target: Ledoit-Wolf shrinkage towards scaled identity
+pub const COV_LW_IDENTITY: usize = 1;
+/// covariance target: Ledoit-Wolf shrinkage towards constant correlation
+pub const COV_LW_CONST_CORR: usize = 2;
+
+/// Demeaned observations of the window ending at date `t`
+struct Window<NumT> {
+  /// securities with at least 2 distinct valid values
+  secs: Vec<usize>,
+  /// normalized weights of the rows, sum to 1
+  weights: Vec<NumT>,
+  /// demeaned values, rows x secs, missing values are 0
+  x: Vec<NumT>,
+}
+
+fn load_window<NumT: Float>(
+  input: &[NumT],
+  groups: usize,
+  group_size: usize,
+  t: usize,
+  periods: usize,
+  halflife: NumT,
+) -> Window<NumT> {
+  let first = (t + 1).saturating_sub(periods);
+  let rows = t + 1 - first;
+  let mut weights: Vec<NumT> = (first..=t)
+    .map(|s| {
+      if halflife > NumT::zero() {
+        NumT::from(0.5)
+          .unwrap()
+          .powf(NumT::from(t - s).unwrap() / halflife)
+      } else {
+        NumT::one()
+      }
+    })
+    .collect();
+  let total = weights.iter().fold(NumT::zero(), |a, &b| a + b);
+  weights.iter_mut().for_each(|w| *w = *w / total);
+
+  let mut secs = Vec::with_capacity(groups);
+  let mut cols: Vec<Vec<NumT>> = Vec::with_capacity(groups);
+  for i in 0..groups {
+    let values = &input[i * group_size + first..i * group_size + t + 1];
+    let mut sum = NumT::zero();
+    let mut sum_w = NumT::zero();
+    let mut count = 0;
+    let mut min = NumT::infinity();
+    let mut max = NumT::neg_infinity();
+    for (x, &w) in values.iter().zip(&weights) {
+      if is_normal(x) {
+        sum = sum + w * *x;
+        sum_w = sum_w + w;
+        count += 1;
+        min = min.min(*x);
+        max = max.max(*x);
+      }
+    }
+    if count < 2 || min == max {
+      continue;
+    }
+    let mean = sum / sum_w;
+    let col: Vec<NumT> = values
+      .iter()
+      .map(|x| {
+        if is_normal(x) {
+          *x - mean
+        } else {
+          NumT::zero()
+        }
+      })
+      .collect();
+    secs.push(i);
+    cols.push(col);
+  }
+
+  let p = secs.len();
+  let mut x = vec![NumT::zero(); rows * p];
+  for (c, col) in cols.iter().enumerate() {
+    for (s, v) in col.iter().enumerate() {
+      x[s * p + c] = *v;
+    }
+  }
+  Window { secs, weights, x }
+}
+
+/// Weighted (MLE) covariance of the window, p x p
+fn window_cov<NumT: Float>(w: &Window<NumT>) -> Vec<NumT> {
+  let p = w.secs.len();
+  let mut cov = vec![NumT::zero(); p * p];
+  for (s, &ws) in w.weights.iter().enumerate() {
+    let row = &w.x[s * p..(s + 1) * p];
+    for a in 0..p {
+      let xa = ws * row[a];
+      for b in a..p {
+        cov[a * p + b] = cov[a * p + b] + xa * row[b];
+      }
+    }
+  }
+  for a in 0..p {
+    for b in 0..a {
+      cov[a * p + b] = cov[b * p + a];
+    }
+  }
+  cov
+}
+
+/// Shrink `cov` towards `mu * I`, returns the shrinkage intensity
+fn shrink_identity<NumT: Float>(w: &Window<NumT>, cov: &mut [NumT]) -> NumT {
+  let p = w.secs.len();
+  let mu = (0..p).fold(NumT::zero(), |a, i| a + cov[i * p + i]) / NumT::from(p).unwrap();
+
+  let mut d2 = NumT::zero();
+  for a in 0..p {
+    for b in 0..p {
+      let f = if a == b { mu } else { NumT::zero() };
+      d2 = d2 + (cov[a * p + b] - f).powi(2);
+    }
+  }
+
+  let mut b2 = NumT::zero();
+  for (s, &ws) in w.weights.iter().enumerate() {
+    let row = &w.x[s * p..(s + 1) * p];
+    let mut dist = NumT::zero();
+    for a in 0..p {
+      for b in 0..p {
+        dist = dist + (row[a] * row[b] - cov[a * p + b]).powi(2);
+      }
+    }
+    b2 = b2 + ws * ws * dist;
+  }
+
+  let delta = if d2 > NumT::zero() {
+    b2.min(d2) / d2
+  } else {
+    NumT::zero()
+  };
+  for a in 0..p {
+    for b in 0..p {
+      let f = if a == b { mu } else { NumT::zero() };
+      cov[a * p + b] = delta * f + (NumT::one() - delta) * cov[a * p + b];
+    }
+  }
+  delta
+}
+
+/// Shrink `cov` towards the constant correlation matrix, returns the shrinkage intensity
+fn shrink_const_corr<NumT: Float>(w: &Window<NumT>, cov: &mut [NumT]) -> NumT {
+  let p = w.secs.len();
+  if p < 2 {
+    return NumT::zero();
+  }
+  let sd: Vec<NumT> = (0..p).map(|i| cov[i * p + i].sqrt()).collect();
+  let mut sum_corr = NumT::zero();
+  for a in 0..p {
+    for b in 0..p {
+      if a != b {
+        sum_corr = sum_corr + cov[a * p + b] / (sd[a] * sd[b]);
+      }
+    }
+  }
+  let r_bar = sum_corr / NumT::from(p * (p - 1)).unwrap();
+  let target = |a: usize, b: usize| {
+    if a == b {
+      cov[a * p + a]
+    } else {
+      r_bar * sd[a] * sd[b]
+    }
+  };
+
+  // pi: sum of asymptotic variances of the sample covariances
+  // rho: sum of asymptotic covariances between target and sample covariances
+  let mut pi = NumT::zero();
+  let mut rho = NumT::zero();
+  let half = NumT::from(0.5).unwrap();
+  for (s, &ws) in w.weights.iter().enumerate() {
+    let row = &w.x[s * p..(s + 1) * p];
+    for a in 0..p {
+      let da = row[a] * row[a] - cov[a * p + a];
+      for b in 0..p {
+        let dab = row[a] * row[b] - cov[a * p + b];
+        pi = pi + ws * dab * dab;
+        if a != b {
+          let db = row[b] * row[b] - cov[b * p + b];
+          let theta = (sd[b] / sd[a]) * da * dab + (sd[a] / sd[b]) * db * dab;
+          rho = rho + ws * half * r_bar * theta;
+        } else {
+          rho = rho + ws * dab * dab;
+        }
+      }
+    }
+  }
+
+  let mut gamma = NumT::zero();
+  for a in 0..p {
+    for b in 0..p {
+      gamma = gamma + (target(a, b) - cov[a * p + b]).powi(2);
+    }
+  }
+
+  let sum_w2 = w.weights.iter().fold(NumT::zero(), |a, &b| a + b * b);
+  let delta = if gamma > NumT::zero() {
+    ((pi - rho) / gamma * sum_w2)
+      .max(NumT::zero())
+      .min(NumT::one())
+  } else {
+    NumT::zero()
+  };
+  let shrunk: Vec<NumT> = (0..p * p)
+    .map(|k| delta * target(k / p, k % p) + (NumT::one() - delta) * cov[k])
+    .collect();
+  cov.copy_from_slice(&shrunk);
+  delta
+}
+
+/// Covariance matrix of securities at chosen dates
+///
+/// For each date in `dates` (time index), computes the N x N covariance of the
+/// securities over the last `periods` dates, N is `ctx.groups()` and `r` is
+/// the row-major matrices of all dates, `dates.len() * N * N` values. When
+/// `halflife > 0` the observations are weighted exponentially,
+/// weight = 0.5 ^ (age / halflife). Missing values are treated as the mean of
+/// the security, securities with fewer than 2 valid values or constant values
+/// get NaN rows and columns.
+/// `target` selects the estimator:
+/// COV_SAMPLE (0): sample covariance, scaled by 1 / (1 - sum(w^2)), N - 1 when unweighted
+/// COV_LW_IDENTITY (1): Ledoit-Wolf shrinkage towards scaled identity
+/// COV_LW_CONST_CORR (2): Ledoit-Wolf shrinkage towards constant correlation
+/// Ledoit-Wolf estimators shrink the MLE covariance, the shrinkage intensity
+/// of each date is written to `shrinkage`.
+///
+/// Ref: Ledoit, O. and Wolf, M. "A well-conditioned estimator for large-dimensional covariance matrices" (2004),
+/// "Honey, I Shrunk the Sample Covariance Matrix" (2004)
+#[allow(clippy::too_many_arguments)]
+pub fn ta_cov_matrix<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  shrinkage: &mut [NumT],
+  input: &[NumT],
+  dates: &[usize],
+  periods: usize,
+  halflife: NumT,
+  target: usize,
+) -> Result<(), Error> {
+  let groups = ctx.groups();
+  let group_size = ctx.chunk_size(input.len());
+  if input.len() != group_size * groups {
+    return Err(Error::LengthMismatch(input.len(), group_size * groups));
+  }
+  if r.len() != dates.len() * groups * groups {
+    return Err(Error::LengthMismatch(
+      r.len(),
+      dates.len() * groups * groups,
+    ));
+  }
+  if shrinkage.len() != dates.len() {
+    return Err(Error::LengthMismatch(shrinkage.len(), dates.len()));
+  }
+  if periods < 2 {
+    return Err(Error::InvalidPeriod("periods must be >= 2".to_string()));
+  }
+  if !matches!(target, COV_SAMPLE | COV_LW_IDENTITY | COV_LW_CONST_CORR) {
+    return Err(Error::InvalidParameter(format!(
+      "invalid covariance target: {}",
+      target
+    )));
+  }
+  if let Some(t) = dates.iter().find(|&&t| t >= group_size) {
+    return Err(Error::InvalidParameter(format!(
+      "date {} out of range 0..{}",
+      t, group_size
+    )));
+  }
+
+  if groups == 0 || dates.is_empty() {
+    return Ok(());
+  }
+
+  r.par_chunks_mut(groups * groups)
+    .zip(shrinkage.par_iter_mut())
+    .zip(dates.par_iter())
+    .for_each(|((r, shrinkage), &t)| {
+      r.fill(NumT::nan());
+      *shrinkage = NumT::nan();
+
+      let w = load_window(input, groups, group_size, t, periods, halflife);
+      let sum_w2 = w.weights.iter().fold(NumT::zero(), |a, &b| a + b * b);
+      if w.secs.is_empty() || sum_w2 >= NumT::one() {
+        return;
+      }
+
+      let mut cov = window_cov(&w);
+      *shrinkage = match target {
+        COV_LW_IDENTITY => shrink_identity(&w, &mut cov),
+        COV_LW_CONST_CORR => shrink_const_corr(&w, &mut cov),
+        _ => {
+          let scale = NumT::one() / (NumT::one() - sum_w2);
+          cov.iter_mut().for_each(|c| *c = *c * scale);
+          NumT::zero()
+        }
+      };
+
+      let p = w.secs.len();
+      for (a, &i) in w.secs.iter().enumerate() {
+        for (b, &j) in w.secs.iter().enumerate() {
+          r[i * groups + j] = cov[a * p + b];
+        }
+      }
+    });
+
+  Ok(())
+}
+
+/// Eigen decomposition of the symmetric n x n matrix `a` with cyclic Jacobi
+/// rotations, returns (eigenvalues, eigenvectors as columns of row-major n x n),
+/// sorted by eigenvalue descending
+fn sym_eigen<NumT: Float>(mut a: Vec<NumT>, n: usize) -> (Vec<NumT>, Vec<NumT>) {
+  let mut v = vec![NumT::zero(); n * n];
+  for i in 0..n {
+    v[i * n + i] = NumT::one();
+  }
+
+  let eps = NumT::epsilon();
+  for _ in 0..100 {
+    let mut off = NumT::zero();
+    let mut diag = NumT::zero();
+    for i in 0..n {
+      diag = diag + a[i * n + i] * a[i * n + i];
+      for j in (i + 1)..n {
+        off = off + a[i * n + j] * a[i * n + j];
+      }
+    }
+    if off <= eps * eps * diag {
+      break;
+    }
+
+    for p in 0..n {
+      for q in (p + 1)..n {
+        let apq = a[p * n + q];
+        if apq == NumT::zero() {
+          continue;
+        }
+        let two = NumT::from(2).unwrap();
+        let theta = (a[q * n + q] - a[p * n + p]) / (two * apq);
+        let t = theta.signum() / (theta.abs() + (theta * theta + NumT::one()).sqrt());
+        let c = NumT::one() / (t * t + NumT::one()).sqrt();
+        let s = t * c;
+        for k in 0..n {
+          let akp = a[k * n + p];
+          let akq = a[k * n + q];
+          a[k * n + p] = c * akp - s * akq;
+          a[k * n + q] = s * akp + c * akq;
+        }
+        for k in 0..n {
+          let apk = a[p * n + k];
+          let aqk = a[q * n + k];
+          a[p * n + k] = c * apk - s * aqk;
+          a[q * n + k] = s * apk + c * aqk;
+        }
+        for k in 0..n {
+          let vkp = v[k * n + p];
+          let vkq = v[k * n + q];
+          v[k * n + p] = c * vkp - s * vkq;
+          v[k * n + q] = s * vkp + c * vkq;
+        }
+      }
+    }
+  }
+
+  let mut order: Vec<usize> = (0..n).collect();
+  order.sort_by(|&x, &y| {
+    a[y * n + y]
+      .partial_cmp(&a[x * n + x])
+      .unwrap_or(std::cmp::Ordering::Equal)
+  });
+  let values = order.iter().map(|&i| a[i * n + i]).collect();
+  let mut vectors = vec![NumT::zero(); n * n];
+  for (c, &i) in order.iter().enumerate() {
+    for k in 0..n {
+      vectors[k * n + c] = v[k * n + i];
+    }
+  }
+  (values, vectors)
+}
+
+/// Principal components of one date
+struct DatePca<NumT> {
+  /// securities in the window
+  secs: Vec<usize>,
+  /// unit eigenvectors, components x secs, NaN for components beyond the rank
+  loadings: Vec<NumT>,
+  /// explained variance ratio of each component
+  explained: Vec<NumT>,
+}
+
+/// Top `k` principal components of the window ending at date `t`
+fn window_pca<NumT: Float>(
+  input: &[NumT],
+  groups: usize,
+  group_size: usize,
+  t: usize,
+  periods: usize,
+  k: usize,
+) -> DatePca<NumT> {
+  let w = load_window(input, groups, group_size, t, periods, NumT::zero());
+  let p = w.secs.len();
+  let m = w.weights.len();
+  let mut loadings = vec![NumT::nan(); k * p];
+  let mut explained = vec![NumT::nan(); k];
+  if p == 0 {
+    return DatePca {
+      secs: w.secs,
+      loadings,
+      explained,
+    };
+  }
+
+  // eigen decompose the smaller of the p x p covariance and the m x m gram matrix
+  let (values, vectors) = if p <= m {
+    let (values, vectors) = sym_eigen(window_cov(&w), p);
+    (values, vectors)
+  } else {
+    // y = sqrt(w) x, cov = y'y, gram = yy'
+    let y: Vec<NumT> = (0..m * p)
+      .map(|k| w.weights[k / p].sqrt() * w.x[k])
+      .collect();
+    let mut gram = vec![NumT::zero(); m * m];
+    for a in 0..m {
+      for b in a..m {
+        let dot = (0..p).fold(NumT::zero(), |s, c| s + y[a * p + c] * y[b * p + c]);
+        gram[a * m + b] = dot;
+        gram[b * m + a] = dot;
+      }
+    }
+    let (values, u) = sym_eigen(gram, m);
+    // v = y'u / sqrt(lambda), as columns of p x m
+    let mut vectors = vec![NumT::nan(); p * m];
+    for (c, &lambda) in values.iter().enumerate() {
+      if lambda <= NumT::zero() {
+        continue;
+      }
+      let norm = lambda.sqrt();
+      for i in 0..p {
+        let dot = (0..m).fold(NumT::zero(), |s, a| s + y[a * p + i] * u[a * m + c]);
+        vectors[i * m + c] = dot / norm;
+      }
+    }
+    (values, vectors)
+  };
+
+  let cols = values.len();
+  let total = values
+    .iter()
+    .fold(NumT::zero(), |s, &v| s + v.max(NumT::zero()));
+  let tol = total * NumT::epsilon() * NumT::from(cols).unwrap();
+  for c in 0..k.min(cols) {
+    if values[c] <= tol {
+      break;
+    }
+    explained[c] = values[c] / total;
+    // fix the sign so that loadings sum to a non-negative value
+    let sum = (0..p).fold(NumT::zero(), |s, i| s + vectors[i * cols + c]);
+    let sign = if sum < NumT::zero() {
+      -NumT::one()
+    } else {
+      NumT::one()
+    };
+    for i in 0..p {
+      loadings[c * p + i] = sign * vectors[i * cols + c];
+    }
+  }
+
+  DatePca {
+    secs: w.secs,
+    loadings,
+    explained,
+  }
+}
+
+/// Statistical factor model by rolling PCA
+///
+/// At each date, extracts the top `k` principal components of the covariance
+/// of the securities over the last `periods` dates (see `ta_cov_matrix` for
+/// missing values), dates with fewer than `periods` history are NaN. Loadings
+/// are unit eigenvectors with non-negative sum. Outputs:
+/// factors: eigen-factor returns, loadings' * returns of the date (NaN as 0), k x T
+/// explained: explained variance ratio of each component, k x T
+/// loadings: loading panel of each component, k x (N x T)
+/// residuals: returns minus the part explained by the k factors, N x T
+/// Components beyond the rank of the window are NaN.
+#[allow(clippy::too_many_arguments)]
+pub fn ta_pca<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  factors: &mut [NumT],
+  explained: &mut [NumT],
+  loadings: &mut [NumT],
+  residuals: &mut [NumT],
+  input: &[NumT],
+  periods: usize,
+  k: usize,
+) -> Result<(), Error> {
+  let groups = ctx.groups();
+  let group_size = ctx.chunk_size(input.len());
+  if input.len() != group_size * groups {
+    return Err(Error::LengthMismatch(input.len(), group_size * groups));
+  }
+  if residuals.len() != input.len() {
+    return Err(Error::LengthMismatch(residuals.len(), input.len()));
+  }
+  if loadings.len() != k * input.len() {
+    return Err(Error::LengthMismatch(loadings.len(), k * input.len()));
+  }
+  for len in [factors.len(), explained.len()] {
+    if len != k * group_size {
+      return Err(Error::LengthMismatch(len, k * group_size));
+    }
+  }
+  if periods < 2 {
+    return Err(Error::InvalidPeriod("periods must be >= 2".to_string()));
+  }
+  if k == 0 {
+    return Err(Error::InvalidParameter("k must be >= 1".to_string()));
+  }
+
+  let dates: Vec<Option<DatePca<NumT>>> = (0..group_size)
+    .into_par_iter()
+    .map(|t| {
+      if t + 1 < periods {
+        return None;
+      }
+      Some(window_pca(input, groups, group_size, t, periods, k))
+    })
+    .collect();
+
+  factors.fill(NumT::nan());
+  explained.fill(NumT::nan());
+  loadings.fill(NumT::nan());
+  residuals.fill(NumT::nan());
+
+  let panel = input.len();
+  for (t, pca) in dates.iter().enumerate() {
+    let Some(pca) = pca else {
+      continue;
+    };
+    let p = pca.secs.len();
+    let mut fitted = vec![NumT::zero(); p];
+    for c in 0..k {
+      explained[c * group_size + t] = pca.explained[c];
+      let v = &pca.loadings[c * p..(c + 1) * p];
+      if p == 0 || v[0].is_nan() {
+        continue;
+      }
+      let f = pca.secs.iter().zip(v).fold(NumT::zero(), |s, (&i, &l)| {
+        let x = input[i * group_size + t];
+        if is_normal(&x) { s + l * x } else { s }
+      });
+      factors[c * group_size + t] = f;
+      for (a, (&i, &l)) in pca.secs.iter().zip(v).enumerate() {
+        loadings[c * panel + i * group_size + t] = l;
+        fitted[a] = fitted[a] + l * f;
+      }
+    }
+    for (a, &i) in pca.secs.iter().enumerate() {
+      let idx = i * group_size + t;
+      residuals[idx] = input[idx] - fitted[a];
+    }
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::algo::{assert_vec_eq_nan, ta_cov};
+
+  #[test]
+  fn test_cov_matrix_sample() {
+    // 3 securities, 4 dates, security 2 is constant
+    let ctx = Context::new(0, 3, 0);
+    let input = [1.0, 2.0, 4.0, 3.0, 2.0, 1.0, 5.0, 2.0, 7.0, 7.0, 7.0, 7.0];
+    let mut r = [0.0; 18];
+    let mut shrinkage = [0.0; 2];
+    ta_cov_matrix(
+      &ctx,
+      &mut r,
+      &mut shrinkage,
+      &input,
+      &[2, 3],
+      3,
+      0.0,
+      COV_SAMPLE,
+    )
+    .unwrap();
+
+    let mut cov = [0.0; 4];
+    ta_cov(
+      &Context::new(0, 1, 0),
+      &mut cov,
+      &input[0..4],
+      &input[4..8],
+      3,
+    )
+    .unwrap();
+    let mut var0 = [0.0; 4];
+    ta_cov(
+      &Context::new(0, 1, 0),
+      &mut var0,
+      &input[0..4],
+      &input[0..4],
+      3,
+    )
+    .unwrap();
+    let mut var1 = [0.0; 4];
+    ta_cov(
+      &Context::new(0, 1, 0),
+      &mut var1,
+      &input[4..8],
+      &input[4..8],
+      3,
+    )
+    .unwrap();
+
+    let nan = f64::NAN;
+    for (d, t) in [2, 3].into_iter().enumerate() {
+      assert_vec_eq_nan(
+        &r[d * 9..(d + 1) * 9],
+        &[var0[t], cov[t], nan, cov[t], var1[t], nan, nan, nan, nan],
+      );
+    }
+    assert_vec_eq_nan(&shrinkage, &[0.0, 0.0]);
+  }
+
+  #[test]
+  fn test_cov_matrix_ledoit_wolf() {
+    // 2 securities, 4 dates
+    let ctx = Context::new(0, 2, 0);
+    let input = [1.0, 2.0, 4.0, 3.0, 2.0, 1.0, 5.0, 2.0];
+    let mut r = [0.0; 4];
+    let mut shrinkage = [0.0; 1];
+
+    // MLE covariance [[1.25, 1.25], [1.25, 2.25]], mu = 1.75
+    // d2 = |S - mu I|^2 = 3.625, b2 = sum(|x x' - S|^2) / 16 = 2.875
+    ta_cov_matrix(
+      &ctx,
+      &mut r,
+      &mut shrinkage,
+      &input,
+      &[3],
+      4,
+      0.0,
+      COV_LW_IDENTITY,
+    )
+    .unwrap();
+    let delta = 2.875 / 3.625;
+    assert_vec_eq_nan(&shrinkage, &[delta]);
+    assert_vec_eq_nan(
+      &r,
+      &[
+        delta * 1.75 + (1.0 - delta) * 1.25,
+        (1.0 - delta) * 1.25,
+        (1.0 - delta) * 1.25,
+        delta * 1.75 + (1.0 - delta) * 2.25,
+      ],
+    );
+
+    // with 2 securities the constant correlation target equals the sample covariance
+    ta_cov_matrix(
+      &ctx,
+      &mut r,
+      &mut shrinkage,
+      &input,
+      &[3],
+      4,
+      0.0,
+      COV_LW_CONST_CORR,
+    )
+    .unwrap();
+    assert_vec_eq_nan(&r, &[1.25, 1.25, 1.25, 2.25]);
+    assert!((0.0..=1.0).contains(&shrinkage[0]));
+  }
+
+  #[test]
+  fn test_cov_matrix_const_corr() {
+    // 3 securities, 5 dates, expected values from the reference covCor.m algorithm
+    let ctx = Context::new(0, 3, 0);
+    let input = [
+      1.0, 2.0, 4.0, 3.0, 0.0, //
+      2.0, 1.0, 5.0, 2.0, 1.0, //
+      0.5, 1.5, 1.0, 3.0, 2.0,
+    ];
+    let mut r = [0.0; 9];
+    let mut shrinkage = [0.0; 1];
+    ta_cov_matrix(
+      &ctx,
+      &mut r,
+      &mut shrinkage,
+      &input,
+      &[4],
+      5,
+      0.0,
+      COV_LW_CONST_CORR,
+    )
+    .unwrap();
+    assert_vec_eq_nan(&shrinkage, &[0.5768335738030652]);
+    assert_vec_eq_nan(
+      &r,
+      &[
+        2.0,
+        0.8847980290099484,
+        0.16390496266783464,
+        0.8847980290099484,
+        2.16,
+        -0.05137161024451706,
+        0.16390496266783464,
+        -0.05137161024451706,
+        0.74,
+      ],
+    );
+  }
+
+  #[test]
+  fn test_sym_eigen() {
+    let (values, vectors) = sym_eigen(vec![2.0, 1.0, 1.0, 2.0], 2);
+    assert_vec_eq_nan(&values, &[3.0, 1.0]);
+    let h = 0.5f64.sqrt();
+    assert_vec_eq_nan(&[vectors[0].abs(), vectors[2].abs()], &[h, h]);
+    assert!((vectors[0] * vectors[1] + vectors[2] * vectors[3]).abs() < 1e-12);
+  }
+
+  #[test]
+  fn test_pca_single_factor() {
+    // 3 securities with loadings (1, 2, 2) on one factor, 4 dates
+    let ctx = Context::new(0, 3, 0);
+    let f = [1.0, -1.0, 2.0, 0.5];
+    let b = [1.0, 2.0, 2.0];
+    let input: Vec<f64> = b
+      .iter()
+      .flat_map(|b| f.iter().map(move |f| b * f))
+      .collect();
+
+    // periods 4 decomposes the 3 x 3 covariance, periods 2 the 2 x 2 gram matrix
+    for periods in [4, 2] {
+      let k = 2;
+      let mut factors = vec![0.0; k * 4];
+      let mut explained = vec![0.0; k * 4];
+      let mut loadings = vec![0.0; k * 12];
+      let mut residuals = vec![0.0; 12];
+      ta_pca(
+        &ctx,
+        &mut factors,
+        &mut explained,
+        &mut loadings,
+        &mut residuals,
+        &input,
+        periods,
+        k,
+      )
+      .unwrap();
+
+      let t = 3;
+      assert_vec_eq_nan(&[explained[t], explained[4 + t]], &[1.0, f64::NAN]);
+      assert_vec_eq_nan(&[factors[t], factors[4 + t]], &[3.0 * f[t], f64::NAN]);
+      assert_vec_eq_nan(
+        &[loadings[t], loadings[4 + t], loadings[8 + t]],
+        &[1.0 / 3.0, 2.0 / 3.0, 2.0 / 3.0],
+      );
+      assert_vec_eq_nan(&[loadings[12 + t]], &[f64::NAN]);
+      assert_vec_eq_nan(
+        &[residuals[t], residuals[4 + t], residuals[8 + t]],
+        &[0.0, 0.0, 0.0],
+      );
+      // not enough history
+      assert!(explained[periods - 2].is_nan());
+    }
+  }
+
+  #[test]
+  fn test_cov_matrix_halflife() {
+    // 1 security, weights 0.25, 0.5, 1 normalized to 1/7, 2/7, 4/7
+    let ctx = Context::new(0, 1, 0);
+    let input = [1.0, 2.0, 4.0];
+    let mut r = [0.0; 1];
+    let mut shrinkage = [0.0; 1];
+    ta_cov_matrix(
+      &ctx,
+      &mut r,
+      &mut shrinkage,
+      &input,
+      &[2],
+      3,
+      1.0,
+      COV_SAMPLE,
+    )
+    .unwrap();
+    let w = [1.0 / 7.0, 2.0 / 7.0, 4.0 / 7.0];
+    let mean = w[0] * 1.0 + w[1] * 2.0 + w[2] * 4.0;
+    let mle =
+      w[0] * (1.0 - mean).powi(2) + w[1] * (2.0 - mean).powi(2) + w[2] * (4.0 - mean).powi(2);
+    let sum_w2 = w.iter().map(|w| w * w).sum::<f64>();
+    assert_vec_eq_nan(&r, &[mle / (1.0 - sum_w2)]);
+
+    assert!(
+      ta_cov_matrix(
+        &ctx,
+        &mut r,
+        &mut shrinkage,
+        &input,
+        &[3],
+        3,
+        1.0,
+        COV_SAMPLE
+      )
+      .is_err()
+    );
+  }
+}
@@ -1,6 +1,8 @@
 // Copyright 2026 MSD-RS Project LiJia
 // SPDX-License-Identifier: BSD-2-Clause
 
+use std::collections::VecDeque;
+
 use num_traits::Float;
 use rayon::prelude::*;
 
@@ -213,6 +215,324 @@ pub fn ta_count<NumT: Float + Send + Sync>(
   Ok(())
 }
 
+/// Value of `input` at the `n`th most recent bar where `cond` is true, n = 1
+/// is the last one, NaN until `cond` has been true `n` times. With
+/// FLAG_SKIP_NAN a bar where `input` is NaN does not count as an occurrence.
+///
+/// Ref: https://www.amibroker.com/guide/afl/valuewhen.html
+pub fn ta_valuewhen<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+  cond: &[bool],
+  n: usize,
+) -> Result<(), Error> {
+  if r.len() != input.len() || r.len() != cond.len() {
+    return Err(Error::LengthMismatch(r.len(), input.len()));
+  }
+  if n == 0 {
+    return Err(Error::InvalidPeriod(
+      "valuewhen requires n >= 1".to_string(),
+    ));
+  }
+
+  r.par_chunks_mut(ctx.chunk_size(r.len()))
+    .zip(input.par_chunks(ctx.chunk_size(input.len())))
+    .zip(cond.par_chunks(ctx.chunk_size(cond.len())))
+    .for_each(|((r, x), c)| {
+      let start = ctx.start(r.len());
+      let end = ctx.end(r.len());
+      r.fill(NumT::nan());
+
+      let mut history = VecDeque::with_capacity(n);
+      for i in start..end {
+        if c[i] && (is_normal(&x[i]) || !ctx.is_skip_nan()) {
+          if history.len() == n {
+            history.pop_front();
+          }
+          history.push_back(x[i]);
+        }
+        if history.len() == n {
+          r[i] = history[0];
+        }
+      }
+    });
+
+  Ok(())
+}
+
+/// Number of consecutive bars up to the current one where `cond` is true, 0
+/// when `cond` is false
+///
+/// Ref: https://www.amibroker.com/guide/afl/barslastcount.html
+pub fn ta_barslastcount<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  cond: &[bool],
+) -> Result<(), Error> {
+  if r.len() != cond.len() {
+    return Err(Error::LengthMismatch(r.len(), cond.len()));
+  }
+
+  r.par_chunks_mut(ctx.chunk_size(r.len()))
+    .zip(cond.par_chunks(ctx.chunk_size(cond.len())))
+    .for_each(|(r, c)| {
+      let start = ctx.start(r.len());
+      let end = ctx.end(r.len());
+      r.fill(NumT::nan());
+
+      let mut count = 0;
+      for i in start..end {
+        count = if c[i] { count + 1 } else { 0 };
+        r[i] = NumT::from(count).unwrap();
+      }
+    });
+
+  Ok(())
+}
+
+/// Signal debounce of 通达信 FILTER(X, N), true where `cond` is true and the
+/// following `n` bars are forced to false
+pub fn ta_filter(ctx: &Context, r: &mut [bool], cond: &[bool], n: usize) -> Result<(), Error> {
+  if r.len() != cond.len() {
+    return Err(Error::LengthMismatch(r.len(), cond.len()));
+  }
+
+  r.par_chunks_mut(ctx.chunk_size(r.len()))
+    .zip(cond.par_chunks(ctx.chunk_size(cond.len())))
+    .for_each(|(r, c)| {
+      let start = ctx.start(r.len());
+      let end = ctx.end(r.len());
+      r.fill(false);
+
+      let mut blocked = 0;
+      for i in start..end {
+        if blocked > 0 {
+          blocked -= 1;
+        } else if c[i] {
+          r[i] = true;
+          blocked = n;
+        }
+      }
+    });
+
+  Ok(())
+}
+
+/// True on the bars where `cond` is true and the following `periods - 1`
+/// bars, i.e. `cond` was true within the last `periods` bars
+///
+/// Ref: https://www.amibroker.com/guide/afl/hold.html
+pub fn ta_hold(ctx: &Context, r: &mut [bool], cond: &[bool], periods: usize) -> Result<(), Error> {
+  if r.len() != cond.len() {
+    return Err(Error::LengthMismatch(r.len(), cond.len()));
+  }
+  if periods == 0 {
+    return Err(Error::InvalidPeriod(
+      "hold requires periods >= 1".to_string(),
+    ));
+  }
+
+  r.par_chunks_mut(ctx.chunk_size(r.len()))
+    .zip(cond.par_chunks(ctx.chunk_size(cond.len())))
+    .for_each(|(r, c)| {
+      let start = ctx.start(r.len());
+      let end = ctx.end(r.len());
+      r.fill(false);
+
+      let mut remain = 0;
+      for i in start..end {
+        if c[i] {
+          remain = periods;
+        }
+        if remain > 0 {
+          r[i] = true;
+          remain -= 1;
+        }
+      }
+    });
+
+  Ok(())
+}
+
+/// Flip-flop, true from a bar where `on` is true until a bar where `off` is
+/// true, `off` wins when both are true on the same bar
+///
+/// Ref: https://www.amibroker.com/guide/afl/flip.html
+pub fn ta_flip(ctx: &Context, r: &mut [bool], on: &[bool], off: &[bool]) -> Result<(), Error> {
+  if r.len() != on.len() || r.len() != off.len() {
+    return Err(Error::LengthMismatch(r.len(), on.len()));
+  }
+
+  r.par_chunks_mut(ctx.chunk_size(r.len()))
+    .zip(on.par_chunks(ctx.chunk_size(on.len())))
+    .zip(off.par_chunks(ctx.chunk_size(off.len())))
+    .for_each(|((r, s), e)| {
+      let start = ctx.start(r.len());
+      let end = ctx.end(r.len());
+      r.fill(false);
+
+      let mut state = false;
+      for i in start..end {
+        if e[i] {
+          state = false;
+        } else if s[i] {
+          state = true;
+        }
+        r[i] = state;
+      }
+    });
+
+  Ok(())
+}
+
+/// Accumulate `input` with `f` from the last bar where `cond` is true, that
+/// bar included, NaN before the first one. Without FLAG_SKIP_NAN a NaN value
+/// makes the result NaN until `cond` restarts it.
+fn since_impl<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+  cond: &[bool],
+  f: impl Fn(NumT, NumT) -> NumT + Send + Sync,
+) -> Result<(), Error> {
+  if r.len() != input.len() || r.len() != cond.len() {
+    return Err(Error::LengthMismatch(r.len(), input.len()));
+  }
+
+  r.par_chunks_mut(ctx.chunk_size(r.len()))
+    .zip(input.par_chunks(ctx.chunk_size(input.len())))
+    .zip(cond.par_chunks(ctx.chunk_size(cond.len())))
+    .for_each(|((r, x), c)| {
+      let start = ctx.start(r.len());
+      let end = ctx.end(r.len());
+      r.fill(NumT::nan());
+
+      let mut started = false;
+      let mut poisoned = false;
+      let mut acc = NumT::nan();
+      for i in start..end {
+        if c[i] {
+          started = true;
+          poisoned = false;
+          acc = NumT::nan();
+        }
+        if !started {
+          continue;
+        }
+        if !is_normal(&x[i]) {
+          poisoned |= !ctx.is_skip_nan();
+        } else if !poisoned {
+          acc = if acc.is_nan() { x[i] } else { f(acc, x[i]) };
+        }
+        if !poisoned {
+          r[i] = acc;
+        }
+      }
+    });
+
+  Ok(())
+}
+
+/// Sum of `input` since the last bar where `cond` is true, that bar included,
+/// NaN before `cond` is first true
+///
+/// Ref: https://www.amibroker.com/guide/afl/sumsince.html
+pub fn ta_sumsince<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+  cond: &[bool],
+) -> Result<(), Error> {
+  since_impl(ctx, r, input, cond, |a, b| a + b)
+}
+
+/// Highest `input` since the last bar where `cond` is true, that bar
+/// included, NaN before `cond` is first true
+pub fn ta_hhvsince<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+  cond: &[bool],
+) -> Result<(), Error> {
+  since_impl(ctx, r, input, cond, NumT::max)
+}
+
+/// Lowest `input` since the last bar where `cond` is true, that bar included,
+/// NaN before `cond` is first true
+pub fn ta_llvsince<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+  cond: &[bool],
+) -> Result<(), Error> {
+  since_impl(ctx, r, input, cond, NumT::min)
+}
+
+/// 通达信 CONST, the last value of `input` in each group repeated over the
+/// group, the last valid one with FLAG_SKIP_NAN. It looks ahead by design,
+/// do not use it for signals.
+pub fn ta_constant<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  input: &[NumT],
+) -> Result<(), Error> {
+  if r.len() != input.len() {
+    return Err(Error::LengthMismatch(r.len(), input.len()));
+  }
+
+  r.par_chunks_mut(ctx.chunk_size(r.len()))
+    .zip(input.par_chunks(ctx.chunk_size(input.len())))
+    .for_each(|(r, x)| {
+      let start = ctx.start(r.len());
+      let end = ctx.end(r.len());
+      r.fill(NumT::nan());
+
+      let last = if ctx.is_skip_nan() {
+        x[start..end].iter().rev().copied().find(is_normal)
+      } else {
+        x[start..end].last().copied()
+      };
+      if let Some(v) = last {
+        r[start..end].fill(v);
+      }
+    });
+
+  Ok(())
+}
+
+/// 通达信 BETWEEN(A, B, C), true when `a` is between `b` and `c` inclusive,
+/// in either order, false on NaN
+pub fn ta_between<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [bool],
+  a: &[NumT],
+  b: &[NumT],
+  c: &[NumT],
+) -> Result<(), Error> {
+  if r.len() != a.len() || r.len() != b.len() || r.len() != c.len() {
+    return Err(Error::LengthMismatch(r.len(), a.len()));
+  }
+
+  r.par_chunks_mut(ctx.chunk_size(r.len()))
+    .zip(a.par_chunks(ctx.chunk_size(a.len())))
+    .zip(b.par_chunks(ctx.chunk_size(b.len())))
+    .zip(c.par_chunks(ctx.chunk_size(c.len())))
+    .for_each(|(((r, a), b), c)| {
+      let start = ctx.start(r.len());
+      let end = ctx.end(r.len());
+      r.fill(false);
+
+      for i in start..end {
+        if is_normal(&a[i]) && is_normal(&b[i]) && is_normal(&c[i]) {
+          r[i] = b[i].min(c[i]) <= a[i] && a[i] <= b[i].max(c[i]);
+        }
+      }
+    });
+
+  Ok(())
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -288,4 +608,85 @@ mod tests {
     ta_count(&ctx, &mut r, &input, 3).unwrap();
     assert_vec_eq_nan(&r, &vec![f64::NAN, f64::NAN, 2.0]);
   }
+
+  #[test]
+  fn test_valuewhen() {
+    let cond = vec![false, true, false, true, true, false];
+    let input = vec![1.0, 2.0, 3.0, f64::NAN, 5.0, 6.0];
+    let mut r = vec![0.0; input.len()];
+    let nan = f64::NAN;
+
+    let ctx = Context::new(0, 0, 0);
+    ta_valuewhen(&ctx, &mut r, &input, &cond, 1).unwrap();
+    assert_vec_eq_nan(&r, &[nan, 2.0, 2.0, nan, 5.0, 5.0]);
+    ta_valuewhen(&ctx, &mut r, &input, &cond, 2).unwrap();
+    assert_vec_eq_nan(&r, &[nan, nan, nan, 2.0, nan, nan]);
+
+    let ctx = Context::new(0, 0, FLAG_SKIP_NAN);
+    ta_valuewhen(&ctx, &mut r, &input, &cond, 2).unwrap();
+    assert_vec_eq_nan(&r, &[nan, nan, nan, nan, 2.0, 2.0]);
+  }
+
+  #[test]
+  fn test_barslastcount_filter() {
+    let cond = vec![true, true, false, true, true, true, false];
+    let mut r = vec![0.0; cond.len()];
+    let ctx = Context::new(0, 0, 0);
+    ta_barslastcount(&ctx, &mut r, &cond).unwrap();
+    assert_vec_eq_nan(&r, &[1.0, 2.0, 0.0, 1.0, 2.0, 3.0, 0.0]);
+
+    let mut b = vec![false; cond.len()];
+    ta_filter(&ctx, &mut b, &cond, 2).unwrap();
+    assert_eq!(b, [true, false, false, true, false, false, false]);
+  }
+
+  #[test]
+  fn test_hold_flip() {
+    let on = vec![true, false, false, false, true, false];
+    let off = vec![false, false, true, false, true, false];
+    let mut r = vec![false; on.len()];
+    let ctx = Context::new(0, 0, 0);
+    ta_hold(&ctx, &mut r, &on, 2).unwrap();
+    assert_eq!(r, [true, true, false, false, true, true]);
+
+    // off wins on the same bar
+    ta_flip(&ctx, &mut r, &on, &off).unwrap();
+    assert_eq!(r, [true, true, false, false, false, false]);
+  }
+
+  #[test]
+  fn test_since() {
+    let cond = vec![false, true, false, false, true, false];
+    let input = vec![9.0, 1.0, 3.0, f64::NAN, 2.0, 1.0];
+    let mut r = vec![0.0; input.len()];
+    let nan = f64::NAN;
+
+    let ctx = Context::new(0, 0, 0);
+    ta_sumsince(&ctx, &mut r, &input, &cond).unwrap();
+    assert_vec_eq_nan(&r, &[nan, 1.0, 4.0, nan, 2.0, 3.0]);
+
+    let ctx = Context::new(0, 0, FLAG_SKIP_NAN);
+    ta_sumsince(&ctx, &mut r, &input, &cond).unwrap();
+    assert_vec_eq_nan(&r, &[nan, 1.0, 4.0, 4.0, 2.0, 3.0]);
+    ta_hhvsince(&ctx, &mut r, &input, &cond).unwrap();
+    assert_vec_eq_nan(&r, &[nan, 1.0, 3.0, 3.0, 2.0, 2.0]);
+    ta_llvsince(&ctx, &mut r, &input, &cond).unwrap();
+    assert_vec_eq_nan(&r, &[nan, 1.0, 1.0, 1.0, 2.0, 1.0]);
+  }
+
+  #[test]
+  fn test_const_between() {
+    let input = vec![1.0, 2.0, 3.0, 4.0, 5.0, f64::NAN];
+    let mut r = vec![0.0; input.len()];
+    // two groups
+    let ctx = Context::new(0, 2, FLAG_SKIP_NAN);
+    ta_constant(&ctx, &mut r, &input).unwrap();
+    assert_vec_eq_nan(&r, &[3.0, 3.0, 3.0, 5.0, 5.0, 5.0]);
+
+    let lo = vec![0.0, 3.0, 2.0, 5.0, 5.0, 1.0];
+    let hi = vec![2.0, 1.0, 3.0, 6.0, f64::NAN, 9.0];
+    let mut b = vec![false; input.len()];
+    ta_between(&ctx, &mut b, &input, &lo, &hi).unwrap();
+    assert_eq!(b, [true, true, true, false, false, false]);
+  }
 }
@@ -0,0 +1,516 @@
+// Copyright 2026 MSD-RS Project LiJia
+// SPDX-License-Identifier: BSD-2-Clause
+
+use std::collections::VecDeque;
+
+use num_traits::Float;
+use rayon::prelude::*;
+
+use super::{
+  band::{Atr, true_range},
+  bars::run_bars,
+  oscillator::{clear_before_start, full_range},
+};
+use crate::algo::{Context, Error, is_normal, ta_hhvbars, ta_llvbars};
+
+/// Directional Movement Index
+struct Dmi<NumT> {
+  n: usize,
+  prev: Option<(NumT, NumT, NumT)>,
+  count: usize,
+  plus_dm: NumT,
+  minus_dm: NumT,
+  tr: NumT,
+  dx_count: usize,
+  adx: NumT,
+  history: VecDeque<NumT>,
+}
+
+impl<NumT: Float> Dmi<NumT> {
+  fn new(n: usize) -> Self {
+    Self {
+      n,
+      prev: None,
+      count: 0,
+      plus_dm: NumT::zero(),
+      minus_dm: NumT::zero(),
+      tr: NumT::zero(),
+      dx_count: 0,
+      adx: NumT::zero(),
+      history: VecDeque::with_capacity(n + 1),
+    }
+  }
+
+  /// [+DI, -DI, ADX, ADXR] after this bar
+  fn update(&mut self, h: NumT, l: NumT, c: NumT) -> Option<[NumT; 4]> {
+    let (ph, pl, pc) = self.prev.replace((h, l, c))?;
+    let up = h - ph;
+    let down = pl - l;
+    let zero = NumT::zero();
+    let plus_dm = if up > zero && up > down { up } else { zero };
+    let minus_dm = if down > zero && down > up { down } else { zero };
+    let tr = true_range(h, l, pc);
+
+    // Wilder smoothing on sums, seeded with the first n - 1 values
+    let n = NumT::from(self.n).unwrap();
+    self.count += 1;
+    if self.count < self.n {
+      self.plus_dm = self.plus_dm + plus_dm;
+      self.minus_dm = self.minus_dm + minus_dm;
+      self.tr = self.tr + tr;
+      return None;
+    }
+    self.plus_dm = self.plus_dm - self.plus_dm / n + plus_dm;
+    self.minus_dm = self.minus_dm - self.minus_dm / n + minus_dm;
+    self.tr = self.tr - self.tr / n + tr;
+
+    let hundred = NumT::from(100).unwrap();
+    let (pdi, mdi) = if self.tr > zero {
+      (
+        hundred * self.plus_dm / self.tr,
+        hundred * self.minus_dm / self.tr,
+      )
+    } else {
+      (zero, zero)
+    };
+    let dx = if pdi + mdi > zero {
+      hundred * (pdi - mdi).abs() / (pdi + mdi)
+    } else {
+      zero
+    };
+
+    self.dx_count += 1;
+    let mut adx = NumT::nan();
+    let mut adxr = NumT::nan();
+    if self.dx_count < self.n {
+      self.adx = self.adx + dx;
+    } else {
+      self.adx = if self.dx_count == self.n {
+        (self.adx + dx) / n
+      } else {
+        (self.adx * (n - NumT::one()) + dx) / n
+      };
+      adx = self.adx;
+      self.history.push_back(adx);
+      if self.history.len() > self.n {
+        self.history.pop_front();
+      }
+      if self.history.len() == self.n {
+        adxr = (adx + self.history[0]) / NumT::from(2).unwrap();
+      }
+    }
+    Some([pdi, mdi, adx, adxr])
+  }
+}
+
+/// Directional Movement Index
+///
+/// +DI and -DI are Wilder smoothed directional movements over true range,
+/// ADX is the Wilder average of DX = 100 * |+DI - -DI| / (+DI + -DI), ADXR is
+/// the mean of ADX and ADX `periods - 1` bars ago, same as TA-Lib PLUS_DI,
+/// MINUS_DI, ADX and ADXR.
+///
+/// With FLAG_SKIP_NAN missing bars are skipped, otherwise a missing bar
+/// restarts the warm-up.
+///
+/// Ref: https://en.wikipedia.org/wiki/Average_directional_movement_index
+#[allow(clippy::too_many_arguments)]
+pub fn ta_dmi<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  plus_di: &mut [NumT],
+  minus_di: &mut [NumT],
+  adx: &mut [NumT],
+  adxr: &mut [NumT],
+  high: &[NumT],
+  low: &[NumT],
+  close: &[NumT],
+  periods: usize,
+) -> Result<(), Error> {
+  if periods < 2 {
+    return Err(Error::InvalidPeriod(format!(
+      "dmi requires periods >= 2, got {}",
+      periods
+    )));
+  }
+
+  run_bars(
+    ctx,
+    [plus_di, minus_di, adx, adxr],
+    [high, low, close],
+    || Dmi::new(periods),
+    |s, [h, l, c]| s.update(h, l, c),
+  )
+}
+
+/// Aroon Indicator
+///
+/// up = 100 * (periods - bars since the highest high) / periods over the last
+/// `periods + 1` bars, down likewise with the lowest low, oscillator is up -
+/// down, same as TA-Lib AROON and AROONOSC after the warm-up.
+///
+/// Ref: https://www.investopedia.com/terms/a/aroon.asp
+pub fn ta_aroon<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  up: &mut [NumT],
+  down: &mut [NumT],
+  osc: &mut [NumT],
+  high: &[NumT],
+  low: &[NumT],
+  periods: usize,
+) -> Result<(), Error> {
+  if periods == 0 {
+    return Err(Error::InvalidPeriod(
+      "aroon requires periods >= 1".to_string(),
+    ));
+  }
+  for len in [up.len(), down.len(), osc.len(), low.len()] {
+    if len != high.len() {
+      return Err(Error::LengthMismatch(len, high.len()));
+    }
+  }
+
+  let full = full_range(ctx);
+  ta_hhvbars(&full, up, high, periods + 1)?;
+  ta_llvbars(&full, down, low, periods + 1)?;
+
+  let n = NumT::from(periods).unwrap();
+  let hundred = NumT::from(100).unwrap();
+  up.par_iter_mut()
+    .zip(down.par_iter_mut())
+    .zip(osc.par_iter_mut())
+    .for_each(|((up, down), osc)| {
+      *up = hundred * (n - *up) / n;
+      *down = hundred * (n - *down) / n;
+      *osc = *up - *down;
+    });
+  clear_before_start(ctx, up);
+  clear_before_start(ctx, down);
+  clear_before_start(ctx, osc);
+  Ok(())
+}
+
+/// Parabolic SAR
+struct Sar<NumT> {
+  acceleration: NumT,
+  maximum: NumT,
+  last: Option<(NumT, NumT)>,
+  started: bool,
+  is_long: bool,
+  sar: NumT,
+  ep: NumT,
+  af: NumT,
+}
+
+impl<NumT: Float> Sar<NumT> {
+  fn new(acceleration: NumT, maximum: NumT) -> Self {
+    Self {
+      acceleration,
+      maximum,
+      last: None,
+      started: false,
+      is_long: true,
+      sar: NumT::zero(),
+      ep: NumT::zero(),
+      af: acceleration,
+    }
+  }
+
+  /// [SAR, direction] of this bar
+  fn update(&mut self, h: NumT, l: NumT) -> Option<[NumT; 2]> {
+    let (ph, pl) = self.last.replace((h, l))?;
+    let (prev_h, prev_l) = if self.started {
+      (ph, pl)
+    } else {
+      // direction from the directional movement of the first two bars
+      let minus_dm = pl - l;
+      self.is_long = !(minus_dm > NumT::zero() && h - ph < minus_dm);
+      (self.ep, self.sar) = if self.is_long { (h, pl) } else { (l, ph) };
+      self.started = true;
+      (h, l)
+    };
+
+    let out;
+    if self.is_long {
+      if l <= self.sar {
+        // reverse to short
+        self.is_long = false;
+        self.sar = self.ep.max(prev_h).max(h);
+        out = self.sar;
+        self.af = self.acceleration;
+        self.ep = l;
+        self.sar = (self.sar + self.af * (self.ep - self.sar))
+          .max(prev_h)
+          .max(h);
+      } else {
+        out = self.sar;
+        if h > self.ep {
+          self.ep = h;
+          self.af = (self.af + self.acceleration).min(self.maximum);
+        }
+        self.sar = (self.sar + self.af * (self.ep - self.sar))
+          .min(prev_l)
+          .min(l);
+      }
+    } else if h >= self.sar {
+      // reverse to long
+      self.is_long = true;
+      self.sar = self.ep.min(prev_l).min(l);
+      out = self.sar;
+      self.af = self.acceleration;
+      self.ep = h;
+      self.sar = (self.sar + self.af * (self.ep - self.sar))
+        .min(prev_l)
+        .min(l);
+    } else {
+      out = self.sar;
+      if l < self.ep {
+        self.ep = l;
+        self.af = (self.af + self.acceleration).min(self.maximum);
+      }
+      self.sar = (self.sar + self.af * (self.ep - self.sar))
+        .max(prev_h)
+        .max(h);
+    }
+
+    let dir = if self.is_long {
+      NumT::one()
+    } else {
+      -NumT::one()
+    };
+    Some([out, dir])
+  }
+}
+
+/// Parabolic SAR
+///
+/// Stop and reverse points with acceleration factor starting at
+/// `acceleration`, increased by it at each new extreme up to `maximum`, same
+/// as TA-Lib SAR. `direction` is 1 for long and -1 for short after the bar.
+///
+/// Ref: https://en.wikipedia.org/wiki/Parabolic_SAR
+#[allow(clippy::too_many_arguments)]
+pub fn ta_sar<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  sar: &mut [NumT],
+  direction: &mut [NumT],
+  high: &[NumT],
+  low: &[NumT],
+  acceleration: NumT,
+  maximum: NumT,
+) -> Result<(), Error> {
+  if !(is_normal(&acceleration) && acceleration > NumT::zero() && maximum >= acceleration) {
+    return Err(Error::InvalidParameter(
+      "sar requires 0 < acceleration <= maximum".to_string(),
+    ));
+  }
+
+  run_bars(
+    ctx,
+    [sar, direction],
+    [high, low],
+    || Sar::new(acceleration, maximum),
+    |s, [h, l]| s.update(h, l),
+  )
+}
+
+/// Supertrend
+struct Supertrend<NumT> {
+  atr: Atr<NumT>,
+  multiplier: NumT,
+  prev_close: NumT,
+  upper: NumT,
+  lower: NumT,
+  dir: NumT,
+}
+
+impl<NumT: Float> Supertrend<NumT> {
+  /// [supertrend, direction] after this bar
+  fn update(&mut self, h: NumT, l: NumT, c: NumT) -> Option<[NumT; 2]> {
+    let atr = self.atr.update(h, l, c);
+    let prev_close = std::mem::replace(&mut self.prev_close, c);
+    let atr = atr?;
+
+    let mid = (h + l) / NumT::from(2).unwrap();
+    let upper = mid + self.multiplier * atr;
+    let lower = mid - self.multiplier * atr;
+    if self.dir.is_nan() {
+      self.upper = upper;
+      self.lower = lower;
+      self.dir = if c >= mid { NumT::one() } else { -NumT::one() };
+    } else {
+      // bands only tighten until price crosses them
+      if upper < self.upper || prev_close > self.upper {
+        self.upper = upper;
+      }
+      if lower > self.lower || prev_close < self.lower {
+        self.lower = lower;
+      }
+      if self.dir < NumT::zero() && c > self.upper {
+        self.dir = NumT::one();
+      } else if self.dir > NumT::zero() && c < self.lower {
+        self.dir = -NumT::one();
+      }
+    }
+
+    let line = if self.dir > NumT::zero() {
+      self.lower
+    } else {
+      self.upper
+    };
+    Some([line, self.dir])
+  }
+}
+
+/// Supertrend
+///
+/// Bands at (high + low) / 2 -/+ multiplier * ATR(periods), the lower band
+/// only rises in an up trend and the upper band only falls in a down trend,
+/// the trend flips when close crosses the opposite band. Outputs the lower
+/// band in an up trend and the upper band in a down trend, `direction` is 1
+/// or -1, the first direction is up when close >= (high + low) / 2.
+///
+/// Ref: https://www.investopedia.com/supertrend-indicator-7976167
+#[allow(clippy::too_many_arguments)]
+pub fn ta_supertrend<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  line: &mut [NumT],
+  direction: &mut [NumT],
+  high: &[NumT],
+  low: &[NumT],
+  close: &[NumT],
+  periods: usize,
+  multiplier: NumT,
+) -> Result<(), Error> {
+  if periods == 0 {
+    return Err(Error::InvalidPeriod(
+      "supertrend requires periods >= 1".to_string(),
+    ));
+  }
+
+  run_bars(
+    ctx,
+    [line, direction],
+    [high, low, close],
+    || Supertrend {
+      atr: Atr::new(periods),
+      multiplier,
+      prev_close: NumT::nan(),
+      upper: NumT::nan(),
+      lower: NumT::nan(),
+      dir: NumT::nan(),
+    },
+    |s, [h, l, c]| s.update(h, l, c),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::algo::{assert_vec_eq_nan, context::FLAG_SKIP_NAN};
+
+  #[test]
+  fn test_dmi() {
+    // steady rise: +DI 100, -DI 0, ADX 100
+    let high: Vec<f64> = (0..6).map(|i| 11.0 + i as f64).collect();
+    let low: Vec<f64> = (0..6).map(|i| 10.0 + i as f64).collect();
+    let n = high.len();
+    let (mut p, mut m, mut adx, mut adxr) =
+      (vec![0.0; n], vec![0.0; n], vec![0.0; n], vec![0.0; n]);
+    let ctx = Context::new(0, 1, 0);
+    ta_dmi(
+      &ctx, &mut p, &mut m, &mut adx, &mut adxr, &high, &low, &high, 2,
+    )
+    .unwrap();
+    let nan = f64::NAN;
+    assert_vec_eq_nan(&p, &[nan, nan, 100.0, 100.0, 100.0, 100.0]);
+    assert_vec_eq_nan(&m, &[nan, nan, 0.0, 0.0, 0.0, 0.0]);
+    assert_vec_eq_nan(&adx, &[nan, nan, nan, 100.0, 100.0, 100.0]);
+    assert_vec_eq_nan(&adxr, &[nan, nan, nan, nan, 100.0, 100.0]);
+
+    // opposite moves cancel in DX
+    let high = [10.0, 11.0, 11.0, 12.0];
+    let low = [9.0, 9.0, 8.0, 8.0];
+    let close = [9.5, 10.0, 9.0, 11.0];
+    let mut p = vec![0.0; 4];
+    let mut m = vec![0.0; 4];
+    let (mut adx, mut adxr) = (vec![0.0; 4], vec![0.0; 4]);
+    ta_dmi(
+      &ctx, &mut p, &mut m, &mut adx, &mut adxr, &high, &low, &close, 2,
+    )
+    .unwrap();
+    // tr 2, 3, 4; +dm 1, 0, 1; -dm 0, 1, 0
+    let tr = 2.0 - 2.0 / 2.0 + 3.0;
+    assert!((p[2] - 100.0 * 0.5 / tr).abs() < 1e-12);
+    assert!((m[2] - 100.0 * 1.0 / tr).abs() < 1e-12);
+
+    assert!(
+      ta_dmi(
+        &ctx, &mut p, &mut m, &mut adx, &mut adxr, &high, &low, &close, 1
+      )
+      .is_err()
+    );
+  }
+
+  #[test]
+  fn test_aroon() {
+    let high = [1.0, 3.0, 2.0, 2.5, 1.0];
+    let low = [0.5, 2.0, 1.0, 0.8, 0.9];
+    let (mut up, mut down, mut osc) = (vec![0.0; 5], vec![0.0; 5], vec![0.0; 5]);
+    let ctx = Context::new(0, 1, 0);
+    ta_aroon(&ctx, &mut up, &mut down, &mut osc, &high, &low, 2).unwrap();
+    // window of 3 bars ending at 2: high 1 bar ago, low 2 bars ago
+    assert_eq!(up[2], 50.0);
+    assert_eq!(down[2], 0.0);
+    assert_eq!(osc[2], 50.0);
+    // window ending at 4: high 2.5 1 bar ago, low 0.8 1 bar ago
+    assert_eq!(up[4], 50.0);
+    assert_eq!(down[4], 50.0);
+    assert_eq!(osc[4], 0.0);
+
+    let mut osc = vec![0.0; 4];
+    assert!(ta_aroon(&ctx, &mut up, &mut down, &mut osc, &high, &low, 2).is_err());
+  }
+
+  #[test]
+  fn test_sar() {
+    let high = [10.0, 11.0, 12.0, 13.0, 12.5];
+    let low = [9.0, 10.0, 11.0, 12.0, 8.0];
+    let (mut sar, mut dir) = (vec![0.0; 5], vec![0.0; 5]);
+    let ctx = Context::new(0, 1, 0);
+    ta_sar(&ctx, &mut sar, &mut dir, &high, &low, 0.02, 0.2).unwrap();
+
+    let s2 = 9.04 + 0.04 * (12.0 - 9.04);
+    assert_vec_eq_nan(&sar, &[f64::NAN, 9.0, 9.04, s2, 13.0]);
+    assert_vec_eq_nan(&dir, &[f64::NAN, 1.0, 1.0, 1.0, -1.0]);
+
+    assert!(ta_sar(&ctx, &mut sar, &mut dir, &high, &low, 0.2, 0.02).is_err());
+  }
+
+  #[test]
+  fn test_supertrend() {
+    let close = [10.0, 11.0, 12.0, 13.0, 14.0, 9.0, 8.0];
+    let high: Vec<f64> = close.iter().map(|c| c + 0.5).collect();
+    let low: Vec<f64> = close.iter().map(|c| c - 0.5).collect();
+    let (mut line, mut dir) = (vec![0.0; 7], vec![0.0; 7]);
+    let ctx = Context::new(0, 1, 0);
+    ta_supertrend(&ctx, &mut line, &mut dir, &high, &low, &close, 2, 1.0).unwrap();
+
+    assert!(line[1].is_nan());
+    // atr at 2 = (1.5 + 1.5) / 2, lower band = 12 - 1.5
+    assert_eq!(line[2], 10.5);
+    assert_vec_eq_nan(&dir[2..], &[1.0, 1.0, 1.0, -1.0, -1.0]);
+    // lower band only rises in the up trend
+    assert!(line[3] >= line[2] && line[4] >= line[3]);
+    // the down trend follows the upper band
+    assert!(line[5] > close[5]);
+
+    // missing bar restarts, unless skipped
+    let mut c = close;
+    c[3] = f64::NAN;
+    ta_supertrend(&ctx, &mut line, &mut dir, &high, &low, &c, 2, 1.0).unwrap();
+    assert!(line[3..6].iter().all(|v| v.is_nan()));
+    let ctx = Context::new(0, 1, FLAG_SKIP_NAN);
+    ta_supertrend(&ctx, &mut line, &mut dir, &high, &low, &c, 2, 1.0).unwrap();
+    assert!(line[3].is_nan() && line[4].is_finite());
+  }
+}
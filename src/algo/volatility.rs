@@ -0,0 +1,379 @@
+// Copyright 2026 MSD-RS Project LiJia
+// SPDX-License-Identifier: BSD-2-Clause
+
+use std::collections::VecDeque;
+
+use num_traits::Float;
+use rayon::prelude::*;
+
+use crate::algo::{Context, Error, is_normal};
+
+fn is_price<NumT: Float>(x: NumT) -> bool {
+  is_normal(&x) && x > NumT::zero()
+}
+
+/// Rolling volatility over per bar terms
+///
+/// `term(group, i)` gives the terms of bar i, None for an invalid bar,
+/// `var(window)` gives the per bar variance of a window with at least
+/// `min_count` bars, the result is sqrt(var * annual).
+///
+/// Without FLAG_SKIP_NAN the window is the last `periods` bars and an invalid
+/// bar in it gives NaN, with FLAG_SKIP_NAN it is the last `periods` valid bars.
+/// FLAG_STRICTLY_CYCLE requires a full window.
+fn rolling_vol<NumT, const K: usize, T, V>(
+  ctx: &Context,
+  r: &mut [NumT],
+  periods: usize,
+  annual: NumT,
+  min_count: usize,
+  term: T,
+  var: V,
+) -> Result<(), Error>
+where
+  NumT: Float + Send + Sync,
+  T: Fn(usize, usize) -> Option<[NumT; K]> + Send + Sync,
+  V: Fn(&VecDeque<Option<[NumT; K]>>) -> NumT + Send + Sync,
+{
+  if periods == 0 {
+    return Err(Error::InvalidPeriod(
+      "periods must be greater than 0".to_string(),
+    ));
+  }
+  if !is_normal(&annual) || annual <= NumT::zero() {
+    return Err(Error::InvalidParameter(
+      "annual must be greater than 0".to_string(),
+    ));
+  }
+
+  let group_size = ctx.chunk_size(r.len());
+  r.par_chunks_mut(group_size).enumerate().for_each(|(j, r)| {
+    let start = ctx.start(r.len());
+    let end = ctx.end(r.len());
+    r.fill(NumT::nan());
+
+    let mut window: VecDeque<Option<[NumT; K]>> = VecDeque::with_capacity(periods + 1);
+    let mut invalid = 0;
+    for (i, r) in r.iter_mut().enumerate().take(end) {
+      let t = term(j, i);
+      if t.is_some() || !ctx.is_skip_nan() {
+        if t.is_none() {
+          invalid += 1;
+        }
+        window.push_back(t);
+        if window.len() > periods && window.pop_front().unwrap().is_none() {
+          invalid -= 1;
+        }
+      }
+
+      if i < start || t.is_none() || invalid > 0 {
+        continue;
+      }
+      if ctx.is_strictly_cycle() && window.len() < periods {
+        continue;
+      }
+      if window.len() >= min_count {
+        *r = (var(&window) * annual).sqrt();
+      }
+    }
+  });
+
+  Ok(())
+}
+
+fn window_mean<NumT: Float, const K: usize>(
+  window: &VecDeque<Option<[NumT; K]>>,
+  k: usize,
+) -> NumT {
+  let sum = window
+    .iter()
+    .flatten()
+    .fold(NumT::zero(), |acc, t| acc + t[k]);
+  sum / NumT::from(window.len()).unwrap()
+}
+
+fn window_var<NumT: Float, const K: usize>(window: &VecDeque<Option<[NumT; K]>>, k: usize) -> NumT {
+  let mean = window_mean(window, k);
+  let sum = window
+    .iter()
+    .flatten()
+    .fold(NumT::zero(), |acc, t| acc + (t[k] - mean).powi(2));
+  sum / NumT::from(window.len() - 1).unwrap()
+}
+
+/// Parkinson Volatility
+///
+/// var = mean(ln(high / low)^2) / (4 * ln(2)) over the window,
+/// result is sqrt(var * annual), e.g. annual = 252 for daily bars.
+///
+/// Ref: Parkinson, M. (1980). The Extreme Value Method for Estimating the
+/// Variance of the Rate of Return.
+pub fn ta_parkinson_vol<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  high: &[NumT],
+  low: &[NumT],
+  periods: usize,
+  annual: NumT,
+) -> Result<(), Error> {
+  if r.len() != high.len() || r.len() != low.len() {
+    return Err(Error::LengthMismatch(r.len(), high.len()));
+  }
+
+  let group_size = ctx.chunk_size(r.len());
+  let factor = NumT::from(4.0 * 2f64.ln()).unwrap();
+  rolling_vol(
+    ctx,
+    r,
+    periods,
+    annual,
+    1,
+    |j, i| {
+      let (h, l) = (high[j * group_size + i], low[j * group_size + i]);
+      (is_price(h) && is_price(l)).then(|| [(h / l).ln().powi(2)])
+    },
+    |window| window_mean(window, 0) / factor,
+  )
+}
+
+/// Garman-Klass Volatility
+///
+/// var = mean(0.5 * ln(high / low)^2 - (2 * ln(2) - 1) * ln(close / open)^2),
+/// result is sqrt(var * annual).
+///
+/// Ref: Garman, M. B., Klass, M. J. (1980). On the Estimation of Security
+/// Price Volatilities from Historical Data.
+#[allow(clippy::too_many_arguments)]
+pub fn ta_garman_klass_vol<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  open: &[NumT],
+  high: &[NumT],
+  low: &[NumT],
+  close: &[NumT],
+  periods: usize,
+  annual: NumT,
+) -> Result<(), Error> {
+  if r.len() != open.len()
+    || r.len() != high.len()
+    || r.len() != low.len()
+    || r.len() != close.len()
+  {
+    return Err(Error::LengthMismatch(r.len(), open.len()));
+  }
+
+  let group_size = ctx.chunk_size(r.len());
+  let half = NumT::from(0.5).unwrap();
+  let k = NumT::from(2.0 * 2f64.ln() - 1.0).unwrap();
+  rolling_vol(
+    ctx,
+    r,
+    periods,
+    annual,
+    1,
+    |j, i| {
+      let n = j * group_size + i;
+      let (o, h, l, c) = (open[n], high[n], low[n], close[n]);
+      (is_price(o) && is_price(h) && is_price(l) && is_price(c))
+        .then(|| [half * (h / l).ln().powi(2) - k * (c / o).ln().powi(2)])
+    },
+    |window| window_mean(window, 0),
+  )
+}
+
+fn rogers_satchell<NumT: Float>(o: NumT, h: NumT, l: NumT, c: NumT) -> NumT {
+  (h / c).ln() * (h / o).ln() + (l / c).ln() * (l / o).ln()
+}
+
+/// Rogers-Satchell Volatility
+///
+/// var = mean(ln(high / close) * ln(high / open) + ln(low / close) * ln(low / open)),
+/// result is sqrt(var * annual), unbiased under a drift.
+///
+/// Ref: Rogers, L. C. G., Satchell, S. E. (1991). Estimating Variance From
+/// High, Low and Closing Prices.
+#[allow(clippy::too_many_arguments)]
+pub fn ta_rogers_satchell_vol<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  open: &[NumT],
+  high: &[NumT],
+  low: &[NumT],
+  close: &[NumT],
+  periods: usize,
+  annual: NumT,
+) -> Result<(), Error> {
+  if r.len() != open.len()
+    || r.len() != high.len()
+    || r.len() != low.len()
+    || r.len() != close.len()
+  {
+    return Err(Error::LengthMismatch(r.len(), open.len()));
+  }
+
+  let group_size = ctx.chunk_size(r.len());
+  rolling_vol(
+    ctx,
+    r,
+    periods,
+    annual,
+    1,
+    |j, i| {
+      let n = j * group_size + i;
+      let (o, h, l, c) = (open[n], high[n], low[n], close[n]);
+      (is_price(o) && is_price(h) && is_price(l) && is_price(c))
+        .then(|| [rogers_satchell(o, h, l, c)])
+    },
+    |window| window_mean(window, 0),
+  )
+}
+
+/// Yang-Zhang Volatility
+///
+/// var = var(overnight) + k * var(open to close) + (1 - k) * RS,
+/// k = 0.34 / (1.34 + (n + 1) / (n - 1)), overnight = ln(open / previous close),
+/// RS is the Rogers-Satchell variance, result is sqrt(var * annual).
+/// A bar needs the previous close, at least 2 bars are required.
+///
+/// Ref: Yang, D., Zhang, Q. (2000). Drift-Independent Volatility Estimation
+/// Based on High, Low, Open, and Close Prices.
+#[allow(clippy::too_many_arguments)]
+pub fn ta_yang_zhang_vol<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  open: &[NumT],
+  high: &[NumT],
+  low: &[NumT],
+  close: &[NumT],
+  periods: usize,
+  annual: NumT,
+) -> Result<(), Error> {
+  if r.len() != open.len()
+    || r.len() != high.len()
+    || r.len() != low.len()
+    || r.len() != close.len()
+  {
+    return Err(Error::LengthMismatch(r.len(), open.len()));
+  }
+
+  let group_size = ctx.chunk_size(r.len());
+  rolling_vol(
+    ctx,
+    r,
+    periods,
+    annual,
+    2,
+    |j, i| {
+      if i == 0 {
+        return None;
+      }
+      let n = j * group_size + i;
+      let (o, h, l, c, pc) = (open[n], high[n], low[n], close[n], close[n - 1]);
+      (is_price(o) && is_price(h) && is_price(l) && is_price(c) && is_price(pc))
+        .then(|| [(o / pc).ln(), (c / o).ln(), rogers_satchell(o, h, l, c)])
+    },
+    |window| {
+      let n = NumT::from(window.len()).unwrap();
+      let k = NumT::from(0.34).unwrap()
+        / (NumT::from(1.34).unwrap() + (n + NumT::one()) / (n - NumT::one()));
+      window_var(window, 0) + k * window_var(window, 1) + (NumT::one() - k) * window_mean(window, 2)
+    },
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::algo::{assert_vec_eq_nan, context::FLAG_SKIP_NAN};
+
+  const OPEN: [f64; 4] = [10.0, 10.5, 10.2, 10.8];
+  const HIGH: [f64; 4] = [10.8, 10.9, 10.9, 11.2];
+  const LOW: [f64; 4] = [9.9, 10.1, 10.0, 10.6];
+  const CLOSE: [f64; 4] = [10.4, 10.3, 10.7, 11.0];
+
+  #[test]
+  fn test_parkinson_vol() {
+    let ctx = Context::new(0, 1, 0);
+    let mut r = vec![0.0; 4];
+    ta_parkinson_vol(&ctx, &mut r, &HIGH, &LOW, 2, 1.0).unwrap();
+
+    let term = |i: usize| (HIGH[i] / LOW[i]).ln().powi(2);
+    let f = 4.0 * 2f64.ln();
+    let expected = vec![
+      (term(0) / f).sqrt(),
+      ((term(0) + term(1)) / 2.0 / f).sqrt(),
+      ((term(1) + term(2)) / 2.0 / f).sqrt(),
+      ((term(2) + term(3)) / 2.0 / f).sqrt(),
+    ];
+    assert_vec_eq_nan(&r, &expected);
+
+    // annualised
+    let mut a = vec![0.0; 4];
+    ta_parkinson_vol(&ctx, &mut a, &HIGH, &LOW, 2, 252.0).unwrap();
+    assert!((a[3] - r[3] * 252f64.sqrt()).abs() < 1e-12);
+
+    assert!(ta_parkinson_vol(&ctx, &mut r, &HIGH, &LOW, 0, 1.0).is_err());
+  }
+
+  #[test]
+  fn test_garman_klass_rogers_satchell_vol() {
+    let ctx = Context::new(0, 1, 0);
+    let mut gk = vec![0.0; 4];
+    let mut rs = vec![0.0; 4];
+    ta_garman_klass_vol(&ctx, &mut gk, &OPEN, &HIGH, &LOW, &CLOSE, 4, 1.0).unwrap();
+    ta_rogers_satchell_vol(&ctx, &mut rs, &OPEN, &HIGH, &LOW, &CLOSE, 4, 1.0).unwrap();
+
+    let gk_term = |i: usize| {
+      0.5 * (HIGH[i] / LOW[i]).ln().powi(2)
+        - (2.0 * 2f64.ln() - 1.0) * (CLOSE[i] / OPEN[i]).ln().powi(2)
+    };
+    let rs_term = |i: usize| rogers_satchell(OPEN[i], HIGH[i], LOW[i], CLOSE[i]);
+    let gk_var = (0..4).map(gk_term).sum::<f64>() / 4.0;
+    let rs_var = (0..4).map(rs_term).sum::<f64>() / 4.0;
+    assert!((gk[3] - gk_var.sqrt()).abs() < 1e-12);
+    assert!((rs[3] - rs_var.sqrt()).abs() < 1e-12);
+    assert!((rs[0] - rs_term(0).sqrt()).abs() < 1e-12);
+  }
+
+  #[test]
+  fn test_yang_zhang_vol() {
+    let ctx = Context::new(0, 1, 0);
+    let mut r = vec![0.0; 4];
+    ta_yang_zhang_vol(&ctx, &mut r, &OPEN, &HIGH, &LOW, &CLOSE, 3, 1.0).unwrap();
+
+    let on: Vec<f64> = (1..4).map(|i| (OPEN[i] / CLOSE[i - 1]).ln()).collect();
+    let oc: Vec<f64> = (1..4).map(|i| (CLOSE[i] / OPEN[i]).ln()).collect();
+    let rs: Vec<f64> = (1..4)
+      .map(|i| rogers_satchell(OPEN[i], HIGH[i], LOW[i], CLOSE[i]))
+      .collect();
+    let var = |x: &[f64]| {
+      let m = x.iter().sum::<f64>() / x.len() as f64;
+      x.iter().map(|v| (v - m).powi(2)).sum::<f64>() / (x.len() - 1) as f64
+    };
+    let k = 0.34 / (1.34 + 4.0 / 2.0);
+    let expected = var(&on) + k * var(&oc) + (1.0 - k) * rs.iter().sum::<f64>() / 3.0;
+
+    // the first bar has no previous close, so only the last window is valid
+    assert_vec_eq_nan(&r[..3], &[f64::NAN; 3]);
+    assert!((r[3] - expected.sqrt()).abs() < 1e-12);
+  }
+
+  #[test]
+  fn test_range_vol_nan() {
+    let high = [10.8, f64::NAN, 10.9, 11.2];
+    let mut r = vec![0.0; 4];
+
+    let ctx = Context::new(0, 1, 0);
+    ta_parkinson_vol(&ctx, &mut r, &high, &LOW, 2, 1.0).unwrap();
+    assert!(r[0].is_finite());
+    assert_vec_eq_nan(&r[1..3], &[f64::NAN, f64::NAN]);
+    assert!(r[3].is_finite());
+
+    let ctx = Context::new(0, 1, FLAG_SKIP_NAN);
+    ta_parkinson_vol(&ctx, &mut r, &high, &LOW, 2, 1.0).unwrap();
+    let term = |i: usize| (high[i] / LOW[i]).ln().powi(2);
+    let f = 4.0 * 2f64.ln();
+    assert!(r[1].is_nan());
+    assert!((r[2] - ((term(0) + term(2)) / 2.0 / f).sqrt()).abs() < 1e-12);
+  }
+}
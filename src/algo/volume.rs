@@ -0,0 +1,354 @@
+// Copyright 2026 MSD-RS Project LiJia
+// SPDX-License-Identifier: BSD-2-Clause
+
+use num_traits::Float;
+
+use super::bars::{run_bars, run_window};
+use crate::algo::{Context, Error};
+
+/// Close location value, ((close - low) - (high - close)) / (high - low), 0 on
+/// a bar without range
+fn clv<NumT: Float>(h: NumT, l: NumT, c: NumT) -> NumT {
+  if h > l {
+    ((c - l) - (h - c)) / (h - l)
+  } else {
+    NumT::zero()
+  }
+}
+
+fn check_periods(name: &str, periods: usize) -> Result<(), Error> {
+  if periods == 0 {
+    return Err(Error::InvalidPeriod(format!(
+      "{} requires periods >= 1",
+      name
+    )));
+  }
+  Ok(())
+}
+
+/// On Balance Volume
+///
+/// OBV = OBV[-1] + volume if close > ref(close, 1), - volume if close <
+/// ref(close, 1), starting with the first volume as TA-Lib. The running total
+/// restarts after a missing bar unless FLAG_SKIP_NAN.
+///
+/// Ref: https://www.investopedia.com/terms/o/onbalancevolume.asp
+pub fn ta_obv<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  close: &[NumT],
+  volume: &[NumT],
+) -> Result<(), Error> {
+  run_bars(
+    ctx,
+    [r],
+    [close, volume],
+    || None,
+    |state: &mut Option<(NumT, NumT)>, [c, v]| {
+      let obv = match *state {
+        None => v,
+        Some((pc, obv)) if c > pc => obv + v,
+        Some((pc, obv)) if c < pc => obv - v,
+        Some((_, obv)) => obv,
+      };
+      *state = Some((c, obv));
+      Some([obv])
+    },
+  )
+}
+
+/// Accumulation / Distribution Line
+///
+/// AD = AD[-1] + CLV * volume, CLV = ((close - low) - (high - close)) /
+/// (high - low) and 0 when high == low. The running total restarts after a
+/// missing bar unless FLAG_SKIP_NAN.
+///
+/// Ref: https://www.investopedia.com/terms/a/accumulationdistribution.asp
+pub fn ta_ad<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  high: &[NumT],
+  low: &[NumT],
+  close: &[NumT],
+  volume: &[NumT],
+) -> Result<(), Error> {
+  run_bars(
+    ctx,
+    [r],
+    [high, low, close, volume],
+    || NumT::zero(),
+    |ad, [h, l, c, v]| {
+      *ad = *ad + clv(h, l, c) * v;
+      Some([*ad])
+    },
+  )
+}
+
+/// Chaikin A/D Oscillator
+///
+/// ADOSC = EMA(AD, fast) - EMA(AD, slow), both EMAs are seeded with the first
+/// AD value and the first max(fast, slow) - 1 bars are NaN as TA-Lib.
+///
+/// Ref: https://www.investopedia.com/terms/c/chaikinoscillator.asp
+#[allow(clippy::too_many_arguments)]
+pub fn ta_adosc<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  high: &[NumT],
+  low: &[NumT],
+  close: &[NumT],
+  volume: &[NumT],
+  fast: usize,
+  slow: usize,
+) -> Result<(), Error> {
+  check_periods("adosc", fast.min(slow))?;
+
+  let two = NumT::from(2.0).unwrap();
+  let k_fast = two / NumT::from(fast + 1).unwrap();
+  let k_slow = two / NumT::from(slow + 1).unwrap();
+  let lookback = fast.max(slow) - 1;
+  run_bars(
+    ctx,
+    [r],
+    [high, low, close, volume],
+    || (NumT::zero(), NumT::nan(), NumT::nan(), 0),
+    |(ad, ema_fast, ema_slow, count), [h, l, c, v]| {
+      *ad = *ad + clv(h, l, c) * v;
+      if *count == 0 {
+        *ema_fast = *ad;
+        *ema_slow = *ad;
+      } else {
+        *ema_fast = *ema_fast + k_fast * (*ad - *ema_fast);
+        *ema_slow = *ema_slow + k_slow * (*ad - *ema_slow);
+      }
+      *count += 1;
+      if *count <= lookback {
+        return None;
+      }
+      Some([*ema_fast - *ema_slow])
+    },
+  )
+}
+
+/// Chaikin Money Flow
+///
+/// CMF = sum(CLV * volume, periods) / sum(volume, periods), NaN when the window
+/// has no volume.
+///
+/// Ref: https://www.investopedia.com/ask/answers/071414/whats-difference-between-chaikin-money-flow-cmf-and-money-flow-index-mfi.asp
+pub fn ta_cmf<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  high: &[NumT],
+  low: &[NumT],
+  close: &[NumT],
+  volume: &[NumT],
+  periods: usize,
+) -> Result<(), Error> {
+  run_window(ctx, [r], [high, low, close, volume], periods, |w| {
+    let (flow, vol) = w
+      .iter()
+      .fold((NumT::zero(), NumT::zero()), |(f, s), [h, l, c, v]| {
+        (f + clv(*h, *l, *c) * *v, s + *v)
+      });
+    [if vol > NumT::zero() {
+      flow / vol
+    } else {
+      NumT::nan()
+    }]
+  })
+}
+
+/// Force Index
+///
+/// FI = EMA((close - ref(close, 1)) * volume, periods), the EMA is seeded with
+/// the first raw value and under FLAG_STRICTLY_CYCLE the first `periods - 1` raw
+/// values are NaN.
+///
+/// Ref: https://www.investopedia.com/terms/f/force-index.asp
+pub fn ta_force<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  close: &[NumT],
+  volume: &[NumT],
+  periods: usize,
+) -> Result<(), Error> {
+  check_periods("force", periods)?;
+
+  let k = NumT::from(2.0).unwrap() / NumT::from(periods + 1).unwrap();
+  let strict = ctx.is_strictly_cycle();
+  run_bars(
+    ctx,
+    [r],
+    [close, volume],
+    || (None, NumT::nan(), 0),
+    |(prev, fi, count): &mut (Option<NumT>, NumT, usize), [c, v]| {
+      let raw = (c - prev.replace(c)?) * v;
+      *fi = if *count == 0 {
+        raw
+      } else {
+        *fi + k * (raw - *fi)
+      };
+      *count += 1;
+      if strict && *count < periods {
+        return None;
+      }
+      Some([*fi])
+    },
+  )
+}
+
+/// Ease of Movement
+///
+/// EMV = MA(distance / box ratio, periods) with distance = (high + low) / 2 -
+/// ref((high + low) / 2, 1) and box ratio = volume / scale / (high - low). A
+/// bar without range counts as 0, a bar without volume is left out of the
+/// average. `scale` is usually 1e8 for share volume.
+///
+/// Ref: https://www.investopedia.com/terms/e/easeofmovement.asp
+pub fn ta_emv<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  high: &[NumT],
+  low: &[NumT],
+  volume: &[NumT],
+  periods: usize,
+  scale: NumT,
+) -> Result<(), Error> {
+  check_periods("emv", periods)?;
+
+  let two = NumT::from(2.0).unwrap();
+  run_window(ctx, [r], [high, low, volume], periods + 1, |w| {
+    let (sum, n) =
+      w.iter()
+        .zip(w.iter().skip(1))
+        .fold((NumT::zero(), 0), |(s, n), ([ph, pl, _], [h, l, v])| {
+          if *v <= NumT::zero() {
+            (s, n)
+          } else {
+            let distance = (*h + *l) / two - (*ph + *pl) / two;
+            (s + distance * (*h - *l) * scale / *v, n + 1)
+          }
+        });
+    [if n > 0 {
+      sum / NumT::from(n).unwrap()
+    } else {
+      NumT::nan()
+    }]
+  })
+}
+
+/// Rolling Volume Weighted Average Price
+///
+/// VWAP = sum(price * volume, periods) / sum(volume, periods), NaN when the
+/// window has no volume.
+///
+/// Ref: https://www.investopedia.com/terms/v/vwap.asp
+pub fn ta_ts_vwap<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  r: &mut [NumT],
+  price: &[NumT],
+  volume: &[NumT],
+  periods: usize,
+) -> Result<(), Error> {
+  run_window(ctx, [r], [price, volume], periods, |w| {
+    let (amount, vol) = w
+      .iter()
+      .fold((NumT::zero(), NumT::zero()), |(a, s), [p, v]| {
+        (a + *p * *v, s + *v)
+      });
+    [if vol > NumT::zero() {
+      amount / vol
+    } else {
+      NumT::nan()
+    }]
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::algo::{
+    assert_vec_eq_nan,
+    context::{FLAG_SKIP_NAN, FLAG_STRICTLY_CYCLE},
+  };
+
+  #[test]
+  fn test_obv_ad() {
+    let close = vec![1.0, 2.0, 2.0, 1.0];
+    let volume = vec![10.0, 20.0, 30.0, 40.0];
+    let mut r = vec![0.0; 4];
+    let ctx = Context::new(0, 1, 0);
+    ta_obv(&ctx, &mut r, &close, &volume).unwrap();
+    assert_vec_eq_nan(&r, &[10.0, 30.0, 30.0, -10.0]);
+
+    // close at the high, the middle, the low and a flat bar
+    let high = vec![2.0, 2.0, 2.0, 1.0];
+    let low = vec![0.0, 0.0, 0.0, 1.0];
+    let close = vec![2.0, 1.0, 0.0, 1.0];
+    ta_ad(&ctx, &mut r, &high, &low, &close, &volume).unwrap();
+    assert_vec_eq_nan(&r, &[10.0, 10.0, -20.0, -20.0]);
+
+    // fast EMA(1) is AD, slow EMA(2) 10, 10, -10, -10 - 20 / 3
+    ta_adosc(&ctx, &mut r, &high, &low, &close, &volume, 1, 2).unwrap();
+    assert_vec_eq_nan(&r, &[f64::NAN, 0.0, -10.0, -10.0 + 20.0 / 3.0]);
+  }
+
+  #[test]
+  fn test_cmf_vwap() {
+    let high = vec![2.0, 2.0, 2.0];
+    let low = vec![0.0, 0.0, 0.0];
+    let close = vec![2.0, 1.0, 0.0];
+    let volume = vec![10.0, 0.0, 30.0];
+    let mut r = vec![0.0; 3];
+    let ctx = Context::new(0, 1, FLAG_STRICTLY_CYCLE);
+
+    ta_cmf(&ctx, &mut r, &high, &low, &close, &volume, 2).unwrap();
+    assert_vec_eq_nan(&r, &[f64::NAN, 1.0, -1.0]);
+
+    let price = vec![10.0, 11.0, 12.0];
+    ta_ts_vwap(&ctx, &mut r, &price, &volume, 2).unwrap();
+    assert_vec_eq_nan(&r, &[f64::NAN, 10.0, 12.0]);
+    // no volume in the window
+    ta_ts_vwap(&ctx, &mut r, &price, &[0.0, 0.0, 5.0], 2).unwrap();
+    assert_vec_eq_nan(&r, &[f64::NAN, f64::NAN, 12.0]);
+  }
+
+  #[test]
+  fn test_force_emv() {
+    let close = vec![1.0, 2.0, 4.0];
+    let volume = vec![10.0, 10.0, 10.0];
+    let mut r = vec![0.0; 3];
+
+    let ctx = Context::new(0, 1, 0);
+    ta_force(&ctx, &mut r, &close, &volume, 2).unwrap();
+    assert_vec_eq_nan(&r, &[f64::NAN, 10.0, 10.0 + 2.0 / 3.0 * 10.0]);
+    let ctx = Context::new(0, 1, FLAG_STRICTLY_CYCLE);
+    ta_force(&ctx, &mut r, &close, &volume, 2).unwrap();
+    assert_vec_eq_nan(&r, &[f64::NAN, f64::NAN, 10.0 + 2.0 / 3.0 * 10.0]);
+
+    // distance 2, box ratio (2e8 / 1e8) / 2
+    let high = vec![2.0, 4.0, 4.0];
+    let low = vec![0.0, 2.0, 2.0];
+    let ctx = Context::new(0, 1, 0);
+    ta_emv(&ctx, &mut r, &high, &low, &[1e8, 2e8, 0.0], 1, 1e8).unwrap();
+    assert_vec_eq_nan(&r, &[f64::NAN, 2.0, f64::NAN]);
+    ta_emv(&ctx, &mut r, &high, &low, &[1e8, 2e8, 0.0], 2, 1e8).unwrap();
+    assert_vec_eq_nan(&r, &[f64::NAN, 2.0, 2.0]);
+  }
+
+  #[test]
+  fn test_volume_nan() {
+    let close = vec![1.0, 2.0, f64::NAN, 3.0];
+    let volume = vec![10.0, 20.0, 30.0, 40.0];
+    let mut r = vec![0.0; 4];
+
+    let ctx = Context::new(0, 1, 0);
+    ta_obv(&ctx, &mut r, &close, &volume).unwrap();
+    assert_vec_eq_nan(&r, &[10.0, 30.0, f64::NAN, 40.0]);
+
+    let ctx = Context::new(0, 1, FLAG_SKIP_NAN);
+    ta_obv(&ctx, &mut r, &close, &volume).unwrap();
+    assert_vec_eq_nan(&r, &[10.0, 30.0, f64::NAN, 70.0]);
+  }
+}
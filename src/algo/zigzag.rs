@@ -0,0 +1,293 @@
+// Copyright 2026 MSD-RS Project LiJia
+// SPDX-License-Identifier: BSD-2-Clause
+
+use num_traits::Float;
+use rayon::prelude::*;
+
+use crate::algo::{Context, Error, is_normal};
+
+/// Percent reversal pivot detector, a pivot is confirmed once the input moves
+/// `percent` away from the running extreme
+struct ZigZag<NumT> {
+  pct: NumT,
+  /// 1 rising, -1 falling, 0 before the first reversal
+  trend: i32,
+  hi: (usize, NumT),
+  lo: (usize, NumT),
+}
+
+impl<NumT: Float> ZigZag<NumT> {
+  fn new(pct: NumT, i: usize, x: NumT) -> Self {
+    Self {
+      pct,
+      trend: 0,
+      hi: (i, x),
+      lo: (i, x),
+    }
+  }
+
+  fn up(&self, from: NumT, x: NumT) -> bool {
+    x >= from + from.abs() * self.pct
+  }
+
+  fn down(&self, from: NumT, x: NumT) -> bool {
+    x <= from - from.abs() * self.pct
+  }
+
+  /// Feed bar `i`, returns the confirmed pivot as (bar, value, is_peak)
+  fn update(&mut self, i: usize, x: NumT) -> Option<(usize, NumT, bool)> {
+    if self.trend >= 0 && x > self.hi.1 {
+      self.hi = (i, x);
+    }
+    if self.trend <= 0 && x < self.lo.1 {
+      self.lo = (i, x);
+    }
+    if self.trend >= 0 && self.down(self.hi.1, x) {
+      self.trend = -1;
+      self.lo = (i, x);
+      return Some((self.hi.0, self.hi.1, true));
+    }
+    if self.trend <= 0 && self.up(self.lo.1, x) {
+      self.trend = 1;
+      self.hi = (i, x);
+      return Some((self.lo.0, self.lo.1, false));
+    }
+    None
+  }
+
+  /// The running extreme of the current leg, not confirmed yet
+  fn extreme(&self) -> Option<(usize, NumT)> {
+    match self.trend {
+      1 => Some(self.hi),
+      -1 => Some(self.lo),
+      _ => None,
+    }
+  }
+}
+
+fn check_percent<NumT: Float>(percent: NumT) -> Result<NumT, Error> {
+  if percent.is_nan() || percent <= NumT::zero() {
+    return Err(Error::InvalidParameter(
+      "zigzag requires percent > 0".to_string(),
+    ));
+  }
+  Ok(percent / NumT::from(100.0).unwrap())
+}
+
+/// ZigZag line with peak / trough markers
+///
+/// A pivot is a running high (low) followed by a fall (rise) of at least
+/// `percent` %, the line joins the first bar, the pivots, the extreme of the
+/// last leg and the last bar linearly, `marker` is 1 at peaks, -1 at troughs
+/// and 0 elsewhere, as ZIG / PEAK / TROUGH of 通达信. Without FLAG_SKIP_NAN
+/// a missing bar starts a new line.
+///
+/// NOTE: pivots are only known once the reversal happens, the line and
+/// markers look ahead and must not be used as signals, see `ZIG_CONFIRMED`.
+///
+/// Ref: https://www.investopedia.com/terms/z/zig_zag_indicator.asp
+pub fn ta_zig<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  zig: &mut [NumT],
+  marker: &mut [NumT],
+  input: &[NumT],
+  percent: NumT,
+) -> Result<(), Error> {
+  if zig.len() != input.len() || marker.len() != input.len() {
+    return Err(Error::LengthMismatch(zig.len(), input.len()));
+  }
+  let pct = check_percent(percent)?;
+
+  let group_size = ctx.chunk_size(input.len());
+  zig
+    .par_chunks_mut(group_size)
+    .zip(marker.par_chunks_mut(group_size))
+    .zip(input.par_chunks(group_size))
+    .for_each(|((zig, marker), x)| {
+      let start = ctx.start(x.len());
+      let end = ctx.end(x.len());
+      zig.fill(NumT::nan());
+      marker.fill(NumT::nan());
+
+      // knots of each line, a knot is (bar, value)
+      let mut lines: Vec<Vec<(usize, NumT)>> = Vec::new();
+      let mut zz: Option<ZigZag<NumT>> = None;
+      let mut last = 0;
+      let close = |lines: &mut Vec<Vec<(usize, NumT)>>, zz: &ZigZag<NumT>, last: usize| {
+        if let Some(knots) = lines.last_mut() {
+          for knot in zz.extreme().into_iter().chain([(last, x[last])]) {
+            if knots.last().is_some_and(|k| k.0 < knot.0) {
+              knots.push(knot);
+            }
+          }
+        }
+      };
+      for i in 0..end {
+        if !is_normal(&x[i]) {
+          if !ctx.is_skip_nan()
+            && let Some(zz) = zz.take()
+          {
+            close(&mut lines, &zz, last);
+          }
+          continue;
+        }
+        marker[i] = NumT::zero();
+        last = i;
+        let Some(zz) = zz.as_mut() else {
+          zz = Some(ZigZag::new(pct, i, x[i]));
+          lines.push(vec![(i, x[i])]);
+          continue;
+        };
+        if let Some((p, v, is_peak)) = zz.update(i, x[i]) {
+          marker[p] = if is_peak { NumT::one() } else { -NumT::one() };
+          let knots = lines.last_mut().unwrap();
+          if knots.last().is_some_and(|k| k.0 < p) {
+            knots.push((p, v));
+          }
+        }
+      }
+      if let Some(zz) = zz {
+        close(&mut lines, &zz, last);
+      }
+
+      for knots in &lines {
+        zig[knots[0].0] = knots[0].1;
+        for w in knots.windows(2) {
+          let ((a, va), (b, vb)) = (w[0], w[1]);
+          let slope = (vb - va) / NumT::from(b - a).unwrap();
+          for (k, z) in zig.iter_mut().enumerate().take(b + 1).skip(a + 1) {
+            if is_normal(&x[k]) {
+              *z = va + slope * NumT::from(k - a).unwrap();
+            }
+          }
+        }
+      }
+      zig[..start].fill(NumT::nan());
+      marker[..start].fill(NumT::nan());
+    });
+
+  Ok(())
+}
+
+/// ZigZag pivots without look-ahead
+///
+/// Same pivots as `ZIG`, but reported on the bar where the `percent` %
+/// reversal confirms them: `marker` is 1 when a peak is confirmed, -1 when a
+/// trough is confirmed and 0 elsewhere, `pivot` holds the value of the last
+/// confirmed pivot and `bars` the number of bars since it, NaN before the
+/// first confirmation. Safe to use in backtests. Without FLAG_SKIP_NAN a
+/// missing bar restarts the detection.
+pub fn ta_zig_confirmed<NumT: Float + Send + Sync>(
+  ctx: &Context,
+  pivot: &mut [NumT],
+  marker: &mut [NumT],
+  bars: &mut [NumT],
+  input: &[NumT],
+  percent: NumT,
+) -> Result<(), Error> {
+  if pivot.len() != input.len() || marker.len() != input.len() || bars.len() != input.len() {
+    return Err(Error::LengthMismatch(pivot.len(), input.len()));
+  }
+  let pct = check_percent(percent)?;
+
+  let group_size = ctx.chunk_size(input.len());
+  pivot
+    .par_chunks_mut(group_size)
+    .zip(marker.par_chunks_mut(group_size))
+    .zip(bars.par_chunks_mut(group_size))
+    .zip(input.par_chunks(group_size))
+    .for_each(|(((pivot, marker), bars), x)| {
+      let start = ctx.start(x.len());
+      let end = ctx.end(x.len());
+      pivot.fill(NumT::nan());
+      marker.fill(NumT::nan());
+      bars.fill(NumT::nan());
+
+      let mut zz: Option<ZigZag<NumT>> = None;
+      let mut confirmed: Option<(usize, NumT)> = None;
+      for i in 0..end {
+        if !is_normal(&x[i]) {
+          if !ctx.is_skip_nan() {
+            zz = None;
+            confirmed = None;
+          }
+          continue;
+        }
+        let mut m = NumT::zero();
+        match zz.as_mut() {
+          None => zz = Some(ZigZag::new(pct, i, x[i])),
+          Some(zz) => {
+            if let Some((p, v, is_peak)) = zz.update(i, x[i]) {
+              confirmed = Some((p, v));
+              m = if is_peak { NumT::one() } else { -NumT::one() };
+            }
+          }
+        }
+        if i < start {
+          continue;
+        }
+        marker[i] = m;
+        if let Some((p, v)) = confirmed {
+          pivot[i] = v;
+          bars[i] = NumT::from(i - p).unwrap();
+        }
+      }
+    });
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::algo::{assert_vec_eq_nan, context::FLAG_SKIP_NAN};
+
+  const INPUT: [f64; 9] = [10.0, 11.0, 12.0, 10.0, 9.0, 10.0, 11.0, 13.0, 12.5];
+
+  #[test]
+  fn test_zig() {
+    let mut zig = vec![0.0; INPUT.len()];
+    let mut marker = vec![0.0; INPUT.len()];
+    let ctx = Context::new(0, 0, 0);
+    ta_zig(&ctx, &mut zig, &mut marker, &INPUT, 10.0).unwrap();
+    // trough 10 at 0 confirmed by 11 (+10%), peak 12 at 2 confirmed by 10
+    // (-16.7%), trough 9 at 4 confirmed by 10 (+11.1%), last leg up to 13 at
+    // 7 then the last bar
+    let (a, b) = (9.0 + 4.0 / 3.0, 9.0 + 8.0 / 3.0);
+    assert_vec_eq_nan(&zig, &[10.0, 11.0, 12.0, 10.5, 9.0, a, b, 13.0, 12.5]);
+    assert_vec_eq_nan(&marker, &[-1.0, 0.0, 1.0, 0.0, -1.0, 0.0, 0.0, 0.0, 0.0]);
+  }
+
+  #[test]
+  fn test_zig_confirmed() {
+    let mut pivot = vec![0.0; INPUT.len()];
+    let mut marker = vec![0.0; INPUT.len()];
+    let mut bars = vec![0.0; INPUT.len()];
+    let ctx = Context::new(0, 0, 0);
+    ta_zig_confirmed(&ctx, &mut pivot, &mut marker, &mut bars, &INPUT, 10.0).unwrap();
+    let nan = f64::NAN;
+    assert_vec_eq_nan(&marker, &[0.0, -1.0, 0.0, 1.0, 0.0, -1.0, 0.0, 0.0, 0.0]);
+    assert_vec_eq_nan(&pivot, &[nan, 10.0, 10.0, 12.0, 12.0, 9.0, 9.0, 9.0, 9.0]);
+    assert_vec_eq_nan(&bars, &[nan, 1.0, 2.0, 1.0, 2.0, 1.0, 2.0, 3.0, 4.0]);
+  }
+
+  #[test]
+  fn test_zig_nan() {
+    let input = [10.0, 12.0, 10.0, f64::NAN, 9.0, 10.0];
+    let mut zig = vec![0.0; input.len()];
+    let mut marker = vec![0.0; input.len()];
+    let nan = f64::NAN;
+
+    // restart after the gap
+    let ctx = Context::new(0, 0, 0);
+    ta_zig(&ctx, &mut zig, &mut marker, &input, 10.0).unwrap();
+    assert_vec_eq_nan(&zig, &[10.0, 12.0, 10.0, nan, 9.0, 10.0]);
+    assert_vec_eq_nan(&marker, &[-1.0, 1.0, 0.0, nan, -1.0, 0.0]);
+
+    // the falling leg goes on to 9 across the gap
+    let ctx = Context::new(0, 0, FLAG_SKIP_NAN);
+    ta_zig(&ctx, &mut zig, &mut marker, &input, 10.0).unwrap();
+    assert_vec_eq_nan(&zig, &[10.0, 12.0, 11.0, nan, 9.0, 10.0]);
+    assert_vec_eq_nan(&marker, &[-1.0, 1.0, 0.0, nan, -1.0, 0.0]);
+  }
+}
@@ -12,7 +12,7 @@ static _ALGO_CTX_: LazyLock<RwLock<Context>> = LazyLock::new(|| RwLock::new(Cont
 
 mod algo_impl {
   use log::debug;
-  use numpy::{PyReadonlyArray1, PyReadwriteArray1};
+  use numpy::{PyArray1, PyArray2, PyArray3, PyArrayMethods, PyReadonlyArray1, PyReadwriteArray1};
   use pyo3::{exceptions::PyValueError, prelude::*, types::PyList};
   use rayon::iter::{IndexedParallelIterator, IntoParallelIterator, ParallelIterator};
 
@@ -173,6 +173,193 @@ mod algo_impl {
     }
   }
 
+  /// IC decay of `horizons` forward returns
+  ///
+  /// Returns the (horizons, T) IC matrix, Spearman correlation when `rank`.
+  ///
+  #[pyfunction]
+  pub fn ic_decay<'py>(
+    py: Python<'py>,
+    factor: PyReadonlyArray1<'py, f64>,
+    price: PyReadonlyArray1<'py, f64>,
+    horizons: usize,
+    rank: bool,
+  ) -> PyResult<Bound<'py, PyArray2<f64>>> {
+    let ctx = ctx(py);
+    let factor = factor.as_array();
+    let factor = factor
+      .as_slice()
+      .ok_or(PyValueError::new_err("failed to get slice"))?;
+    let price = price.as_array();
+    let price = price
+      .as_slice()
+      .ok_or(PyValueError::new_err("failed to get slice"))?;
+
+    let t = ctx.chunk_size(factor.len());
+    let mut r = vec![0.0; horizons * t];
+    if rank {
+      ta_rank_ic_decay(&ctx, &mut r, factor, price, horizons)
+    } else {
+      ta_ic_decay(&ctx, &mut r, factor, price, horizons)
+    }
+    .map_err(PyErr::from)?;
+
+    PyArray1::from_vec(py, r).reshape([horizons, t])
+  }
+
+  /// layer returns, NAV, turnover and top minus bottom spread
+  type LayerBacktest<'py> = (
+    Bound<'py, PyArray2<f64>>,
+    Bound<'py, PyArray2<f64>>,
+    Bound<'py, PyArray2<f64>>,
+    Bound<'py, PyArray1<f64>>,
+  );
+
+  /// Quantile portfolio (layered) backtest
+  ///
+  /// Returns (layers, T) layer returns, NAV and turnover, and the T spread series.
+  ///
+  #[pyfunction]
+  pub fn layer_backtest<'py>(
+    py: Python<'py>,
+    factor: PyReadonlyArray1<'py, f64>,
+    returns: PyReadonlyArray1<'py, f64>,
+    weights: PyReadonlyArray1<'py, f64>,
+    layers: usize,
+  ) -> PyResult<LayerBacktest<'py>> {
+    let ctx = ctx(py);
+    let factor = factor.as_array();
+    let factor = factor
+      .as_slice()
+      .ok_or(PyValueError::new_err("failed to get slice"))?;
+    let returns = returns.as_array();
+    let returns = returns
+      .as_slice()
+      .ok_or(PyValueError::new_err("failed to get slice"))?;
+    let weights = weights.as_array();
+    let weights = weights
+      .as_slice()
+      .ok_or(PyValueError::new_err("failed to get slice"))?;
+
+    let t = ctx.chunk_size(factor.len());
+    let mut ret = vec![0.0; layers * t];
+    let mut nav = vec![0.0; layers * t];
+    let mut turnover = vec![0.0; layers * t];
+    let mut spread = vec![0.0; t];
+    ta_layer_backtest(
+      &ctx,
+      &mut ret,
+      &mut nav,
+      &mut turnover,
+      &mut spread,
+      factor,
+      returns,
+      weights,
+      layers,
+    )
+    .map_err(PyErr::from)?;
+
+    Ok((
+      PyArray1::from_vec(py, ret).reshape([layers, t])?,
+      PyArray1::from_vec(py, nav).reshape([layers, t])?,
+      PyArray1::from_vec(py, turnover).reshape([layers, t])?,
+      PyArray1::from_vec(py, spread),
+    ))
+  }
+
+  /// covariance matrices and shrinkage intensities
+  type CovMatrix<'py> = (Bound<'py, PyArray3<f64>>, Bound<'py, PyArray1<f64>>);
+
+  /// Covariance matrix of securities at chosen dates
+  ///
+  /// Returns the (dates, N, N) covariance matrices and the shrinkage intensity
+  /// of each date, N is the groups of context.
+  ///
+  #[pyfunction]
+  pub fn cov_matrix<'py>(
+    py: Python<'py>,
+    input: PyReadonlyArray1<'py, f64>,
+    dates: Vec<usize>,
+    periods: usize,
+    halflife: f64,
+    target: usize,
+  ) -> PyResult<CovMatrix<'py>> {
+    let ctx = ctx(py);
+    let input = input.as_array();
+    let input = input
+      .as_slice()
+      .ok_or(PyValueError::new_err("failed to get slice"))?;
+
+    let n = ctx.groups();
+    let mut r = vec![0.0; dates.len() * n * n];
+    let mut shrinkage = vec![0.0; dates.len()];
+    ta_cov_matrix(
+      &ctx,
+      &mut r,
+      &mut shrinkage,
+      input,
+      &dates,
+      periods,
+      halflife,
+      target,
+    )
+    .map_err(PyErr::from)?;
+
+    let r = PyArray1::from_vec(py, r).reshape([dates.len(), n, n])?;
+    Ok((r, PyArray1::from_vec(py, shrinkage)))
+  }
+
+  /// factor returns, explained variance ratios, loadings and residuals
+  type Pca<'py> = (
+    Bound<'py, PyArray2<f64>>,
+    Bound<'py, PyArray2<f64>>,
+    Bound<'py, PyArray2<f64>>,
+    Bound<'py, PyArray1<f64>>,
+  );
+
+  /// Statistical factor model by rolling PCA
+  ///
+  /// Returns (k, T) factor returns, (k, T) explained variance ratios,
+  /// (k, N x T) loading panels and the residual panel.
+  ///
+  #[pyfunction]
+  pub fn pca<'py>(
+    py: Python<'py>,
+    input: PyReadonlyArray1<'py, f64>,
+    periods: usize,
+    k: usize,
+  ) -> PyResult<Pca<'py>> {
+    let ctx = ctx(py);
+    let input = input.as_array();
+    let input = input
+      .as_slice()
+      .ok_or(PyValueError::new_err("failed to get slice"))?;
+
+    let t = ctx.chunk_size(input.len());
+    let mut factors = vec![0.0; k * t];
+    let mut explained = vec![0.0; k * t];
+    let mut loadings = vec![0.0; k * input.len()];
+    let mut residuals = vec![0.0; input.len()];
+    ta_pca(
+      &ctx,
+      &mut factors,
+      &mut explained,
+      &mut loadings,
+      &mut residuals,
+      input,
+      periods,
+      k,
+    )
+    .map_err(PyErr::from)?;
+
+    Ok((
+      PyArray1::from_vec(py, factors).reshape([k, t])?,
+      PyArray1::from_vec(py, explained).reshape([k, t])?,
+      PyArray1::from_vec(py, loadings).reshape([k, input.len()])?,
+      PyArray1::from_vec(py, residuals),
+    ))
+  }
+
   include!(concat!(env!("OUT_DIR"), "/algo_bindings.rs"));
 }
 
@@ -182,6 +369,10 @@ fn _algo(m: &Bound<'_, PyModule>) -> PyResult<()> {
   pyo3_log::init();
 
   m.add_function(wrap_pyfunction!(ema, m)?)?;
+  m.add_function(wrap_pyfunction!(ic_decay, m)?)?;
+  m.add_function(wrap_pyfunction!(layer_backtest, m)?)?;
+  m.add_function(wrap_pyfunction!(cov_matrix, m)?)?;
+  m.add_function(wrap_pyfunction!(pca, m)?)?;
   m.add_function(wrap_pyfunction!(set_ctx, m)?)?;
   algo_impl::register_functions(m)?;
   Ok(())